@@ -5,7 +5,7 @@ use uv_normalize::PackageName;
 
 pub use build_tag::{BuildTag, BuildTagError};
 pub use source_dist::{SourceDistExtension, SourceDistFilename, SourceDistFilenameError};
-pub use wheel::{WheelFilename, WheelFilenameError};
+pub use wheel::{WheelFilename, WheelFilenameError, WheelSelection};
 
 mod build_tag;
 mod source_dist;
@@ -222,6 +222,48 @@ impl Serialize for WheelFilename {
     }
 }
 
+/// The compatibility of a set of candidate wheels with a set of [`Tags`], as computed by
+/// [`WheelSelection::new`].
+#[derive(Debug)]
+pub struct WheelSelection<'a> {
+    /// Each candidate wheel, along with its compatibility, in the order given.
+    pub wheels: Vec<(&'a WheelFilename, TagCompatibility)>,
+}
+
+impl<'a> WheelSelection<'a> {
+    /// Determine the compatibility of each of the given wheels with the given tags, using the same
+    /// logic as the resolver.
+    pub fn new(candidates: impl IntoIterator<Item = &'a WheelFilename>, tags: &Tags) -> Self {
+        Self {
+            wheels: candidates
+                .into_iter()
+                .map(|filename| (filename, filename.compatibility(tags)))
+                .collect(),
+        }
+    }
+
+    /// Returns the compatible wheels, in the order given.
+    pub fn compatible(&self) -> impl Iterator<Item = &'a WheelFilename> + '_ {
+        self.wheels
+            .iter()
+            .filter(|(_, compatibility)| compatibility.is_compatible())
+            .map(|(filename, _)| *filename)
+    }
+
+    /// Returns the most compatible wheel (i.e., the wheel with the highest-priority tags), if any
+    /// are compatible.
+    ///
+    /// Ties are broken in favor of the earliest candidate.
+    pub fn best(&self) -> Option<&'a WheelFilename> {
+        self.wheels
+            .iter()
+            .filter(|(_, compatibility)| compatibility.is_compatible())
+            .rev()
+            .max_by(|(_, a), (_, b)| a.cmp(b))
+            .map(|(filename, _)| *filename)
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum WheelFilenameError {
     #[error("The wheel filename \"{0}\" is invalid: {1}")]
@@ -236,6 +278,8 @@ pub enum WheelFilenameError {
 
 #[cfg(test)]
 mod tests {
+    use platform_tags::{Arch, IncompatibleTag, Os, Platform};
+
     use super::*;
 
     #[test]
@@ -332,4 +376,110 @@ mod tests {
             );
         }
     }
+
+    /// Parse each of the given wheel filenames.
+    fn wheels(filenames: &[&str]) -> Vec<WheelFilename> {
+        filenames
+            .iter()
+            .map(|filename| WheelFilename::from_str(filename).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn select_manylinux() {
+        let tags = Tags::from_platform(
+            &Platform::new(
+                Os::Manylinux {
+                    major: 2,
+                    minor: 28,
+                },
+                Arch::X86_64,
+            ),
+            (3, 12),
+        )
+        .unwrap();
+        let candidates = wheels(&[
+            "numpy-1.26.4-cp312-cp312-manylinux_2_17_x86_64.manylinux2014_x86_64.whl",
+            "numpy-1.26.4-cp312-cp312-musllinux_1_1_x86_64.whl",
+            "numpy-1.26.4-cp311-cp311-manylinux_2_17_x86_64.manylinux2014_x86_64.whl",
+            "numpy-1.26.4-cp312-cp312-manylinux_2_17_aarch64.manylinux2014_aarch64.whl",
+        ]);
+        let selection = WheelSelection::new(&candidates, &tags);
+
+        assert_eq!(
+            selection.compatible().collect::<Vec<_>>(),
+            vec![&candidates[0]]
+        );
+        assert_eq!(selection.best(), Some(&candidates[0]));
+
+        // Incompatible wheels should report the closest match.
+        assert_eq!(
+            selection.wheels[1].1,
+            TagCompatibility::Incompatible(IncompatibleTag::Platform)
+        );
+        assert_eq!(
+            selection.wheels[2].1,
+            TagCompatibility::Incompatible(IncompatibleTag::Abi)
+        );
+        assert_eq!(
+            selection.wheels[3].1,
+            TagCompatibility::Incompatible(IncompatibleTag::Platform)
+        );
+    }
+
+    #[test]
+    fn select_macos_universal2() {
+        let tags = Tags::from_platform(
+            &Platform::new(
+                Os::Macos {
+                    major: 14,
+                    minor: 0,
+                },
+                Arch::Aarch64,
+            ),
+            (3, 12),
+        )
+        .unwrap();
+        let candidates = wheels(&[
+            "black-24.4.0-cp312-cp312-macosx_10_9_x86_64.whl",
+            "black-24.4.0-cp312-cp312-macosx_10_9_universal2.whl",
+            "black-24.4.0-cp312-cp312-macosx_11_0_arm64.whl",
+            "black-24.4.0-py3-none-any.whl",
+        ]);
+        let selection = WheelSelection::new(&candidates, &tags);
+
+        // The `universal2` wheel is compatible, but the native `arm64` wheel is preferred.
+        assert_eq!(
+            selection.compatible().collect::<Vec<_>>(),
+            vec![&candidates[1], &candidates[2], &candidates[3]]
+        );
+        assert_eq!(selection.best(), Some(&candidates[2]));
+
+        // Without the native wheel, the `universal2` wheel is preferred over the pure-Python wheel.
+        let selection =
+            WheelSelection::new([&candidates[0], &candidates[1], &candidates[3]], &tags);
+        assert_eq!(selection.best(), Some(&candidates[1]));
+    }
+
+    #[test]
+    fn select_pure_python() {
+        let tags = Tags::from_platform(&Platform::new(Os::Windows, Arch::X86_64), (3, 8)).unwrap();
+        let candidates = wheels(&[
+            "six-1.16.0-py2.py3-none-any.whl",
+            "typing_extensions-4.11.0-py3-none-any.whl",
+            "tomli-2.0.1-py311-none-any.whl",
+        ]);
+        let selection = WheelSelection::new(&candidates, &tags);
+
+        // Pure-Python wheels are compatible with any platform, but not with newer Pythons.
+        assert_eq!(
+            selection.compatible().collect::<Vec<_>>(),
+            vec![&candidates[0], &candidates[1]]
+        );
+        assert_eq!(
+            selection.wheels[2].1,
+            TagCompatibility::Incompatible(IncompatibleTag::Python)
+        );
+        assert!(selection.best().is_some());
+    }
 }
@@ -457,6 +457,14 @@ impl Dist {
             Self::Source(source_dist) => source_dist.version(),
         }
     }
+
+    /// Returns the [`WheelFilename`], if the distribution is a wheel.
+    pub fn wheel_filename(&self) -> Option<&WheelFilename> {
+        match self {
+            Self::Built(wheel) => Some(wheel.wheel_filename()),
+            Self::Source(_) => None,
+        }
+    }
 }
 
 impl BuiltDist {
@@ -489,6 +497,17 @@ impl BuiltDist {
             Self::Path(wheel) => &wheel.filename.version,
         }
     }
+
+    /// Returns the [`WheelFilename`] of the wheel, including its Python, ABI, and platform tags.
+    ///
+    /// For registry distributions, this is the filename of the best compatible wheel.
+    pub fn wheel_filename(&self) -> &WheelFilename {
+        match self {
+            Self::Registry(wheels) => &wheels.best_wheel().filename,
+            Self::DirectUrl(wheel) => &wheel.filename,
+            Self::Path(wheel) => &wheel.filename,
+        }
+    }
 }
 
 impl SourceDist {
@@ -207,9 +207,12 @@ impl PrioritizedDist {
         hashes: Vec<HashDigest>,
         compatibility: WheelCompatibility,
     ) {
-        // Track the highest-priority wheel.
-        if let Some((.., existing_compatibility)) = self.best_wheel() {
-            if compatibility.is_more_compatible(existing_compatibility) {
+        // Track the highest-priority wheel. Between equally compatible wheels, break ties
+        // deterministically, such that the choice doesn't depend on the order of the index.
+        if let Some((existing, existing_compatibility)) = self.best_wheel() {
+            if compatibility.is_more_compatible(existing_compatibility)
+                || (compatibility == *existing_compatibility && is_preferred_wheel(&dist, existing))
+            {
                 self.0.best_wheel_index = Some(self.0.wheels.len());
             }
         } else {
@@ -368,6 +371,19 @@ impl PrioritizedDist {
     }
 }
 
+/// Return `true` if the wheel should be preferred over an equally compatible wheel.
+///
+/// Prefers the wheel with the most specific tags (i.e., the fewest compressed tags), then the
+/// lexicographically smallest filename.
+fn is_preferred_wheel(wheel: &RegistryBuiltWheel, other: &RegistryBuiltWheel) -> bool {
+    let specificity = |wheel: &RegistryBuiltWheel| {
+        wheel.filename.python_tag.len()
+            + wheel.filename.abi_tag.len()
+            + wheel.filename.platform_tag.len()
+    };
+    (specificity(wheel), &wheel.file.filename) < (specificity(other), &other.file.filename)
+}
+
 impl<'a> CompatibleDist<'a> {
     /// Return the [`ResolvedDistRef`] to use during resolution.
     pub fn for_resolution(&self) -> ResolvedDistRef<'a> {
@@ -520,3 +536,66 @@ impl IncompatibleWheel {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use distribution_filename::WheelFilename;
+    use platform_tags::TagPriority;
+
+    use crate::{
+        File, FileLocation, HashComparison, IndexUrl, PrioritizedDist, RegistryBuiltWheel,
+        WheelCompatibility,
+    };
+
+    fn wheel(filename: &str) -> RegistryBuiltWheel {
+        RegistryBuiltWheel {
+            filename: WheelFilename::from_str(filename).unwrap(),
+            file: Box::new(File {
+                dist_info_metadata: false,
+                filename: filename.to_string(),
+                hashes: vec![],
+                requires_python: None,
+                size: None,
+                upload_time_utc_ms: None,
+                url: FileLocation::AbsoluteUrl(format!(
+                    "https://files.pythonhosted.org/packages/{filename}"
+                )),
+                yanked: None,
+            }),
+            index: IndexUrl::from_str("https://pypi.org/simple").unwrap(),
+        }
+    }
+
+    /// Among equally compatible wheels, the same wheel should be selected regardless of the order
+    /// in which they're inserted.
+    #[test]
+    fn best_wheel_tie_break() {
+        let compatibility = || {
+            WheelCompatibility::Compatible(
+                HashComparison::Matched,
+                TagPriority::try_from(1).unwrap(),
+                None,
+            )
+        };
+        let wheels = [
+            "example-1.0.0-py3-none-manylinux_2_17_x86_64.manylinux2014_x86_64.whl",
+            "example-1.0.0-py3-none-manylinux_2_17_x86_64.whl",
+            "example-1.0.0-py2.py3-none-manylinux_2_17_x86_64.whl",
+        ];
+
+        for order in [[0, 1, 2], [2, 1, 0], [1, 0, 2], [2, 0, 1]] {
+            let mut prioritized =
+                PrioritizedDist::from_built(wheel(wheels[order[0]]), vec![], compatibility());
+            for index in &order[1..] {
+                prioritized.insert_built(wheel(wheels[*index]), vec![], compatibility());
+            }
+            let (best, _) = prioritized.best_wheel().unwrap();
+            assert_eq!(
+                best.file.filename,
+                "example-1.0.0-py3-none-manylinux_2_17_x86_64.whl"
+            );
+        }
+    }
+}
@@ -1,12 +1,13 @@
 use std::fmt::{Display, Formatter};
 
+use distribution_filename::WheelFilename;
 use pep508_rs::PackageName;
 use pypi_types::Yanked;
 
 use crate::{
-    BuiltDist, Dist, DistributionId, DistributionMetadata, Identifier, IndexUrl, InstalledDist,
-    Name, PrioritizedDist, RegistryBuiltWheel, RegistrySourceDist, ResourceId, SourceDist,
-    VersionOrUrlRef,
+    BuiltDist, Dist, DistributionId, DistributionMetadata, File, Identifier, IndexUrl,
+    InstalledDist, Name, PrioritizedDist, RegistryBuiltWheel, RegistrySourceDist, ResourceId,
+    SourceDist, VersionOrUrlRef,
 };
 
 /// A distribution that can be used for resolution and installation.
@@ -72,6 +73,25 @@ impl ResolvedDist {
             Self::Installed(_) => None,
         }
     }
+
+    /// Returns the [`File`] of the resolved artifact, if the distribution was resolved from a
+    /// registry.
+    pub fn file(&self) -> Option<&File> {
+        match self {
+            Self::Installable(dist) => dist.file(),
+            Self::Installed(_) => None,
+        }
+    }
+
+    /// Returns the [`WheelFilename`] of the resolved wheel, if the distribution is a wheel.
+    ///
+    /// Returns `None` for source distributions and already-installed distributions.
+    pub fn wheel_filename(&self) -> Option<&WheelFilename> {
+        match self {
+            Self::Installable(dist) => dist.wheel_filename(),
+            Self::Installed(_) => None,
+        }
+    }
 }
 
 impl ResolvedDistRef<'_> {
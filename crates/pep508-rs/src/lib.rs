@@ -52,7 +52,8 @@ use pep440_rs::{Version, VersionSpecifier, VersionSpecifiers};
 pub use unnamed::{UnnamedRequirement, UnnamedRequirementUrl};
 pub use uv_normalize::{ExtraName, InvalidNameError, PackageName};
 pub use verbatim_url::{
-    expand_env_vars, split_scheme, strip_host, Scheme, VerbatimUrl, VerbatimUrlError,
+    expand_env_vars, expand_project_root, split_scheme, strip_host, strip_project_root, Scheme,
+    VerbatimUrl, VerbatimUrlError,
 };
 
 mod cursor;
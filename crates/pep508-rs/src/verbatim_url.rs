@@ -321,6 +321,48 @@ pub fn expand_env_vars(s: &str) -> Cow<'_, str> {
     })
 }
 
+/// If the given path starts with the `${PROJECT_ROOT}` variable, return the remainder of the path
+/// (e.g., `/flask-3.0.3-py3-none-any.whl` for `${PROJECT_ROOT}/flask-3.0.3-py3-none-any.whl`).
+pub fn strip_project_root(path: &str) -> Option<&str> {
+    let rest = path.strip_prefix("${PROJECT_ROOT}")?;
+    if rest.is_empty() || rest.starts_with(['/', '\\']) {
+        Some(rest)
+    } else {
+        None
+    }
+}
+
+/// Substitute the given project root for a leading `${PROJECT_ROOT}` variable in a verbatim path
+/// or `file://` URL, leaving all other inputs untouched.
+///
+/// Unlike [`expand_env_vars`], the root is provided explicitly, rather than read from the
+/// environment or the current directory. When expanding a `file://` URL, the root is converted to
+/// use forward slashes (e.g., `C:\Users\ferris` becomes `file:///C:/Users/ferris`).
+pub fn expand_project_root<'a>(verbatim: &'a str, root: &Path) -> Cow<'a, str> {
+    let root = root.to_string_lossy();
+    let root = root.trim_end_matches(['/', '\\']);
+
+    // Ex) `${PROJECT_ROOT}/flask-3.0.3-py3-none-any.whl`
+    if let Some(rest) = strip_project_root(verbatim) {
+        return Cow::Owned(format!("{root}{rest}"));
+    }
+
+    // Ex) `file://${PROJECT_ROOT}/flask-3.0.3-py3-none-any.whl`
+    if let Some(rest) = verbatim
+        .strip_prefix("file://")
+        .and_then(strip_project_root)
+    {
+        let root = root.replace('\\', "/");
+        let separator = if root.starts_with('/') { "" } else { "/" };
+        return Cow::Owned(format!(
+            "file://{separator}{root}{}",
+            rest.replace('\\', "/")
+        ));
+    }
+
+    Cow::Borrowed(verbatim)
+}
+
 /// Like [`Url::parse`], but only splits the scheme. Derived from the `url` crate.
 pub fn split_scheme(s: &str) -> Option<(&str, &str)> {
     /// <https://url.spec.whatwg.org/#c0-controls-and-space>
@@ -537,6 +579,58 @@ mod tests {
         assert_eq!(split_scheme("https:"), Some(("https", "")));
     }
 
+    #[test]
+    fn project_root() {
+        // Prefixed paths and URLs should be expanded.
+        assert_eq!(
+            expand_project_root(
+                "${PROJECT_ROOT}/flask-3.0.3-py3-none-any.whl",
+                Path::new("/home/ferris/project/")
+            ),
+            "/home/ferris/project/flask-3.0.3-py3-none-any.whl"
+        );
+        assert_eq!(
+            expand_project_root(
+                "file://${PROJECT_ROOT}/flask-3.0.3-py3-none-any.whl",
+                Path::new("/home/ferris/project")
+            ),
+            "file:///home/ferris/project/flask-3.0.3-py3-none-any.whl"
+        );
+        assert_eq!(
+            expand_project_root("${PROJECT_ROOT}", Path::new("/home/ferris/project")),
+            "/home/ferris/project"
+        );
+
+        // Windows separators should be preserved in paths, but normalized in URLs.
+        assert_eq!(
+            expand_project_root(
+                "${PROJECT_ROOT}\\flask-3.0.3-py3-none-any.whl",
+                Path::new("C:\\Users\\ferris\\project")
+            ),
+            "C:\\Users\\ferris\\project\\flask-3.0.3-py3-none-any.whl"
+        );
+        assert_eq!(
+            expand_project_root(
+                "file://${PROJECT_ROOT}\\flask-3.0.3-py3-none-any.whl",
+                Path::new("C:\\Users\\ferris\\project")
+            ),
+            "file:///C:/Users/ferris/project/flask-3.0.3-py3-none-any.whl"
+        );
+
+        // Unprefixed inputs should be left untouched.
+        for verbatim in [
+            "./flask-3.0.3-py3-none-any.whl",
+            "file:///home/ferris/project/flask-3.0.3-py3-none-any.whl",
+            "https://example.com/${PROJECT_ROOT}/flask-3.0.3-py3-none-any.whl",
+            "${PROJECT_ROOT_DIR}/flask-3.0.3-py3-none-any.whl",
+        ] {
+            assert!(matches!(
+                expand_project_root(verbatim, Path::new("/home/ferris/project")),
+                Cow::Borrowed(given) if given == verbatim
+            ));
+        }
+    }
+
     #[test]
     fn fragment() {
         assert_eq!(
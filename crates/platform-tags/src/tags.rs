@@ -181,6 +181,14 @@ impl Tags {
         Ok(Self::new(tags))
     }
 
+    /// Returns the compatible tags for CPython at the given version on the given platform.
+    ///
+    /// A shorthand for [`Tags::from_env`], for callers that only know the target platform and
+    /// Python version (e.g., to pre-filter wheels before resolution).
+    pub fn from_platform(platform: &Platform, python_version: (u8, u8)) -> Result<Self, TagsError> {
+        Self::from_env(platform, python_version, "cpython", python_version, false)
+    }
+
     /// Returns true when there exists at least one tag for this platform
     /// whose individual components all appear in each of the slices given.
     ///
@@ -21,7 +21,10 @@ use crate::{LenientVersionSpecifiers, VerbatimParsedUrl};
 /// This is a subset of the full metadata specification, and only includes the
 /// fields that are relevant to dependency resolution.
 ///
-/// At present, we support up to version 2.3 of the metadata specification.
+/// At present, we support up to version 2.3 of the metadata specification, along with the
+/// `License-Expression` and `License-File` fields introduced in version 2.4. For source
+/// distributions, the PEP 518 build requirements are also recorded, if known, though they aren't
+/// part of the core metadata.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "kebab-case")]
 pub struct Metadata23 {
@@ -32,6 +35,19 @@ pub struct Metadata23 {
     pub requires_dist: Vec<Requirement<VerbatimParsedUrl>>,
     pub requires_python: Option<VersionSpecifiers>,
     pub provides_extras: Vec<ExtraName>,
+    /// The SPDX license expression (Metadata 2.4 and later).
+    #[serde(default)]
+    pub license_expression: Option<String>,
+    /// The paths to the license files, relative to the `.dist-info` directory's `licenses`
+    /// subdirectory (Metadata 2.4 and later).
+    #[serde(default)]
+    pub license_files: Vec<String>,
+    /// The PEP 518 build requirements (i.e., `build-system.requires`) of a source distribution,
+    /// which are distinct from its runtime requirements (`requires_dist`).
+    ///
+    /// Always empty for built distributions, and for source distributions that don't declare any.
+    #[serde(default)]
+    pub build_requires: Vec<Requirement<VerbatimParsedUrl>>,
 }
 
 /// <https://github.com/PyO3/python-pkginfo-rs/blob/d719988323a0cfea86d4737116d7917f30e819e2/src/error.rs>
@@ -106,6 +122,8 @@ impl Metadata23 {
                 }
             })
             .collect::<Vec<_>>();
+        let license_expression = headers.get_first_value("License-Expression");
+        let license_files = headers.get_all_values("License-File").collect::<Vec<_>>();
 
         Ok(Self {
             name,
@@ -113,6 +131,9 @@ impl Metadata23 {
             requires_dist,
             requires_python,
             provides_extras,
+            license_expression,
+            license_files,
+            build_requires: Vec::new(),
         })
     }
 
@@ -181,6 +202,8 @@ impl Metadata23 {
                 }
             })
             .collect::<Vec<_>>();
+        let license_expression = headers.get_first_value("License-Expression");
+        let license_files = headers.get_all_values("License-File").collect::<Vec<_>>();
 
         Ok(Self {
             name,
@@ -188,12 +211,16 @@ impl Metadata23 {
             requires_dist,
             requires_python,
             provides_extras,
+            license_expression,
+            license_files,
+            build_requires: Vec::new(),
         })
     }
 
     /// Extract the metadata from a `pyproject.toml` file, as specified in PEP 621.
     pub fn parse_pyproject_toml(contents: &str) -> Result<Self, MetadataError> {
         let pyproject_toml: PyProjectToml = toml::from_str(contents)?;
+        let build_requires = pyproject_toml.build_requires();
 
         let project = pyproject_toml
             .project
@@ -246,8 +273,22 @@ impl Metadata23 {
             requires_dist,
             requires_python,
             provides_extras,
+            license_expression: None,
+            license_files: Vec::new(),
+            build_requires,
         })
     }
+
+    /// Read the PEP 518 build requirements (i.e., `build-system.requires`) from a source
+    /// distribution's `pyproject.toml` file.
+    ///
+    /// Returns an empty list if the `pyproject.toml` doesn't declare a `build-system`.
+    pub fn parse_build_requires(
+        pyproject_toml: &str,
+    ) -> Result<Vec<Requirement<VerbatimParsedUrl>>, MetadataError> {
+        let pyproject_toml: PyProjectToml = toml::from_str(pyproject_toml)?;
+        Ok(pyproject_toml.build_requires())
+    }
 }
 
 /// A `pyproject.toml` as specified in PEP 517.
@@ -256,6 +297,34 @@ impl Metadata23 {
 pub(crate) struct PyProjectToml {
     /// Project metadata
     pub(crate) project: Option<Project>,
+    /// Build-related data
+    pub(crate) build_system: Option<BuildSystem>,
+}
+
+impl PyProjectToml {
+    /// Return the PEP 518 build requirements, if any.
+    fn build_requires(&self) -> Vec<Requirement<VerbatimParsedUrl>> {
+        self.build_system
+            .as_ref()
+            .map(|build_system| {
+                build_system
+                    .requires
+                    .iter()
+                    .cloned()
+                    .map(Requirement::from)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// The `[build-system]` table of a `pyproject.toml`, as specified in PEP 518.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct BuildSystem {
+    /// The PEP 508 requirements needed to build the project.
+    #[serde(default)]
+    pub(crate) requires: Vec<LenientRequirement>,
 }
 
 /// PEP 621 project metadata.
@@ -400,6 +469,23 @@ mod tests {
         assert!(matches!(meta, Err(MetadataError::InvalidName(_))));
     }
 
+    #[test]
+    fn test_parse_metadata_license() {
+        let s = "Metadata-Version: 2.4\nName: asdf\nVersion: 1.0\nLicense-Expression: MIT OR Apache-2.0\nLicense-File: LICENSE-MIT\nLicense-File: LICENSE-APACHE";
+        let meta = Metadata23::parse_metadata(s.as_bytes()).unwrap();
+        assert_eq!(
+            meta.license_expression.as_deref(),
+            Some("MIT OR Apache-2.0")
+        );
+        assert_eq!(meta.license_files, vec!["LICENSE-MIT", "LICENSE-APACHE"]);
+
+        // Older metadata versions don't include either field.
+        let s = "Metadata-Version: 2.1\nName: asdf\nVersion: 1.0\nLicense: MIT";
+        let meta = Metadata23::parse_metadata(s.as_bytes()).unwrap();
+        assert_eq!(meta.license_expression, None);
+        assert!(meta.license_files.is_empty());
+    }
+
     #[test]
     fn test_parse_pkg_info() {
         let s = "Metadata-Version: 2.1";
@@ -512,4 +598,32 @@ mod tests {
         );
         assert_eq!(meta.provides_extras, vec!["dotenv".parse().unwrap()]);
     }
+
+    #[test]
+    fn test_parse_build_requires() {
+        let s = r#"
+            [project]
+            name = "asdf"
+            version = "1.0"
+            dependencies = ["foo"]
+
+            [build-system]
+            requires = ["setuptools>=42", "wheel"]
+            build-backend = "setuptools.build_meta"
+        "#;
+        let build_requires = vec!["setuptools>=42".parse().unwrap(), "wheel".parse().unwrap()];
+        assert_eq!(Metadata23::parse_build_requires(s).unwrap(), build_requires);
+
+        // The build requirements should be kept separate from the runtime requirements.
+        let meta = Metadata23::parse_pyproject_toml(s).unwrap();
+        assert_eq!(meta.requires_dist, vec!["foo".parse().unwrap()]);
+        assert_eq!(meta.build_requires, build_requires);
+
+        let s = r#"
+            [project]
+            name = "asdf"
+            version = "1.0"
+        "#;
+        assert!(Metadata23::parse_build_requires(s).unwrap().is_empty());
+    }
 }
@@ -1,3 +1,7 @@
+use std::env;
+use std::path::Path;
+use std::time::Duration;
+
 use tokio::process::Command;
 use tracing::{instrument, trace, warn};
 use url::Url;
@@ -11,24 +15,53 @@ use crate::credentials::Credentials;
 #[derive(Debug)]
 pub struct KeyringProvider {
     backend: KeyringProviderBackend,
+    /// The maximum amount of time to wait for a single keyring lookup.
+    timeout: Duration,
 }
 
+/// The default timeout for a single keyring lookup, in seconds.
+const DEFAULT_KEYRING_TIMEOUT: u64 = 30;
+
 #[derive(Debug)]
 pub enum KeyringProviderBackend {
     /// Use the `keyring` command to fetch credentials.
     Subprocess,
+    /// Use the given command, in place of `keyring`, to fetch credentials.
+    #[cfg(test)]
+    Command(std::path::PathBuf),
     #[cfg(test)]
     Dummy(std::collections::HashMap<(String, &'static str), &'static str>),
 }
 
 impl KeyringProvider {
     /// Create a new [`KeyringProvider::Subprocess`].
+    ///
+    /// Each lookup is limited to the number of seconds given by `UV_KEYRING_TIMEOUT`, if set.
     pub fn subprocess() -> Self {
+        let timeout = env::var("UV_KEYRING_TIMEOUT")
+            .ok()
+            .and_then(|value| {
+                value
+                    .parse::<u64>()
+                    .inspect_err(|_| {
+                        warn!("Ignoring invalid value from environment for UV_KEYRING_TIMEOUT. Expected integer number of seconds, got \"{value}\".");
+                    })
+                    .ok()
+            })
+            .unwrap_or(DEFAULT_KEYRING_TIMEOUT);
         Self {
             backend: KeyringProviderBackend::Subprocess,
+            timeout: Duration::from_secs(timeout),
         }
     }
 
+    /// Set the maximum amount of time to wait for a single keyring lookup.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
     /// Fetch credentials for the given [`Url`] from the keyring.
     ///
     /// Returns [`None`] if no password was found for the username or if any errors
@@ -54,7 +87,12 @@ impl KeyringProvider {
         trace!("Checking keyring for URL {url}");
         let mut password = match self.backend {
             KeyringProviderBackend::Subprocess => {
-                self.fetch_subprocess(url.as_str(), username).await
+                self.fetch_subprocess(Path::new("keyring"), url.as_str(), username)
+                    .await
+            }
+            #[cfg(test)]
+            KeyringProviderBackend::Command(ref program) => {
+                self.fetch_subprocess(program, url.as_str(), username).await
             }
             #[cfg(test)]
             KeyringProviderBackend::Dummy(ref store) => {
@@ -66,7 +104,14 @@ impl KeyringProvider {
             let host = url.host_str()?;
             trace!("Checking keyring for host {host}");
             password = match self.backend {
-                KeyringProviderBackend::Subprocess => self.fetch_subprocess(host, username).await,
+                KeyringProviderBackend::Subprocess => {
+                    self.fetch_subprocess(Path::new("keyring"), host, username)
+                        .await
+                }
+                #[cfg(test)]
+                KeyringProviderBackend::Command(ref program) => {
+                    self.fetch_subprocess(program, host, username).await
+                }
                 #[cfg(test)]
                 KeyringProviderBackend::Dummy(ref store) => self.fetch_dummy(store, host, username),
             };
@@ -76,13 +121,28 @@ impl KeyringProvider {
     }
 
     #[instrument(skip(self))]
-    async fn fetch_subprocess(&self, service_name: &str, username: &str) -> Option<String> {
-        let output = Command::new("keyring")
+    async fn fetch_subprocess(
+        &self,
+        program: &Path,
+        service_name: &str,
+        username: &str,
+    ) -> Option<String> {
+        // Kill the subprocess if the lookup times out (or is otherwise cancelled), to avoid
+        // leaving a hung `keyring` process behind.
+        let output = Command::new(program)
             .arg("get")
             .arg(service_name)
             .arg(username)
-            .output()
-            .await
+            .kill_on_drop(true)
+            .output();
+        let Ok(output) = tokio::time::timeout(self.timeout, output).await else {
+            warn!(
+                "Timed out after {}s waiting for `keyring` command; continuing without credentials",
+                self.timeout.as_secs()
+            );
+            return None;
+        };
+        let output = output
             .inspect_err(|err| warn!("Failure running `keyring` command: {err}"))
             .ok()?;
 
@@ -122,6 +182,16 @@ impl KeyringProvider {
                 iter.into_iter()
                     .map(|((service, username), password)| ((service.into(), username), password)),
             )),
+            timeout: Duration::from_secs(DEFAULT_KEYRING_TIMEOUT),
+        }
+    }
+
+    /// Create a new provider with [`KeyringProviderBackend::Command`].
+    #[cfg(test)]
+    pub fn command(program: impl Into<std::path::PathBuf>) -> Self {
+        Self {
+            backend: KeyringProviderBackend::Command(program.into()),
+            timeout: Duration::from_secs(DEFAULT_KEYRING_TIMEOUT),
         }
     }
 
@@ -132,6 +202,7 @@ impl KeyringProvider {
 
         Self {
             backend: KeyringProviderBackend::Dummy(HashMap::new()),
+            timeout: Duration::from_secs(DEFAULT_KEYRING_TIMEOUT),
         }
     }
 }
@@ -266,4 +337,24 @@ mod test {
         let credentials = keyring.fetch(&url, "bar").await;
         assert_eq!(credentials, None);
     }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn fetch_timeout() {
+        use std::os::unix::fs::PermissionsExt;
+
+        // Mimic a `keyring` command that hangs (e.g., on an interactive prompt).
+        let temp_dir = tempfile::tempdir().unwrap();
+        let program = temp_dir.path().join("keyring");
+        std::fs::write(&program, "#!/bin/sh\nsleep 60\n").unwrap();
+        std::fs::set_permissions(&program, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let url = Url::parse("https://example.com").unwrap();
+        let keyring = KeyringProvider::command(&program).with_timeout(Duration::from_millis(100));
+        let credentials =
+            tokio::time::timeout(Duration::from_secs(10), keyring.fetch(&url, "user"))
+                .await
+                .expect("keyring lookup should time out, rather than hang");
+        assert_eq!(credentials, None);
+    }
 }
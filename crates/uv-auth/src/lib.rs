@@ -9,6 +9,7 @@ use std::sync::Arc;
 use cache::CredentialsCache;
 use credentials::Credentials;
 
+pub use credentials::{CredentialsSource, ResolvedCredentials};
 pub use keyring::KeyringProvider;
 pub use middleware::AuthMiddleware;
 use once_cell::sync::Lazy;
@@ -0,0 +1,68 @@
+use std::future::Future;
+
+use reqwest::Response;
+use url::Url;
+
+use crate::{BaseClient, Error, ErrorKind};
+
+/// A blocking wrapper around a [`BaseClient`], for use from synchronous code.
+///
+/// Each request is driven to completion on a dedicated single-threaded runtime, such that the full
+/// middleware stack (authentication, retries, offline mode, local files, etc.) applies exactly as
+/// it does for the async client. The only caveat is that the middleware runs on the blocking
+/// client's runtime: for example, credentials fetched via the `keyring` subprocess block the
+/// calling thread until the subprocess exits or times out.
+///
+/// The blocking client must not be used from within an async context, as blocking on a runtime
+/// from within another runtime panics.
+#[derive(Debug)]
+pub struct BlockingClient {
+    client: BaseClient,
+    runtime: tokio::runtime::Runtime,
+}
+
+impl BlockingClient {
+    /// Create a [`BlockingClient`] that wraps the given [`BaseClient`].
+    ///
+    /// The [`BaseClient`] should be dedicated to the blocking client, as pooled connections are
+    /// tied to the runtime that created them.
+    pub(crate) fn new(client: BaseClient) -> Result<Self, Error> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(ErrorKind::Io)?;
+        Ok(Self { client, runtime })
+    }
+
+    /// The underlying [`BaseClient`].
+    pub fn client(&self) -> &BaseClient {
+        &self.client
+    }
+
+    /// Send a `GET` request to the given URL, blocking until the response headers are received.
+    pub fn get(&self, url: Url) -> Result<Response, Error> {
+        self.block_on(self.client.send_get(url))
+    }
+
+    /// Send a `HEAD` request to the given URL, blocking until the response headers are received.
+    pub fn head(&self, url: Url) -> Result<Response, Error> {
+        self.block_on(self.client.send_head(url))
+    }
+
+    /// Read the full body of the given response, blocking until it's received.
+    pub fn bytes(&self, response: Response) -> Result<Vec<u8>, Error> {
+        let bytes = self.block_on(response.bytes()).map_err(ErrorKind::from)?;
+        Ok(bytes.to_vec())
+    }
+
+    /// Read the full body of the given response as text, blocking until it's received.
+    pub fn text(&self, response: Response) -> Result<String, Error> {
+        Ok(self.block_on(response.text()).map_err(ErrorKind::from)?)
+    }
+
+    /// Run an arbitrary future (e.g., a request built via [`BaseClient::client`]) to completion
+    /// on the blocking client's runtime.
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+}
@@ -0,0 +1,62 @@
+use std::io;
+
+use futures::{Stream, StreamExt};
+use reqwest::Response;
+
+use crate::{Error, ErrorKind};
+
+/// The maximum number of bytes to pre-allocate for a response body based on its declared
+/// `Content-Length`, such that a misbehaving server can't trigger an excessive allocation.
+pub(crate) const MAX_PREALLOCATION: u64 = 16 * 1024 * 1024;
+
+/// Stream the body of the response, failing with an [`ErrorKind::ContentLengthMismatch`] error if
+/// the number of bytes received doesn't match the declared `Content-Length` (e.g., if the download
+/// was truncated), rather than silently returning a partial body.
+///
+/// Responses without a declared length aren't checked. Errors from the underlying body are
+/// converted with `map_err`; a mismatch is reported as an [`io::Error`] wrapping an [`Error`].
+pub fn checked_bytes_stream<F>(
+    response: Response,
+    map_err: F,
+) -> impl Stream<Item = io::Result<impl AsRef<[u8]>>> + Unpin
+where
+    F: FnMut(reqwest::Error) -> io::Error,
+{
+    let url = response.url().clone();
+    let expected = response.content_length();
+    Box::pin(futures::stream::unfold(
+        (response.bytes_stream(), map_err, 0u64, false),
+        move |(mut stream, mut map_err, received, done)| {
+            let url = url.clone();
+            async move {
+                if done {
+                    return None;
+                }
+                match stream.next().await {
+                    Some(Ok(chunk)) => {
+                        let received = received + chunk.len() as u64;
+                        Some((Ok(chunk), (stream, map_err, received, false)))
+                    }
+                    Some(Err(err)) => {
+                        let err = map_err(err);
+                        Some((Err(err), (stream, map_err, received, true)))
+                    }
+                    None => match expected {
+                        Some(expected) if expected != received => {
+                            let err = io::Error::new(
+                                io::ErrorKind::UnexpectedEof,
+                                Error::from(ErrorKind::ContentLengthMismatch {
+                                    url,
+                                    expected,
+                                    received,
+                                }),
+                            );
+                            Some((Err(err), (stream, map_err, received, true)))
+                        }
+                        _ => None,
+                    },
+                }
+            }
+        },
+    ))
+}
@@ -425,6 +425,7 @@ impl CachedClient {
         Ok(match cached.cache_policy.before_request(&mut req) {
             BeforeRequest::Fresh => {
                 debug!("Found fresh response for: {}", req.url());
+                self.0.record_cache_hit();
                 CachedResponse::FreshCache(cached)
             }
             BeforeRequest::Stale(new_cache_policy_builder) => match cache_control {
@@ -435,6 +436,7 @@ impl CachedClient {
                 }
                 CacheControl::AllowStale => {
                     debug!("Found stale (but allowed) response for: {}", req.url());
+                    self.0.record_cache_hit();
                     CachedResponse::FreshCache(cached)
                 }
             },
@@ -475,6 +477,7 @@ impl CachedClient {
         {
             AfterResponse::NotModified(new_policy) => {
                 debug!("Found not-modified response for: {url}");
+                self.0.record_cache_hit();
                 Ok(CachedResponse::NotModified {
                     cached,
                     new_policy: Box::new(new_policy),
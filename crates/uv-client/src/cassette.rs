@@ -0,0 +1,231 @@
+//! Record HTTP interactions to a file (a "cassette"), and replay them later, for deterministic
+//! tests that don't depend on a live index.
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use http::Extensions;
+use reqwest::{Request, Response, ResponseBuilderExt};
+use reqwest_middleware::{Middleware, Next};
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+use url::Url;
+
+/// Headers that are never written to a cassette, since they may contain credentials.
+const SENSITIVE_HEADERS: &[&str] = &[
+    "authorization",
+    "cookie",
+    "proxy-authorization",
+    "set-cookie",
+    "www-authenticate",
+];
+
+/// Whether to record HTTP interactions to a cassette, or replay them from it.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum CassetteMode {
+    /// Send requests over the network, and record each interaction to the cassette, replacing
+    /// any existing cassette.
+    Record,
+    /// Serve responses from the cassette, without sending any requests over the network. Requests
+    /// that weren't recorded fail with an error.
+    Replay,
+}
+
+/// A recorded request and its response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Interaction {
+    method: String,
+    url: String,
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+/// An error indicating that a request wasn't recorded in the cassette being replayed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CassetteMissError {
+    pub(crate) method: String,
+    pub(crate) url: Url,
+    pub(crate) path: PathBuf,
+}
+
+impl std::fmt::Display for CassetteMissError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "No response was recorded for `{} {}` in the cassette at: `{}`",
+            self.method,
+            self.url,
+            self.path.display()
+        )
+    }
+}
+
+impl std::error::Error for CassetteMissError {}
+
+/// Remove any credentials from a URL, before it's written to a cassette or used to look up a
+/// recorded interaction.
+fn scrub_url(url: &Url) -> String {
+    let mut url = url.clone();
+    let _ = url.set_username("");
+    let _ = url.set_password(None);
+    url.to_string()
+}
+
+/// A middleware that sends each request over the network, and records it, along with its
+/// response, to a cassette.
+///
+/// The cassette is rewritten after each interaction, such that it's complete even if the client
+/// is never dropped. Sensitive headers (e.g., `Authorization`) and URL credentials are scrubbed.
+pub(crate) struct CassetteRecordMiddleware {
+    path: PathBuf,
+    interactions: Mutex<Vec<Interaction>>,
+}
+
+impl CassetteRecordMiddleware {
+    pub(crate) fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            interactions: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for CassetteRecordMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        let method = req.method().to_string();
+        let url = scrub_url(req.url());
+        let response = next.run(req, extensions).await?;
+
+        // Read the body, such that it can be recorded and then returned to the caller.
+        let status = response.status();
+        let version = response.version();
+        let response_url = response.url().clone();
+        let headers = response.headers().clone();
+        let body = response.bytes().await?;
+
+        let interaction = Interaction {
+            method,
+            url,
+            status: status.as_u16(),
+            headers: headers
+                .iter()
+                .filter(|(name, _)| !SENSITIVE_HEADERS.contains(&name.as_str()))
+                .filter_map(|(name, value)| {
+                    Some((name.to_string(), value.to_str().ok()?.to_string()))
+                })
+                .collect(),
+            body: body.to_vec(),
+        };
+        let contents = {
+            let mut interactions = self.interactions.lock().unwrap();
+            interactions.push(interaction);
+            serde_json::to_vec_pretty(&*interactions)
+                .map_err(reqwest_middleware::Error::middleware)?
+        };
+        if let Some(parent) = self.path.parent() {
+            fs_err::tokio::create_dir_all(parent)
+                .await
+                .map_err(reqwest_middleware::Error::middleware)?;
+        }
+        fs_err::tokio::write(&self.path, contents)
+            .await
+            .map_err(reqwest_middleware::Error::middleware)?;
+
+        let mut builder = http::Response::builder()
+            .status(status)
+            .version(version)
+            .url(response_url);
+        if let Some(builder_headers) = builder.headers_mut() {
+            *builder_headers = headers;
+        }
+        let response = builder
+            .body(body)
+            .map_err(reqwest_middleware::Error::middleware)?;
+        Ok(Response::from(response))
+    }
+}
+
+/// A middleware that serves each request from a cassette, without sending it over the network.
+///
+/// If a request was recorded more than once (e.g., across retries), the last response is served.
+pub(crate) struct CassetteReplayMiddleware {
+    path: PathBuf,
+    interactions: Result<FxHashMap<(String, String), Interaction>, String>,
+}
+
+impl CassetteReplayMiddleware {
+    pub(crate) fn new(path: PathBuf) -> Self {
+        let interactions = read_cassette(&path).map_err(|err| err.to_string());
+        Self { path, interactions }
+    }
+}
+
+/// Read the interactions from a cassette, keyed by method and URL.
+fn read_cassette(
+    path: &Path,
+) -> Result<FxHashMap<(String, String), Interaction>, Box<dyn std::error::Error>> {
+    let contents = fs_err::read(path)?;
+    let interactions: Vec<Interaction> = serde_json::from_slice(&contents)?;
+    debug!(
+        "Replaying {} interactions from cassette: {}",
+        interactions.len(),
+        path.display()
+    );
+    Ok(interactions
+        .into_iter()
+        .map(|interaction| {
+            (
+                (interaction.method.clone(), interaction.url.clone()),
+                interaction,
+            )
+        })
+        .collect())
+}
+
+#[async_trait::async_trait]
+impl Middleware for CassetteReplayMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        _extensions: &mut Extensions,
+        _next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        let interactions = self.interactions.as_ref().map_err(|err| {
+            reqwest_middleware::Error::Middleware(anyhow::anyhow!(
+                "Failed to read cassette at `{}`: {err}",
+                self.path.display()
+            ))
+        })?;
+
+        let key = (req.method().to_string(), scrub_url(req.url()));
+        let Some(interaction) = interactions.get(&key) else {
+            return Err(reqwest_middleware::Error::Middleware(
+                CassetteMissError {
+                    method: key.0,
+                    url: req.url().clone(),
+                    path: self.path.clone(),
+                }
+                .into(),
+            ));
+        };
+
+        let mut builder = http::Response::builder()
+            .status(interaction.status)
+            .url(req.url().clone());
+        for (name, value) in &interaction.headers {
+            builder = builder.header(name, value);
+        }
+        let response = builder
+            .body(interaction.body.clone())
+            .map_err(reqwest_middleware::Error::middleware)?;
+        Ok(Response::from(response))
+    }
+}
@@ -0,0 +1,112 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use pep508_rs::MarkerEnvironment;
+use platform_tags::Platform;
+
+/// A snapshot of a (possibly remote) target environment, used to build a client whose linehaul
+/// and marker-driven behavior reflect that environment rather than the host (e.g., to reproduce a
+/// user's bug report).
+///
+/// Snapshots are stored as JSON, with a `version` field to allow the format to evolve:
+///
+/// ```json
+/// {
+///   "version": 1,
+///   "markers": {
+///     "implementation_name": "cpython",
+///     "implementation_version": "3.12.2",
+///     "os_name": "posix",
+///     "platform_machine": "x86_64",
+///     "platform_python_implementation": "CPython",
+///     "platform_release": "6.5.0-1016-azure",
+///     "platform_system": "Linux",
+///     "platform_version": "#16~22.04.1-Ubuntu SMP Fri Feb 16 15:42:02 UTC 2024",
+///     "python_full_version": "3.12.2",
+///     "python_version": "3.12",
+///     "sys_platform": "linux"
+///   },
+///   "platform": {
+///     "os": { "name": "manylinux", "major": 2, "minor": 38 },
+///     "arch": "x86_64"
+///   }
+/// }
+/// ```
+///
+/// The `platform` field is optional.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnvironmentSnapshot {
+    version: u32,
+    markers: MarkerEnvironment,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    platform: Option<Platform>,
+}
+
+impl EnvironmentSnapshot {
+    /// The current version of the snapshot format.
+    pub const VERSION: u32 = 1;
+
+    /// Capture a snapshot of the given environment.
+    pub fn new(markers: MarkerEnvironment, platform: Option<Platform>) -> Self {
+        Self {
+            version: Self::VERSION,
+            markers,
+            platform,
+        }
+    }
+
+    /// Read a snapshot from the JSON file at the given path.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, EnvironmentSnapshotError> {
+        let contents = fs_err::read_to_string(path.as_ref())?;
+        Self::from_json(&contents)
+    }
+
+    /// Parse a snapshot from a JSON string.
+    pub fn from_json(contents: &str) -> Result<Self, EnvironmentSnapshotError> {
+        // Check the version before parsing the rest of the snapshot, such that snapshots from
+        // newer versions are reported as such, rather than as malformed.
+        #[derive(Deserialize)]
+        struct Versioned {
+            version: u32,
+        }
+
+        let Versioned { version } = serde_json::from_str(contents)?;
+        if version != Self::VERSION {
+            return Err(EnvironmentSnapshotError::UnsupportedVersion(version));
+        }
+        Ok(serde_json::from_str(contents)?)
+    }
+
+    /// Write the snapshot to the given path, as JSON.
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<(), EnvironmentSnapshotError> {
+        let contents = serde_json::to_string_pretty(self)?;
+        fs_err::write(path.as_ref(), contents)?;
+        Ok(())
+    }
+
+    /// The marker environment of the target environment.
+    pub fn markers(&self) -> &MarkerEnvironment {
+        &self.markers
+    }
+
+    /// The platform of the target environment, if captured.
+    pub fn platform(&self) -> Option<&Platform> {
+        self.platform.as_ref()
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum EnvironmentSnapshotError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error("Failed to parse environment snapshot")]
+    Json(#[from] serde_json::Error),
+
+    #[error(
+        "Unsupported environment snapshot version: {0} (expected: {})",
+        EnvironmentSnapshot::VERSION
+    )]
+    UnsupportedVersion(u32),
+}
@@ -8,6 +8,8 @@ use url::Url;
 use distribution_filename::{WheelFilename, WheelFilenameError};
 use uv_normalize::PackageName;
 
+use crate::base_client::InsecureRedirectError;
+use crate::cassette::CassetteMissError;
 use crate::html;
 use crate::middleware::OfflineError;
 
@@ -40,7 +42,10 @@ impl Error {
 
     /// Returns `true` if this error corresponds to an offline error.
     pub(crate) fn is_offline(&self) -> bool {
-        matches!(&*self.kind, ErrorKind::Offline(_))
+        matches!(
+            &*self.kind,
+            ErrorKind::Offline(_) | ErrorKind::OfflineRequest(_)
+        )
     }
 
     /// Returns `true` if this error corresponds to an I/O "not found" error.
@@ -233,10 +238,53 @@ pub enum ErrorKind {
 
     #[error("Network connectivity is disabled, but the requested data wasn't found in the cache for: `{0}`")]
     Offline(String),
+
+    /// A request was blocked because network connectivity is disabled (e.g., via `--offline`).
+    ///
+    /// Unlike transport errors, this doesn't indicate a network failure: the request was never
+    /// sent.
+    #[error("Network connectivity is disabled, but the requested data wasn't found in the cache for: `{0}` (run without `--offline`, or run the command once with network access to populate the cache)")]
+    OfflineRequest(Url),
+
+    #[error("Response from {0} exceeded the maximum size of {1} bytes. Try increasing UV_MAX_RESPONSE_BYTES.")]
+    ResponseTooLarge(Url, u64),
+
+    #[error("Request to {0} was cancelled")]
+    Cancelled(Url),
+
+    /// The response body didn't match its declared `Content-Length` (e.g., a truncated download).
+    #[error("Received {received} bytes from {url}, but the server declared a `Content-Length` of {expected} bytes")]
+    ContentLengthMismatch {
+        url: Url,
+        expected: u64,
+        received: u64,
+    },
+
+    /// A request wasn't found in the cassette being replayed (see
+    /// [`crate::BaseClientBuilder::with_cassette`]).
+    #[error("No response was recorded for `{method} {url}` in the cassette at: `{}`", path.display())]
+    CassetteMiss {
+        method: String,
+        url: Url,
+        path: std::path::PathBuf,
+    },
+
+    #[error("Refusing to follow redirect from `{from}` to `{to}`, which would downgrade the connection from HTTPS to HTTP")]
+    InsecureRedirect { from: Url, to: Url },
 }
 
 impl From<reqwest::Error> for ErrorKind {
     fn from(error: reqwest::Error) -> Self {
+        if error.is_redirect() {
+            if let Some(err) = std::error::Error::source(&error)
+                .and_then(|err| err.downcast_ref::<InsecureRedirectError>())
+            {
+                return Self::InsecureRedirect {
+                    from: err.from.clone(),
+                    to: err.to.clone(),
+                };
+            }
+        }
         Self::ReqwestError(BetterReqwestError::from(error))
     }
 }
@@ -245,7 +293,14 @@ impl From<reqwest_middleware::Error> for ErrorKind {
     fn from(error: reqwest_middleware::Error) -> Self {
         if let reqwest_middleware::Error::Middleware(ref underlying) = error {
             if let Some(err) = underlying.downcast_ref::<OfflineError>() {
-                return Self::Offline(err.url().to_string());
+                return Self::OfflineRequest(err.url().clone());
+            }
+            if let Some(err) = underlying.downcast_ref::<CassetteMissError>() {
+                return Self::CassetteMiss {
+                    method: err.method.clone(),
+                    url: err.url.clone(),
+                    path: err.path.clone(),
+                };
             }
         }
 
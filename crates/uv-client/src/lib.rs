@@ -1,16 +1,27 @@
-pub use base_client::{BaseClient, BaseClientBuilder};
+pub use base_client::{
+    BaseClient, BaseClientBuilder, CorrelationId, IpPreference, OriginalUrl, RetryCount,
+    RetryExclusion, TlsRootMode, UrlRewrite,
+};
+pub use blocking::BlockingClient;
+pub use body::checked_bytes_stream;
 pub use cached_client::{CacheControl, CachedClient, CachedClientError, DataWithCachePolicy};
+pub use cassette::CassetteMode;
+pub use environment_snapshot::{EnvironmentSnapshot, EnvironmentSnapshotError};
 pub use error::{BetterReqwestError, Error, ErrorKind};
 pub use flat_index::{FlatIndexClient, FlatIndexEntries, FlatIndexError};
-pub use linehaul::LineHaul;
+pub use linehaul::{Distro, Implementation, Installer, Libc, LineHaul, System};
 pub use registry_client::{
-    Connectivity, RegistryClient, RegistryClientBuilder, SimpleMetadata, SimpleMetadatum,
-    VersionFiles,
+    Connectivity, RegistryClient, RegistryClientBuilder, SimpleApiAccept, SimpleMetadata,
+    SimpleMetadatum, VersionFiles,
 };
 pub use rkyvutil::OwnedArchive;
 
 mod base_client;
+mod blocking;
+mod body;
 mod cached_client;
+mod cassette;
+mod environment_snapshot;
 mod error;
 mod flat_index;
 mod html;
@@ -1,10 +1,29 @@
 use http::Extensions;
+use std::collections::BTreeSet;
 use std::fmt::Debug;
+use std::future::Future;
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{ready, Context, Poll};
+use std::time::Duration;
 
-use reqwest::{Request, Response};
+use bytes::Bytes;
+use http_body::{Frame, SizeHint};
+use reqwest::{Request, Response, ResponseBuilderExt, StatusCode};
 use reqwest_middleware::{Middleware, Next};
+use reqwest_retry::{DefaultRetryableStrategy, Retryable, RetryableStrategy};
+use rustc_hash::FxHashMap;
+use tokio::sync::Semaphore;
+use tokio::time::{Instant, Sleep};
+use tracing::{debug, trace};
 use url::Url;
 
+use crate::base_client::{
+    is_trusted_host, CorrelationId, OriginalUrl, RetryCount, RetryExclusion, UrlRewrite,
+};
+
 /// A custom error type for the offline middleware.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct OfflineError {
@@ -45,3 +64,743 @@ impl Middleware for OfflineMiddleware {
         ))
     }
 }
+
+/// A middleware that attaches the [`OriginalUrl`] of each request to its response.
+///
+/// Must be the first middleware in the stack, such that it sees the URL requested by the caller.
+pub(crate) struct OriginalUrlMiddleware;
+
+#[async_trait::async_trait]
+impl Middleware for OriginalUrlMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        let original = OriginalUrl(req.url().clone());
+        let mut response = next.run(req, extensions).await?;
+        response.extensions_mut().insert(original);
+        Ok(response)
+    }
+}
+
+/// A middleware that rewrites request URLs according to a set of [`UrlRewrite`] rules.
+pub(crate) struct UrlRewriteMiddleware {
+    rules: Vec<UrlRewrite>,
+}
+
+impl UrlRewriteMiddleware {
+    pub(crate) fn new(rules: Vec<UrlRewrite>) -> Self {
+        Self { rules }
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for UrlRewriteMiddleware {
+    async fn handle(
+        &self,
+        mut req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        if let Some(url) = self.rules.iter().find_map(|rule| rule.apply(req.url())) {
+            trace!("Rewriting {} to {url}", req.url());
+
+            // If the `Host` header was set explicitly, keep it consistent with the new URL.
+            if req.headers().contains_key(http::header::HOST) {
+                let host = match (url.host_str(), url.port()) {
+                    (Some(host), Some(port)) => Some(format!("{host}:{port}")),
+                    (Some(host), None) => Some(host.to_string()),
+                    (None, _) => None,
+                };
+                match host.and_then(|host| http::HeaderValue::from_str(&host).ok()) {
+                    Some(host) => {
+                        req.headers_mut().insert(http::header::HOST, host);
+                    }
+                    None => {
+                        req.headers_mut().remove(http::header::HOST);
+                    }
+                }
+            }
+
+            *req.url_mut() = url;
+        }
+        next.run(req, extensions).await
+    }
+}
+
+/// A middleware that attaches a [`CorrelationId`] header to each request that doesn't already
+/// set it.
+pub(crate) struct CorrelationIdMiddleware {
+    correlation_id: CorrelationId,
+}
+
+impl CorrelationIdMiddleware {
+    pub(crate) fn new(correlation_id: CorrelationId) -> Self {
+        Self { correlation_id }
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for CorrelationIdMiddleware {
+    async fn handle(
+        &self,
+        mut req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        let name = self.correlation_id.name();
+        if !req.headers().contains_key(name) {
+            let value = self.correlation_id.generate();
+            match http::HeaderValue::from_str(&value) {
+                Ok(header) => {
+                    debug!(
+                        "Sending {} {} with {name}: {value}",
+                        req.method(),
+                        req.url()
+                    );
+                    req.headers_mut().insert(name.clone(), header);
+                }
+                Err(_) => {
+                    debug!("Ignoring invalid value for {name}: {value:?}");
+                }
+            }
+        }
+        next.run(req, extensions).await
+    }
+}
+
+/// A middleware that requests uncompressed responses, by sending `Accept-Encoding: identity`.
+///
+/// Applies to all hosts if `hosts` is `None`, or otherwise to the given hosts (see
+/// [`is_trusted_host`] for the matching rules).
+pub(crate) struct IdentityEncodingMiddleware {
+    hosts: Option<Vec<String>>,
+}
+
+impl IdentityEncodingMiddleware {
+    pub(crate) fn new(hosts: Option<Vec<String>>) -> Self {
+        Self { hosts }
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for IdentityEncodingMiddleware {
+    async fn handle(
+        &self,
+        mut req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        if self
+            .hosts
+            .as_ref()
+            .map_or(true, |hosts| is_trusted_host(req.url(), hosts))
+        {
+            trace!("Requesting identity encoding for {}", req.url());
+            req.headers_mut().insert(
+                http::header::ACCEPT_ENCODING,
+                http::HeaderValue::from_static("identity"),
+            );
+        }
+        next.run(req, extensions).await
+    }
+}
+
+/// A middleware that serves `file://` URLs from the local filesystem, such that a directory on disk
+/// can be used as a simple index (e.g., a local mirror).
+///
+/// Files are returned as-is. Directories are returned as their `index.html`, if present, or
+/// otherwise as a [PEP 503](https://peps.python.org/pep-0503/) HTML listing of their entries.
+/// Missing paths result in a `404 Not Found` response, as with a remote index.
+pub(crate) struct LocalFileMiddleware;
+
+#[async_trait::async_trait]
+impl Middleware for LocalFileMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        if req.url().scheme() != "file" {
+            return next.run(req, extensions).await;
+        }
+
+        let url = req.url().clone();
+        let Ok(path) = url.to_file_path() else {
+            return local_response(&url, StatusCode::BAD_REQUEST, "text/plain", Vec::new());
+        };
+
+        if path.is_dir() {
+            let index = path.join("index.html");
+            if index.is_file() {
+                let body = fs_err::tokio::read(&index)
+                    .await
+                    .map_err(reqwest_middleware::Error::middleware)?;
+                return local_response(&url, StatusCode::OK, "text/html", body);
+            }
+            let body = simple_index_html(&path)
+                .await
+                .map_err(reqwest_middleware::Error::middleware)?;
+            local_response(&url, StatusCode::OK, "text/html", body.into_bytes())
+        } else if path.is_file() {
+            let content_type = match path.extension().and_then(|ext| ext.to_str()) {
+                Some("html" | "htm") => "text/html",
+                Some("json") => "application/vnd.pypi.simple.v1+json",
+                _ => "application/octet-stream",
+            };
+            let body = fs_err::tokio::read(&path)
+                .await
+                .map_err(reqwest_middleware::Error::middleware)?;
+            local_response(&url, StatusCode::OK, content_type, body)
+        } else {
+            local_response(&url, StatusCode::NOT_FOUND, "text/plain", Vec::new())
+        }
+    }
+}
+
+/// Render a [PEP 503](https://peps.python.org/pep-0503/) HTML page linking to each entry in the
+/// given directory, sorted by name. Subdirectories are linked with a trailing slash.
+async fn simple_index_html(path: &Path) -> std::io::Result<String> {
+    let mut entries = Vec::new();
+    let mut dir = fs_err::tokio::read_dir(path).await?;
+    while let Some(entry) = dir.next_entry().await? {
+        let Ok(name) = entry.file_name().into_string() else {
+            continue;
+        };
+        if entry.file_type().await?.is_dir() {
+            entries.push(format!("{name}/"));
+        } else {
+            entries.push(name);
+        }
+    }
+    entries.sort_unstable();
+
+    let mut html = String::from("<!DOCTYPE html>\n<html>\n<body>\n");
+    for entry in entries {
+        // Percent-encode the name, such that characters like `+`, `#`, and spaces are preserved
+        // when the link is resolved as a relative URL.
+        let href = match entry.strip_suffix('/') {
+            Some(name) => format!("{}/", urlencoding::encode(name)),
+            None => urlencoding::encode(&entry).into_owned(),
+        };
+        html.push_str(&format!(
+            "<a href=\"{}\">{}</a><br/>\n",
+            html_escape::encode_double_quoted_attribute(&href),
+            html_escape::encode_text(&entry)
+        ));
+    }
+    html.push_str("</body>\n</html>\n");
+    Ok(html)
+}
+
+/// Synthesize a [`Response`] for a local file.
+fn local_response(
+    url: &Url,
+    status: StatusCode,
+    content_type: &str,
+    body: Vec<u8>,
+) -> reqwest_middleware::Result<Response> {
+    let response = http::Response::builder()
+        .status(status)
+        .url(url.clone())
+        .header("content-type", content_type)
+        // Local files can change at any time, so always revalidate cached responses.
+        .header("cache-control", "no-cache")
+        .body(body)
+        .map_err(reqwest_middleware::Error::middleware)?;
+    Ok(Response::from(response))
+}
+
+/// A [`RetryableStrategy`] that extends the default strategy to treat additional status codes as
+/// transient (e.g., a `502 Bad Gateway` from a flaky CDN).
+///
+/// If enabled, connection errors (including DNS resolution failures) are treated as transient
+/// too, rather than failing the request immediately.
+pub(crate) struct RetryStatusStrategy {
+    statuses: Vec<StatusCode>,
+    connect_errors: bool,
+}
+
+impl RetryStatusStrategy {
+    pub(crate) fn new(statuses: Vec<StatusCode>, connect_errors: bool) -> Self {
+        Self {
+            statuses,
+            connect_errors,
+        }
+    }
+}
+
+impl RetryableStrategy for RetryStatusStrategy {
+    fn handle(&self, res: &Result<Response, reqwest_middleware::Error>) -> Option<Retryable> {
+        match res {
+            Ok(response) if self.statuses.contains(&response.status()) => {
+                Some(Retryable::Transient)
+            }
+            Err(reqwest_middleware::Error::Reqwest(err))
+                if self.connect_errors && err.is_connect() =>
+            {
+                Some(Retryable::Transient)
+            }
+            _ => DefaultRetryableStrategy.handle(res),
+        }
+    }
+}
+
+/// A middleware that applies the wrapped (retry) middleware to idempotent requests, and the
+/// fallback (retry) middleware to non-idempotent requests (e.g., `POST`), such that retrying
+/// additional statuses is limited to requests for which it's safe.
+///
+/// Requests matching the [`RetryExclusion`], if any, are never retried.
+pub(crate) struct IdempotentMiddleware<M, F> {
+    inner: M,
+    fallback: F,
+    exclusion: Option<RetryExclusion>,
+}
+
+impl<M, F> IdempotentMiddleware<M, F> {
+    pub(crate) fn new(inner: M, fallback: F, exclusion: Option<RetryExclusion>) -> Self {
+        Self {
+            inner,
+            fallback,
+            exclusion,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<M: Middleware, F: Middleware> Middleware for IdempotentMiddleware<M, F> {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        let excluded = self
+            .exclusion
+            .as_ref()
+            .is_some_and(|exclusion| exclusion.matches(req.method(), req.url()));
+        if excluded {
+            next.run(req, extensions).await
+        } else if req.method().is_idempotent() {
+            self.inner.handle(req, extensions, next).await
+        } else {
+            self.fallback.handle(req, extensions, next).await
+        }
+    }
+}
+
+/// A middleware that attaches a [`RetryCount`] to each response.
+///
+/// Must run after the retry middleware, such that it sees each attempt. The retry middleware
+/// passes the same extensions to every attempt, so the count is tracked there.
+pub(crate) struct RetryCountMiddleware;
+
+#[async_trait::async_trait]
+impl Middleware for RetryCountMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        let count = extensions
+            .get::<RetryCount>()
+            .map_or(RetryCount::default(), |count| RetryCount(count.0 + 1));
+        extensions.insert(count);
+        let mut response = next.run(req, extensions).await?;
+        response.extensions_mut().insert(count);
+        Ok(response)
+    }
+}
+
+/// A middleware that limits the number of concurrent requests to each host.
+///
+/// Hosts without an explicit limit fall back to the default limit, if any. A permit is held until
+/// the response headers are received; reading the response body doesn't count against the limit.
+pub(crate) struct HostLimitMiddleware {
+    limits: FxHashMap<String, usize>,
+    default: Option<usize>,
+    semaphores: Mutex<FxHashMap<String, Arc<Semaphore>>>,
+}
+
+impl HostLimitMiddleware {
+    pub(crate) fn new(limits: FxHashMap<String, usize>, default: Option<usize>) -> Self {
+        Self {
+            limits,
+            default,
+            semaphores: Mutex::new(FxHashMap::default()),
+        }
+    }
+
+    /// Return the semaphore for the given host, or `None` if the host is unlimited.
+    fn semaphore(&self, host: &str) -> Option<Arc<Semaphore>> {
+        let limit = self.limits.get(host).copied().or(self.default)?;
+        let mut semaphores = self.semaphores.lock().unwrap();
+        let semaphore = semaphores
+            .entry(host.to_string())
+            .or_insert_with(|| Arc::new(Semaphore::new(limit.max(1))));
+        Some(semaphore.clone())
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for HostLimitMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        let Some(semaphore) = req.url().host_str().and_then(|host| self.semaphore(host)) else {
+            return next.run(req, extensions).await;
+        };
+        let _permit = semaphore
+            .acquire_owned()
+            .await
+            .expect("Host semaphores are never closed");
+        next.run(req, extensions).await
+    }
+}
+
+/// A middleware that sends requests to trusted hosts with a separate client, which skips
+/// certificate verification.
+///
+/// Must be the last middleware in the stack, as it bypasses the remaining middleware for trusted
+/// hosts.
+pub(crate) struct TrustedHostMiddleware {
+    client: reqwest::Client,
+    hosts: Vec<String>,
+}
+
+impl TrustedHostMiddleware {
+    pub(crate) fn new(client: reqwest::Client, hosts: Vec<String>) -> Self {
+        Self { client, hosts }
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for TrustedHostMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        if !is_trusted_host(req.url(), &self.hosts) {
+            return next.run(req, extensions).await;
+        }
+        trace!("Sending request to trusted host: {}", req.url());
+        self.client
+            .execute(req)
+            .await
+            .map_err(reqwest_middleware::Error::Reqwest)
+    }
+}
+
+/// A token bucket that paces reads to a maximum rate, holding up to one second's worth of tokens.
+///
+/// Rather than waiting for tokens to accumulate, each read reserves the next available slot and
+/// sleeps until it elapses, such that concurrent readers are served in order and never block one
+/// another while holding the lock.
+struct RateLimiter {
+    bytes_per_second: u64,
+    next: Mutex<Instant>,
+}
+
+impl RateLimiter {
+    fn new(bytes_per_second: u64) -> Self {
+        Self {
+            bytes_per_second: bytes_per_second.max(1),
+            next: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Reserve a slot for the given number of bytes, returning the instant at which it elapses.
+    fn reserve(&self, bytes: usize) -> Instant {
+        let cost = Duration::from_nanos(
+            u64::try_from(
+                u128::from(bytes as u64) * 1_000_000_000 / u128::from(self.bytes_per_second),
+            )
+            .unwrap_or(u64::MAX),
+        );
+        let mut next = self.next.lock().unwrap();
+        let now = Instant::now();
+        let start = (*next).max(now.checked_sub(Duration::from_secs(1)).unwrap_or(now));
+        *next = start + cost;
+        *next
+    }
+}
+
+/// A response body that's paced by a shared [`RateLimiter`].
+///
+/// Each frame is returned as soon as it's received, but the body isn't read any further until the
+/// slot reserved for that frame elapses.
+struct RateLimitedBody {
+    inner: reqwest::Body,
+    limiter: Arc<RateLimiter>,
+    wait: Option<Pin<Box<Sleep>>>,
+}
+
+impl http_body::Body for RateLimitedBody {
+    type Data = Bytes;
+    type Error = reqwest::Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Bytes>, reqwest::Error>>> {
+        if let Some(wait) = &mut self.wait {
+            ready!(wait.as_mut().poll(cx));
+            self.wait = None;
+        }
+        let frame = ready!(Pin::new(&mut self.inner).poll_frame(cx));
+        if let Some(data) = frame
+            .as_ref()
+            .and_then(|frame| frame.as_ref().ok())
+            .and_then(Frame::data_ref)
+        {
+            let deadline = self.limiter.reserve(data.len());
+            self.wait = Some(Box::pin(tokio::time::sleep_until(deadline)));
+        }
+        Poll::Ready(frame)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.wait.is_none() && self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+/// A middleware that limits the rate at which response bodies are read, across all concurrent
+/// requests (e.g., to avoid saturating an upstream index while mirroring it).
+pub(crate) struct RateLimitMiddleware {
+    limiter: Arc<RateLimiter>,
+}
+
+impl RateLimitMiddleware {
+    pub(crate) fn new(bytes_per_second: u64) -> Self {
+        Self {
+            limiter: Arc::new(RateLimiter::new(bytes_per_second)),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for RateLimitMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        let response = next.run(req, extensions).await?;
+        map_body(response, |inner| RateLimitedBody {
+            inner,
+            limiter: self.limiter.clone(),
+            wait: None,
+        })
+    }
+}
+
+/// Replace the body of a response, preserving its status, headers, extensions, and URL.
+///
+/// As long as the new body forwards the size hint of the original, the metadata that `reqwest`
+/// derives from the body and connection (i.e., [`Response::content_length`] and
+/// [`Response::remote_addr`]) is preserved too.
+fn map_body<B>(
+    response: Response,
+    f: impl FnOnce(reqwest::Body) -> B,
+) -> reqwest_middleware::Result<Response>
+where
+    B: http_body::Body<Data = Bytes, Error = reqwest::Error> + Send + Sync + 'static,
+{
+    let url = response.url().clone();
+    let (parts, body) = http::Response::from(response).into_parts();
+    let mut builder = http::Response::builder()
+        .status(parts.status)
+        .version(parts.version)
+        .url(url);
+    if let Some(headers) = builder.headers_mut() {
+        *headers = parts.headers;
+    }
+    // Extend, rather than replace, the extensions, which hold the URL.
+    if let Some(extensions) = builder.extensions_mut() {
+        extensions.extend(parts.extensions);
+    }
+    let response = builder
+        .body(reqwest::Body::wrap(f(body)))
+        .map_err(reqwest_middleware::Error::middleware)?;
+    Ok(Response::from(response))
+}
+
+/// Counters for the requests made by a client, shared across all of its clones.
+///
+/// The counters are only used for reporting, so they're updated with relaxed ordering.
+#[derive(Debug, Default)]
+pub(crate) struct ClientCounters {
+    /// The number of requests sent over the network, including retries.
+    pub(crate) requests: AtomicU64,
+    /// The number of response body bytes read from the network.
+    pub(crate) bytes_downloaded: AtomicU64,
+    /// The number of responses served from the cache.
+    pub(crate) cache_hits: AtomicU64,
+}
+
+/// A middleware that counts each request sent over the network, along with the size of each
+/// response body as it's read.
+pub(crate) struct CounterMiddleware {
+    counters: Arc<ClientCounters>,
+}
+
+impl CounterMiddleware {
+    pub(crate) fn new(counters: Arc<ClientCounters>) -> Self {
+        Self { counters }
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for CounterMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        self.counters.requests.fetch_add(1, Ordering::Relaxed);
+        let response = next.run(req, extensions).await?;
+        map_body(response, |inner| CountingBody {
+            inner,
+            counters: self.counters.clone(),
+        })
+    }
+}
+
+/// A response body that counts the bytes read from it.
+struct CountingBody {
+    inner: reqwest::Body,
+    counters: Arc<ClientCounters>,
+}
+
+impl http_body::Body for CountingBody {
+    type Data = Bytes;
+    type Error = reqwest::Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Bytes>, reqwest::Error>>> {
+        let frame = ready!(Pin::new(&mut self.inner).poll_frame(cx));
+        if let Some(data) = frame
+            .as_ref()
+            .and_then(|frame| frame.as_ref().ok())
+            .and_then(Frame::data_ref)
+        {
+            self.counters
+                .bytes_downloaded
+                .fetch_add(data.len() as u64, Ordering::Relaxed);
+        }
+        Poll::Ready(frame)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+/// The distinct hosts contacted by a client, shared across all of its clones.
+#[derive(Debug, Default)]
+pub(crate) struct ContactedHosts(Mutex<BTreeSet<String>>);
+
+impl ContactedHosts {
+    /// Record the host of the given URL, if any (e.g., `pypi.org`, or `localhost:8000`).
+    pub(crate) fn record(&self, url: &Url) {
+        let Some(host) = url.host_str() else {
+            return;
+        };
+        let host = match url.port() {
+            Some(port) => format!("{host}:{port}"),
+            None => host.to_string(),
+        };
+        self.0.lock().unwrap().insert(host);
+    }
+
+    /// Return the recorded hosts, sorted.
+    pub(crate) fn to_vec(&self) -> Vec<String> {
+        self.0.lock().unwrap().iter().cloned().collect()
+    }
+}
+
+/// A middleware that records the host of each request sent over the network, along with the host
+/// that ultimately served the response, if it was redirected.
+pub(crate) struct ContactedHostsMiddleware {
+    hosts: Arc<ContactedHosts>,
+}
+
+impl ContactedHostsMiddleware {
+    pub(crate) fn new(hosts: Arc<ContactedHosts>) -> Self {
+        Self { hosts }
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for ContactedHostsMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        self.hosts.record(req.url());
+        let response = next.run(req, extensions).await?;
+        self.hosts.record(response.url());
+        Ok(response)
+    }
+}
+
+/// A middleware that logs the connection details of each response at trace level (e.g., the
+/// resolved peer address), for diagnosing slow or misrouted requests.
+///
+/// `reqwest` doesn't expose the negotiated TLS version or cipher suite, so only the peer
+/// certificate is reported for TLS connections.
+pub(crate) struct ConnectionTraceMiddleware;
+
+#[async_trait::async_trait]
+impl Middleware for ConnectionTraceMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        let method = req.method().clone();
+        let url = req.url().clone();
+        let response = next.run(req, extensions).await?;
+
+        let remote_addr = response
+            .remote_addr()
+            .map_or_else(|| "unknown".to_string(), |addr| addr.to_string());
+        let peer_certificate = response
+            .extensions()
+            .get::<reqwest::tls::TlsInfo>()
+            .and_then(reqwest::tls::TlsInfo::peer_certificate)
+            .map(<[u8]>::len);
+        trace!(
+            "{method} {url} connected to {remote_addr} ({:?}, status: {}, peer certificate: {})",
+            response.version(),
+            response.status(),
+            peer_certificate.map_or_else(|| "none".to_string(), |len| format!("{len} bytes")),
+        );
+
+        Ok(response)
+    }
+}
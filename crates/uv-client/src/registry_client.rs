@@ -1,12 +1,16 @@
 use std::collections::BTreeMap;
+use std::env;
 use std::fmt::Debug;
 use std::path::Path;
 use std::str::FromStr;
 
 use async_http_range_reader::AsyncHttpRangeReader;
+use bytes::{Bytes, BytesMut};
 use futures::{FutureExt, TryStreamExt};
+use http::header::CONTENT_TYPE;
 use http::HeaderMap;
 use reqwest::{Client, Response, StatusCode};
+use rustc_hash::FxHashMap;
 use serde::{Deserialize, Serialize};
 use tokio::io::AsyncReadExt;
 use tokio_util::compat::{FuturesAsyncReadCompatExt, TokioAsyncReadCompatExt};
@@ -19,13 +23,15 @@ use install_wheel_rs::metadata::{find_archive_dist_info, is_metadata_entry};
 use pep440_rs::Version;
 use pep508_rs::MarkerEnvironment;
 use platform_tags::Platform;
-use pypi_types::{Metadata23, SimpleJson};
+use pypi_types::{CoreMetadata, Metadata23, SimpleJson};
 use uv_cache::{Cache, CacheBucket, WheelCache};
 use uv_configuration::IndexStrategy;
 use uv_configuration::KeyringProviderType;
 use uv_normalize::PackageName;
+use uv_warnings::warn_user_once;
 
 use crate::base_client::{BaseClient, BaseClientBuilder};
+use crate::body::MAX_PREALLOCATION;
 use crate::cached_client::CacheControl;
 use crate::html::SimpleHtml;
 use crate::remote_metadata::wheel_metadata_from_remote_zip;
@@ -45,6 +51,10 @@ pub struct RegistryClientBuilder<'a> {
     client: Option<Client>,
     markers: Option<&'a MarkerEnvironment>,
     platform: Option<&'a Platform>,
+    download_buffer_size: Option<usize>,
+    simple_api_accept: SimpleApiAccept,
+    index_simple_api_accept: FxHashMap<IndexUrl, SimpleApiAccept>,
+    max_response_bytes: Option<u64>,
 }
 
 impl RegistryClientBuilder<'_> {
@@ -60,6 +70,10 @@ impl RegistryClientBuilder<'_> {
             client: None,
             markers: None,
             platform: None,
+            download_buffer_size: None,
+            simple_api_accept: SimpleApiAccept::default(),
+            index_simple_api_accept: FxHashMap::default(),
+            max_response_bytes: None,
         }
     }
 }
@@ -125,6 +139,39 @@ impl<'a> RegistryClientBuilder<'a> {
         self
     }
 
+    #[must_use]
+    pub fn download_buffer_size(mut self, download_buffer_size: usize) -> Self {
+        self.download_buffer_size = Some(download_buffer_size);
+        self
+    }
+
+    #[must_use]
+    pub fn max_response_bytes(mut self, max_response_bytes: u64) -> Self {
+        self.max_response_bytes = Some(max_response_bytes);
+        self
+    }
+
+    /// Set the formats to request from the simple API (i.e., the `Accept` header), for every
+    /// index without an explicit preference.
+    #[must_use]
+    pub fn simple_api_accept(mut self, simple_api_accept: SimpleApiAccept) -> Self {
+        self.simple_api_accept = simple_api_accept;
+        self
+    }
+
+    /// Set the formats to request from the simple API of the given index (e.g., to avoid a broken
+    /// JSON API).
+    #[must_use]
+    pub fn index_simple_api_accept(
+        mut self,
+        index: IndexUrl,
+        simple_api_accept: SimpleApiAccept,
+    ) -> Self {
+        self.index_simple_api_accept
+            .insert(index, simple_api_accept);
+        self
+    }
+
     pub fn build(self) -> RegistryClient {
         // Build a base client
         let mut builder = BaseClientBuilder::new();
@@ -141,6 +188,14 @@ impl<'a> RegistryClientBuilder<'a> {
             builder = builder.platform(platform)
         }
 
+        if let Some(download_buffer_size) = self.download_buffer_size {
+            builder = builder.download_buffer_size(download_buffer_size)
+        }
+
+        if let Some(max_response_bytes) = self.max_response_bytes {
+            builder = builder.max_response_bytes(max_response_bytes)
+        }
+
         let client = builder
             .retries(self.retries)
             .connectivity(self.connectivity)
@@ -150,6 +205,7 @@ impl<'a> RegistryClientBuilder<'a> {
 
         let timeout = client.timeout();
         let connectivity = client.connectivity();
+        let max_response_bytes = client.max_response_bytes();
 
         // Wrap in the cache middleware.
         let client = CachedClient::new(client);
@@ -161,6 +217,9 @@ impl<'a> RegistryClientBuilder<'a> {
             connectivity,
             client,
             timeout,
+            max_response_bytes,
+            simple_api_accept: self.simple_api_accept,
+            index_simple_api_accept: self.index_simple_api_accept,
         }
     }
 }
@@ -180,6 +239,12 @@ pub struct RegistryClient {
     connectivity: Connectivity,
     /// Configured client timeout, in seconds.
     timeout: u64,
+    /// The maximum size of a metadata response, in bytes.
+    max_response_bytes: Option<u64>,
+    /// The formats to request from the simple API, by default.
+    simple_api_accept: SimpleApiAccept,
+    /// The formats to request from the simple API of specific indexes.
+    index_simple_api_accept: FxHashMap<IndexUrl, SimpleApiAccept>,
 }
 
 impl RegistryClient {
@@ -236,7 +301,7 @@ impl RegistryClient {
                     }
                 }
                 Err(CachedClientError::Client(err)) => match err.into_kind() {
-                    ErrorKind::Offline(_) => continue,
+                    ErrorKind::Offline(_) | ErrorKind::OfflineRequest(_) => continue,
                     ErrorKind::ReqwestError(err) => {
                         if err.status() == Some(StatusCode::NOT_FOUND)
                             || err.status() == Some(StatusCode::UNAUTHORIZED)
@@ -299,11 +364,16 @@ impl RegistryClient {
             Connectivity::Offline => CacheControl::AllowStale,
         };
 
+        let accept = self
+            .index_simple_api_accept
+            .get(index)
+            .copied()
+            .unwrap_or(self.simple_api_accept);
         let simple_request = self
             .uncached_client()
             .get(url.clone())
             .header("Accept-Encoding", "gzip")
-            .header("Accept", MediaType::accepts())
+            .header("Accept", accept.header())
             .build()
             .map_err(ErrorKind::from)?;
         let parse_simple_response = |response: Response| {
@@ -329,16 +399,18 @@ impl RegistryClient {
 
                 let unarchived = match media_type {
                     MediaType::Json => {
-                        let bytes = response.bytes().await.map_err(ErrorKind::from)?;
+                        let bytes = read_limited(response, self.max_response_bytes).await?;
                         let data: SimpleJson = serde_json::from_slice(bytes.as_ref())
                             .map_err(|err| Error::from_json_err(err, url.clone()))?;
+                        warn_deprecated_index(&url, false, &data.files);
 
                         SimpleMetadata::from_files(data.files, package_name, &url)
                     }
                     MediaType::Html => {
-                        let text = response.text().await.map_err(ErrorKind::from)?;
+                        let text = read_limited_text(response, self.max_response_bytes).await?;
                         let SimpleHtml { base, files } = SimpleHtml::parse(&text, &url)
                             .map_err(|err| Error::from_html_err(err, url.clone()))?;
+                        warn_deprecated_index(&url, accept != SimpleApiAccept::HtmlOnly, &files);
 
                         SimpleMetadata::from_files(files, package_name, base.as_url())
                     }
@@ -448,7 +520,7 @@ impl RegistryClient {
             };
 
             let response_callback = |response: Response| async {
-                let bytes = response.bytes().await.map_err(ErrorKind::from)?;
+                let bytes = read_limited(response, self.max_response_bytes).await?;
 
                 info_span!("parse_metadata21")
                     .in_scope(|| Metadata23::parse_metadata(bytes.as_ref()))
@@ -614,6 +686,63 @@ impl RegistryClient {
     }
 }
 
+/// Read the body of a metadata response, failing if it exceeds the given limit (in bytes).
+///
+/// The body is read in chunks, such that oversized responses are rejected without buffering the
+/// entire body.
+async fn read_limited(mut response: Response, limit: Option<u64>) -> Result<Bytes, Error> {
+    let content_length = response.content_length();
+    let limit = limit.unwrap_or(u64::MAX);
+
+    // Reject the response upfront if the server reports an excessive length.
+    if content_length.is_some_and(|content_length| content_length > limit) {
+        return Err(ErrorKind::ResponseTooLarge(response.url().clone(), limit).into());
+    }
+
+    // Pre-allocate based on the declared length, if any.
+    let capacity = content_length.map_or(0, |content_length| content_length.min(MAX_PREALLOCATION));
+    let mut body = BytesMut::with_capacity(usize::try_from(capacity).unwrap_or(0));
+    while let Some(chunk) = response.chunk().await.map_err(ErrorKind::from)? {
+        if body.len() as u64 + chunk.len() as u64 > limit {
+            return Err(ErrorKind::ResponseTooLarge(response.url().clone(), limit).into());
+        }
+        body.extend_from_slice(&chunk);
+    }
+
+    // Reject truncated responses.
+    if let Some(content_length) = content_length {
+        if body.len() as u64 != content_length {
+            return Err(ErrorKind::ContentLengthMismatch {
+                url: response.url().clone(),
+                expected: content_length,
+                received: body.len() as u64,
+            }
+            .into());
+        }
+    }
+    Ok(body.freeze())
+}
+
+/// Read the body of a metadata response as text, failing if it exceeds the given limit (in bytes).
+///
+/// The body is decoded in the same way as [`Response::text`], i.e., respecting the charset in the
+/// `Content-Type` header, if any.
+async fn read_limited_text(response: Response, limit: Option<u64>) -> Result<String, Error> {
+    let content_type = response.headers().get(CONTENT_TYPE).cloned();
+    let bytes = read_limited(response, limit).await?;
+
+    // Decode the (bounded) body via `reqwest`, to share its charset handling.
+    let mut decoded = http::Response::new(bytes);
+    if let Some(content_type) = content_type {
+        decoded.headers_mut().insert(CONTENT_TYPE, content_type);
+    }
+    let text = Response::from(decoded)
+        .text()
+        .await
+        .map_err(ErrorKind::from)?;
+    Ok(text)
+}
+
 /// Read a wheel's `METADATA` file from a zip file.
 async fn read_metadata_async_seek(
     filename: &WheelFilename,
@@ -841,12 +970,72 @@ impl MediaType {
             _ => None,
         }
     }
+}
+
+/// Warn (once per host) if an index relies on deprecated simple API behavior, such that users can
+/// ask the index administrators to upgrade: serving only the HTML API (PEP 503) when JSON
+/// (PEP 691) was preferred, or serving wheels without their metadata (PEP 658).
+///
+/// Local indexes are exempt. Set `UV_NO_DEPRECATED_INDEX_WARNING` to suppress the warnings.
+fn warn_deprecated_index(url: &Url, html_only: bool, files: &[pypi_types::File]) {
+    let Some(host) = url.host_str() else {
+        return;
+    };
+    if env::var_os("UV_NO_DEPRECATED_INDEX_WARNING").is_some() {
+        return;
+    }
+    let host = match url.port() {
+        Some(port) => format!("{host}:{port}"),
+        None => host.to_string(),
+    };
+
+    if html_only {
+        warn_user_once!(
+            "The index at `{host}` only serves the legacy HTML simple API; consider asking its administrators to enable the JSON simple API (PEP 691)"
+        );
+    }
+
+    let mut wheels = files
+        .iter()
+        .filter(|file| file.filename.ends_with(".whl"))
+        .peekable();
+    if wheels.peek().is_some()
+        && !wheels.any(|file| {
+            file.core_metadata
+                .as_ref()
+                .or(file.dist_info_metadata.as_ref())
+                .or(file.data_dist_info_metadata.as_ref())
+                .is_some_and(CoreMetadata::is_available)
+        })
+    {
+        warn_user_once!(
+            "The index at `{host}` doesn't serve wheel metadata separately; consider asking its administrators to enable PEP 658 metadata"
+        );
+    }
+}
+
+/// The formats to request from a simple API, via the `Accept` header.
+///
+/// See: <https://peps.python.org/pep-0691/#version-format-selection>
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default)]
+pub enum SimpleApiAccept {
+    /// Prefer the JSON API, falling back to HTML.
+    #[default]
+    PreferJson,
+    /// Request only the JSON API.
+    JsonOnly,
+    /// Request only HTML (e.g., for indexes with a broken JSON API).
+    HtmlOnly,
+}
 
-    /// Return the `Accept` header value for all supported media types.
-    #[inline]
-    const fn accepts() -> &'static str {
-        // See: https://peps.python.org/pep-0691/#version-format-selection
-        "application/vnd.pypi.simple.v1+json, application/vnd.pypi.simple.v1+html;q=0.2, text/html;q=0.01"
+impl SimpleApiAccept {
+    /// Return the `Accept` header value for the requested formats.
+    pub const fn header(self) -> &'static str {
+        match self {
+            Self::PreferJson => "application/vnd.pypi.simple.v1+json, application/vnd.pypi.simple.v1+html;q=0.2, text/html;q=0.01",
+            Self::JsonOnly => "application/vnd.pypi.simple.v1+json",
+            Self::HtmlOnly => "application/vnd.pypi.simple.v1+html, text/html;q=0.01",
+        }
     }
 }
 
@@ -0,0 +1,39 @@
+//! Kept out of the shared `it` binary: these assertions inspect the process-wide warning set,
+//! which other tests in that binary populate when they disable certificate verification.
+
+use std::env;
+
+use uv_client::BaseClientBuilder;
+
+/// Return the emitted warnings that mention disabled certificate verification.
+fn insecure_warnings() -> Vec<String> {
+    uv_warnings::WARNINGS
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|warning| warning.contains("TLS certificate verification is disabled"))
+        .cloned()
+        .collect()
+}
+
+#[test]
+fn allow_insecure() {
+    uv_warnings::enable();
+
+    // By default, certificate verification is enabled, without a warning.
+    env::remove_var("UV_INSECURE");
+    let _client = BaseClientBuilder::new().build();
+    let _client = BaseClientBuilder::from_env().build();
+    assert!(insecure_warnings().is_empty());
+
+    // Disabling verification should warn.
+    let _client = BaseClientBuilder::new().allow_insecure(true).build();
+    assert_eq!(insecure_warnings().len(), 1);
+
+    // As should disabling it via the environment.
+    env::set_var("UV_INSECURE", "1");
+    let builder = BaseClientBuilder::from_env();
+    assert!(format!("{builder:?}").contains("allow_insecure: true"));
+    let _client = builder.build();
+    assert_eq!(insecure_warnings().len(), 1);
+}
@@ -0,0 +1,38 @@
+use anyhow::Result;
+use reqwest::StatusCode;
+use url::Url;
+
+use uv_client::{BaseClientBuilder, Connectivity, ErrorKind};
+
+#[test]
+fn blocking_client() -> Result<()> {
+    let index = tempfile::tempdir()?;
+    fs_err::create_dir(index.path().join("flask"))?;
+    fs_err::write(
+        index.path().join("flask").join("index.html"),
+        "<a href=\"flask-3.0.3-py3-none-any.whl\">flask-3.0.3-py3-none-any.whl</a>",
+    )?;
+
+    let client = BaseClientBuilder::new()
+        .connectivity(Connectivity::Offline)
+        .build_blocking()?;
+
+    // The middleware stack should apply to blocking requests, e.g., to serve local files...
+    let url = Url::from_directory_path(index.path().join("flask")).unwrap();
+    let response = client.get(url)?;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(client
+        .text(response)?
+        .contains("flask-3.0.3-py3-none-any.whl"));
+
+    // ...and to reject remote requests in offline mode.
+    let err = client
+        .get(Url::parse("https://pypi.org/simple/flask/")?)
+        .unwrap_err();
+    assert!(
+        matches!(err.kind(), ErrorKind::OfflineRequest(_)),
+        "{err:?}"
+    );
+
+    Ok(())
+}
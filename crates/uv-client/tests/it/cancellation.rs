@@ -0,0 +1,52 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use http_body_util::Full;
+use hyper::body::Bytes;
+use hyper::Response;
+use tokio::net::TcpListener;
+use tokio::sync::oneshot;
+use tokio_util::sync::CancellationToken;
+use url::Url;
+
+use uv_client::{BaseClientBuilder, ErrorKind};
+
+use crate::serve_connection;
+
+#[tokio::test]
+async fn cancel_in_flight_request() -> Result<()> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+
+    // Accept a single connection whose response never arrives, and report when the client
+    // closes it.
+    let (closed_tx, closed_rx) = oneshot::channel();
+    tokio::spawn(async move {
+        let (socket, _) = listener.accept().await.unwrap();
+        serve_connection(socket, |_req| async {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            Response::new(Full::new(Bytes::new()))
+        })
+        .await;
+        closed_tx.send(()).unwrap();
+    });
+
+    let client = BaseClientBuilder::new().build();
+    let cancellation = CancellationToken::new();
+    let url = Url::parse(&format!("http://{addr}"))?;
+
+    let request = client.get_cancellable(url, &cancellation);
+    let cancel = async {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        cancellation.cancel();
+    };
+    let (result, ()) = tokio::join!(request, cancel);
+
+    let err = result.unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::Cancelled(_)), "{err:?}");
+
+    // The connection should be closed promptly, rather than kept open by the client.
+    tokio::time::timeout(Duration::from_secs(5), closed_rx).await??;
+
+    Ok(())
+}
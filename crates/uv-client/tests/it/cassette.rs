@@ -0,0 +1,77 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use anyhow::Result;
+use http_body_util::Full;
+use hyper::body::Bytes;
+use hyper::header::{CONTENT_TYPE, SET_COOKIE};
+use hyper::{Response, StatusCode};
+use url::Url;
+
+use uv_client::{BaseClientBuilder, CassetteMode, Connectivity, ErrorKind};
+
+use crate::spawn_http_server;
+
+#[tokio::test]
+async fn cassette_record_replay() -> Result<()> {
+    // Count the requests that reach the server.
+    let requests = Arc::new(AtomicUsize::new(0));
+    let counter = requests.clone();
+    let addr = spawn_http_server(move |_req| {
+        counter.fetch_add(1, Ordering::SeqCst);
+        let mut response = Response::new(Full::new(Bytes::from("<html></html>")));
+        response
+            .headers_mut()
+            .insert(CONTENT_TYPE, "text/html".parse().unwrap());
+        response
+            .headers_mut()
+            .insert(SET_COOKIE, "session=hunter2".parse().unwrap());
+        async { response }
+    })
+    .await?;
+
+    let dir = tempfile::tempdir()?;
+    let cassette = dir.path().join("cassette.json");
+    let url = Url::parse(&format!("http://user:s3cret@{addr}/simple/flask/"))?;
+
+    // Record an interaction, which should be passed through to the caller unchanged.
+    let client = BaseClientBuilder::new()
+        .with_cassette(&cassette, CassetteMode::Record)
+        .build();
+    let response = client.send_get(url.clone()).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[SET_COOKIE], "session=hunter2");
+    assert_eq!(response.text().await?, "<html></html>");
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+    // Credentials and sensitive headers should be scrubbed from the cassette.
+    let contents = fs_err::read_to_string(&cassette)?;
+    assert!(contents.contains("/simple/flask/"), "{contents}");
+    assert!(!contents.contains("s3cret"), "{contents}");
+    assert!(!contents.contains("hunter2"), "{contents}");
+
+    // Replay the interaction, without reaching the server, in both online and offline mode.
+    for connectivity in [Connectivity::Online, Connectivity::Offline] {
+        let client = BaseClientBuilder::new()
+            .connectivity(connectivity)
+            .with_cassette(&cassette, CassetteMode::Replay)
+            .build();
+        let response = client.send_get(url.clone()).await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CONTENT_TYPE], "text/html");
+        assert!(response.headers().get(SET_COOKIE).is_none());
+        assert_eq!(response.text().await?, "<html></html>");
+
+        // Requests that weren't recorded should fail clearly.
+        let missing = Url::parse(&format!("http://{addr}/simple/django/"))?;
+        let err = client.send_get(missing.clone()).await.unwrap_err();
+        assert!(
+            matches!(err.kind(), ErrorKind::CassetteMiss { method, url, .. } if method == "GET" && *url == missing),
+            "{err:?}"
+        );
+        assert!(err.to_string().contains("cassette.json"), "{err}");
+    }
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use std::net::SocketAddr;
+
+use anyhow::Result;
+use http_body_util::Full;
+use hyper::body::Bytes;
+use hyper::header::LOCATION;
+use hyper::{Response, StatusCode};
+use url::Url;
+
+use uv_client::BaseClientBuilder;
+
+use crate::spawn_http_server;
+
+/// Serve every request, redirecting to the given location, if any.
+async fn serve(location: Option<String>) -> Result<SocketAddr> {
+    spawn_http_server(move |_req| {
+        let mut response = Response::new(Full::new(Bytes::from("ok")));
+        if let Some(location) = &location {
+            *response.status_mut() = StatusCode::FOUND;
+            response
+                .headers_mut()
+                .insert(LOCATION, location.parse().unwrap());
+        }
+        async { response }
+    })
+    .await
+}
+
+#[tokio::test]
+async fn contacted_hosts() -> Result<()> {
+    // An index that redirects to an artifact served by another host.
+    let artifacts = serve(None).await?;
+    let index = serve(Some(format!(
+        "http://{artifacts}/flask-3.0.3-py3-none-any.whl"
+    )))
+    .await?;
+
+    let client = BaseClientBuilder::new().build();
+    assert!(client.contacted_hosts().is_empty());
+
+    let url = Url::parse(&format!("http://{index}/flask-3.0.3-py3-none-any.whl"))?;
+    let response = client.send_get(url.clone()).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    client.send_get(url).await?;
+
+    // Both hosts should be recorded, once each, and sorted.
+    let mut expected = vec![index.to_string(), artifacts.to_string()];
+    expected.sort();
+    assert_eq!(client.contacted_hosts(), expected);
+
+    Ok(())
+}
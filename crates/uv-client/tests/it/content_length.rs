@@ -0,0 +1,108 @@
+use std::convert::Infallible;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use anyhow::Result;
+use futures::TryStreamExt;
+use http_body_util::Full;
+use hyper::body::{Body, Bytes, Frame, SizeHint};
+use hyper::Response;
+use reqwest::ResponseBuilderExt;
+use url::Url;
+
+use uv_client::{checked_bytes_stream, BaseClientBuilder, ErrorKind};
+
+use crate::spawn_http_server;
+
+/// A body that declares the given length, if any, regardless of its actual length.
+struct DeclaredBody {
+    inner: Full<Bytes>,
+    declared: Option<u64>,
+}
+
+impl Body for DeclaredBody {
+    type Data = Bytes;
+    type Error = Infallible;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Bytes>, Infallible>>> {
+        Pin::new(&mut self.inner).poll_frame(cx)
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.declared.map(SizeHint::with_exact).unwrap_or_default()
+    }
+}
+
+/// Read the body of the response, checking it against its declared `Content-Length`.
+async fn read(response: reqwest::Response) -> io::Result<Vec<u8>> {
+    checked_bytes_stream(response, |err| io::Error::new(io::ErrorKind::Other, err))
+        .try_fold(Vec::new(), |mut body, chunk| async move {
+            body.extend_from_slice(chunk.as_ref());
+            Ok(body)
+        })
+        .await
+}
+
+/// Build a response with the given body, and optionally a declared length.
+fn response(body: &'static str, declared: Option<u64>) -> Result<reqwest::Response> {
+    let body = DeclaredBody {
+        inner: Full::new(Bytes::from(body)),
+        declared,
+    };
+    Ok(reqwest::Response::from(
+        http::Response::builder()
+            .url(Url::parse("https://example.com/file.whl")?)
+            .body(reqwest::Body::wrap(body))?,
+    ))
+}
+
+#[tokio::test]
+async fn content_length_complete() -> Result<()> {
+    let addr =
+        spawn_http_server(|_req| async { Response::new(Full::new(Bytes::from("hello world"))) })
+            .await?;
+
+    // The declared length should survive the client's middleware.
+    let client = BaseClientBuilder::new().build();
+    let url = Url::parse(&format!("http://{addr}/file.whl"))?;
+    let response = client.send_get(url).await?;
+    assert_eq!(response.content_length(), Some(11));
+    assert_eq!(read(response).await?, b"hello world");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn content_length_mismatch() -> Result<()> {
+    // A truncated body should be rejected...
+    let err = read(response("short", Some(100))?).await.unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    let err = err
+        .into_inner()
+        .unwrap()
+        .downcast::<uv_client::Error>()
+        .unwrap();
+    assert!(
+        matches!(
+            err.kind(),
+            ErrorKind::ContentLengthMismatch {
+                expected: 100,
+                received: 5,
+                ..
+            }
+        ),
+        "{err:?}"
+    );
+
+    // ...as should an overlong one.
+    assert!(read(response("too long", Some(3))?).await.is_err());
+
+    // Bodies without a declared length aren't checked.
+    assert_eq!(read(response("short", None)?).await?, b"short");
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use anyhow::Result;
+use http_body_util::Full;
+use hyper::body::Bytes;
+use hyper::Response;
+use reqwest::header::HeaderName;
+use url::Url;
+
+use uv_client::BaseClientBuilder;
+
+use crate::spawn_http_server;
+
+#[tokio::test]
+async fn correlation_id() -> Result<()> {
+    // Echo the correlation header back in the response body.
+    let addr = spawn_http_server(|req| {
+        let id = req
+            .headers()
+            .get("x-request-id")
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        async move { Response::new(Full::new(Bytes::from(id))) }
+    })
+    .await?;
+
+    let counter = AtomicUsize::new(0);
+    let client = BaseClientBuilder::new()
+        .correlation_id(HeaderName::from_static("x-request-id"), move || {
+            format!("job-42-{}", counter.fetch_add(1, Ordering::SeqCst))
+        })
+        .build();
+    let url = Url::parse(&format!("http://{addr}"))?;
+
+    // Each request should receive its own identifier.
+    assert_eq!(
+        client.send_get(url.clone()).await?.text().await?,
+        "job-42-0"
+    );
+    assert_eq!(
+        client.send_get(url.clone()).await?.text().await?,
+        "job-42-1"
+    );
+
+    // An existing header should be left untouched.
+    let response = client
+        .get(url)
+        .header("x-request-id", "explicit")
+        .send()
+        .await?;
+    assert_eq!(response.text().await?, "explicit");
+
+    Ok(())
+}
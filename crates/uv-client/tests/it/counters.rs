@@ -0,0 +1,61 @@
+use anyhow::Result;
+use http_body_util::Full;
+use hyper::body::Bytes;
+use hyper::header::CACHE_CONTROL;
+use hyper::Response;
+use url::Url;
+
+use distribution_types::FlatIndexLocation;
+use uv_cache::Cache;
+use uv_client::{BaseClientBuilder, FlatIndexClient, RegistryClientBuilder};
+
+use crate::spawn_http_server;
+
+const BODY: &str = r#"<html><body><a href="flask-3.0.3-py3-none-any.whl">flask</a></body></html>"#;
+
+#[tokio::test]
+async fn counters() -> Result<()> {
+    let addr = spawn_http_server(|_req| async {
+        let mut response = Response::new(Full::new(Bytes::from(BODY)));
+        response
+            .headers_mut()
+            .insert(CACHE_CONTROL, "max-age=600".parse().unwrap());
+        response
+    })
+    .await?;
+    let url = Url::parse(&format!("http://{addr}/wheels/"))?;
+
+    // Each request, and the bytes read from its body, should be counted across clones.
+    let client = BaseClientBuilder::new().build();
+    assert_eq!(client.request_count(), 0);
+    assert_eq!(client.bytes_downloaded(), 0);
+    let clone = client.clone();
+    clone.send_get(url.clone()).await?.text().await?;
+    let response = client.send_get(url.clone()).await?;
+
+    // Counting the bytes shouldn't discard the response metadata.
+    assert_eq!(response.url(), &url);
+    assert_eq!(response.content_length(), Some(BODY.len() as u64));
+    assert_eq!(response.remote_addr(), Some(addr));
+    response.text().await?;
+
+    assert_eq!(client.request_count(), 2);
+    assert_eq!(client.bytes_downloaded(), 2 * BODY.len() as u64);
+    assert_eq!(client.cache_hits(), 0);
+
+    // A fresh cached response should be counted as a cache hit, rather than a request.
+    let cache = Cache::temp()?.init()?;
+    let client = RegistryClientBuilder::new(cache.clone()).build();
+    let location = FlatIndexLocation::Url(url);
+    for _ in 0..2 {
+        FlatIndexClient::new(&client, &cache)
+            .fetch(std::iter::once(&location))
+            .await?;
+    }
+    let client = client.uncached_client();
+    assert_eq!(client.request_count(), 1);
+    assert_eq!(client.bytes_downloaded(), BODY.len() as u64);
+    assert_eq!(client.cache_hits(), 1);
+
+    Ok(())
+}
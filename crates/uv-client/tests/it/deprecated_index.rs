@@ -0,0 +1,94 @@
+use std::net::SocketAddr;
+use std::str::FromStr;
+
+use anyhow::Result;
+use http_body_util::Full;
+use hyper::body::Bytes;
+use hyper::header::CONTENT_TYPE;
+use hyper::Response;
+use url::Url;
+
+use distribution_types::{IndexLocations, IndexUrl, IndexUrls};
+use pep508_rs::VerbatimUrl;
+use uv_cache::Cache;
+use uv_client::RegistryClientBuilder;
+use uv_normalize::PackageName;
+
+use crate::spawn_http_server;
+
+const HTML: &str =
+    r#"<a href="/packages/flask-3.0.3-py3-none-any.whl">flask-3.0.3-py3-none-any.whl</a>"#;
+
+const JSON: &str = r#"{
+  "files": [
+    {
+      "core-metadata": true,
+      "filename": "flask-3.0.3-py3-none-any.whl",
+      "hashes": {},
+      "url": "/packages/flask-3.0.3-py3-none-any.whl"
+    }
+  ]
+}"#;
+
+/// Serve the given body, with the given content type, for every request.
+async fn serve(body: &'static str, content_type: &'static str) -> Result<SocketAddr> {
+    spawn_http_server(move |_req| async move {
+        let mut response = Response::new(Full::new(Bytes::from(body)));
+        response
+            .headers_mut()
+            .insert(CONTENT_TYPE, content_type.parse().unwrap());
+        response
+    })
+    .await
+}
+
+/// Fetch the simple API page for `flask` from the index at the given address.
+async fn fetch(addr: SocketAddr) -> Result<()> {
+    let index = IndexUrl::from(VerbatimUrl::from_url(Url::parse(&format!(
+        "http://{addr}/simple/"
+    ))?));
+    let client = RegistryClientBuilder::new(Cache::temp()?.init()?)
+        .index_urls(IndexUrls::from(IndexLocations::new(
+            Some(index),
+            vec![],
+            vec![],
+            false,
+        )))
+        .build();
+    client.simple(&PackageName::from_str("flask")?).await?;
+    Ok(())
+}
+
+/// Return the warnings emitted so far that mention the given address.
+fn warnings(addr: SocketAddr) -> Vec<String> {
+    let mut warnings = uv_warnings::WARNINGS
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|warning| warning.contains(&format!("`{addr}`")))
+        .cloned()
+        .collect::<Vec<_>>();
+    warnings.sort();
+    warnings
+}
+
+#[tokio::test]
+async fn deprecated_index() -> Result<()> {
+    uv_warnings::enable();
+
+    // An HTML-only index without PEP 658 metadata should be flagged, once.
+    let html = serve(HTML, "text/html").await?;
+    fetch(html).await?;
+    fetch(html).await?;
+    let warnings = warnings(html);
+    assert_eq!(warnings.len(), 2, "{warnings:?}");
+    assert!(warnings[0].contains("doesn't serve wheel metadata"));
+    assert!(warnings[1].contains("only serves the legacy HTML simple API"));
+
+    // A JSON index with PEP 658 metadata shouldn't be.
+    let json = serve(JSON, "application/vnd.pypi.simple.v1+json").await?;
+    fetch(json).await?;
+    assert!(warnings(json).is_empty());
+
+    Ok(())
+}
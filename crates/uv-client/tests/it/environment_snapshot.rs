@@ -0,0 +1,45 @@
+use anyhow::Result;
+use pep508_rs::{MarkerEnvironment, MarkerEnvironmentBuilder};
+use platform_tags::{Arch, Os, Platform};
+
+use uv_client::{BaseClientBuilder, EnvironmentSnapshot, EnvironmentSnapshotError};
+
+#[test]
+fn environment_snapshot() -> Result<()> {
+    let markers = MarkerEnvironment::try_from(MarkerEnvironmentBuilder {
+        implementation_name: "cpython",
+        implementation_version: "3.11.8",
+        os_name: "nt",
+        platform_machine: "AMD64",
+        platform_python_implementation: "CPython",
+        platform_release: "10",
+        platform_system: "Windows",
+        platform_version: "10.0.22631",
+        python_full_version: "3.11.8",
+        python_version: "3.11",
+        sys_platform: "win32",
+    })
+    .unwrap();
+    let platform = Platform::new(Os::Windows, Arch::X86_64);
+
+    // Snapshots should round-trip through the filesystem.
+    let temp_dir = tempfile::tempdir()?;
+    let path = temp_dir.path().join("environment.json");
+    EnvironmentSnapshot::new(markers.clone(), Some(platform.clone())).write(&path)?;
+    let snapshot = EnvironmentSnapshot::from_path(&path)?;
+    assert_eq!(snapshot.markers(), &markers);
+    assert_eq!(snapshot.platform(), Some(&platform));
+
+    // The client should reflect the snapshotted environment.
+    let client = BaseClientBuilder::new().snapshot(&snapshot).build();
+    assert!(client.user_agent().contains(r#""python":"3.11.8""#));
+
+    // Snapshots from other versions of the format should be rejected.
+    let err = EnvironmentSnapshot::from_json(r#"{"version": 2}"#).unwrap_err();
+    assert!(matches!(
+        err,
+        EnvironmentSnapshotError::UnsupportedVersion(2)
+    ));
+
+    Ok(())
+}
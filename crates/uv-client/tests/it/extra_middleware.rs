@@ -0,0 +1,59 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use anyhow::Result;
+use http::Extensions;
+use http_body_util::Full;
+use hyper::body::Bytes;
+use hyper::{Response, StatusCode};
+use reqwest_middleware::{Middleware, Next};
+use url::Url;
+
+use uv_client::{BaseClientBuilder, Connectivity};
+
+use crate::spawn_http_server;
+
+/// A middleware that counts the requests it sees.
+#[derive(Default)]
+struct CountRequests(AtomicUsize);
+
+#[async_trait::async_trait]
+impl Middleware for CountRequests {
+    async fn handle(
+        &self,
+        req: reqwest::Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<reqwest::Response> {
+        self.0.fetch_add(1, Ordering::SeqCst);
+        next.run(req, extensions).await
+    }
+}
+
+#[tokio::test]
+async fn extra_middleware() -> Result<()> {
+    let addr =
+        spawn_http_server(|_req| async { Response::new(Full::new(Bytes::from("ok"))) }).await?;
+    let url = Url::parse(&format!("http://{addr}"))?;
+
+    let client = BaseClientBuilder::new().download_buffer_size(1024).build();
+    let counter = Arc::new(CountRequests::default());
+    let extended = client.with_extra_middleware(counter.clone());
+
+    // The extra middleware should see requests made through the derived client...
+    let response = extended.send_get(url.clone()).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(counter.0.load(Ordering::SeqCst), 1);
+
+    // ...but not those made through the original client.
+    let response = client.send_get(url).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(counter.0.load(Ordering::SeqCst), 1);
+
+    // The configuration should be preserved.
+    assert_eq!(extended.timeout(), client.timeout());
+    assert_eq!(extended.download_buffer_size(), 1024);
+    assert_eq!(extended.connectivity(), Connectivity::Online);
+
+    Ok(())
+}
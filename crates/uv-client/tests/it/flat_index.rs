@@ -0,0 +1,90 @@
+use anyhow::Result;
+use http_body_util::Full;
+use hyper::body::Bytes;
+use hyper::Response;
+use url::Url;
+
+use distribution_filename::DistFilename;
+use distribution_types::FlatIndexLocation;
+use uv_cache::Cache;
+use uv_client::{Connectivity, FlatIndexClient, RegistryClientBuilder};
+
+use crate::spawn_http_server;
+
+/// A `--find-links` page, as generated by a plain directory listing.
+const FIND_LINKS: &str = r#"
+<html>
+  <body>
+    <a href="../">../</a>
+    <a href="flask-3.0.3-py3-none-any.whl">flask-3.0.3-py3-none-any.whl</a>
+    <a href="flask-3.0.3.tar.gz">flask-3.0.3.tar.gz</a>
+    <a href="/packages/anyio-4.3.0-py3-none-any.whl">anyio-4.3.0-py3-none-any.whl</a>
+    <a href="README.md">README.md</a>
+  </body>
+</html>
+"#;
+
+#[tokio::test]
+async fn find_links_url() -> Result<()> {
+    let addr =
+        spawn_http_server(|_req| async { Response::new(Full::new(Bytes::from(FIND_LINKS))) })
+            .await?;
+    let location = FlatIndexLocation::Url(Url::parse(&format!("http://{addr}/wheels/"))?);
+
+    // Only links to distributions should be extracted, resolved relative to the page.
+    let cache = Cache::temp()?.init()?;
+    let client = RegistryClientBuilder::new(cache.clone()).build();
+    let entries = FlatIndexClient::new(&client, &cache)
+        .fetch(std::iter::once(&location))
+        .await?;
+    assert!(!entries.offline);
+    let mut entries = entries
+        .entries
+        .iter()
+        .map(|(filename, file, _)| {
+            let kind = match filename {
+                DistFilename::WheelFilename(_) => "wheel",
+                DistFilename::SourceDistFilename(_) => "sdist",
+            };
+            (
+                file.filename.clone(),
+                kind,
+                file.url.to_url().unwrap().to_string(),
+            )
+        })
+        .collect::<Vec<_>>();
+    entries.sort();
+    assert_eq!(
+        entries,
+        vec![
+            (
+                "anyio-4.3.0-py3-none-any.whl".to_string(),
+                "wheel",
+                format!("http://{addr}/packages/anyio-4.3.0-py3-none-any.whl"),
+            ),
+            (
+                "flask-3.0.3-py3-none-any.whl".to_string(),
+                "wheel",
+                format!("http://{addr}/wheels/flask-3.0.3-py3-none-any.whl"),
+            ),
+            (
+                "flask-3.0.3.tar.gz".to_string(),
+                "sdist",
+                format!("http://{addr}/wheels/flask-3.0.3.tar.gz"),
+            ),
+        ]
+    );
+
+    // In offline mode, an uncached page should be reported as unavailable, rather than an error.
+    let cache = Cache::temp()?.init()?;
+    let client = RegistryClientBuilder::new(cache.clone())
+        .connectivity(Connectivity::Offline)
+        .build();
+    let entries = FlatIndexClient::new(&client, &cache)
+        .fetch(std::iter::once(&location))
+        .await?;
+    assert!(entries.offline);
+    assert!(entries.entries.is_empty());
+
+    Ok(())
+}
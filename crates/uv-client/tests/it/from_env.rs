@@ -0,0 +1,58 @@
+use std::env;
+
+use anyhow::Result;
+use url::Url;
+
+use uv_client::{BaseClientBuilder, Connectivity, ErrorKind};
+
+#[tokio::test]
+async fn from_env() -> Result<()> {
+    env::set_var("UV_OFFLINE", "true");
+    env::set_var("UV_NATIVE_TLS", "1");
+    env::set_var("UV_KEYRING_PROVIDER", "subprocess");
+    env::set_var("UV_MAX_CONNECTIONS_PER_HOST", "4");
+    env::set_var("UV_TCP_KEEPALIVE", "30");
+
+    // Each recognized variable should be applied to the builder.
+    let builder = BaseClientBuilder::from_env();
+    let debug = format!("{builder:?}");
+    assert!(debug.contains("native_tls: true"), "{debug}");
+    assert!(debug.contains("keyring: Subprocess"), "{debug}");
+    assert!(
+        debug.contains("max_connections_per_host: Some(4)"),
+        "{debug}"
+    );
+    assert!(debug.contains("tcp_keepalive: Some(30s)"), "{debug}");
+
+    let client = builder.build();
+    assert_eq!(client.connectivity(), Connectivity::Offline);
+    let err = client
+        .send_get(Url::parse("https://pypi.org/simple/flask/")?)
+        .await
+        .unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::OfflineRequest(_)), "{err}");
+
+    // Explicit setters take precedence over the environment.
+    let client = BaseClientBuilder::from_env()
+        .connectivity(Connectivity::Online)
+        .build();
+    assert_eq!(client.connectivity(), Connectivity::Online);
+
+    // Invalid values are ignored.
+    env::set_var("UV_OFFLINE", "maybe");
+    let client = BaseClientBuilder::from_env().build();
+    assert_eq!(client.connectivity(), Connectivity::Online);
+
+    // Avoid leaking the configuration into other tests.
+    for key in [
+        "UV_OFFLINE",
+        "UV_NATIVE_TLS",
+        "UV_KEYRING_PROVIDER",
+        "UV_MAX_CONNECTIONS_PER_HOST",
+        "UV_TCP_KEEPALIVE",
+    ] {
+        env::remove_var(key);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use http_body_util::Full;
+use hyper::body::Bytes;
+use hyper::Response;
+use url::Url;
+
+use uv_client::BaseClientBuilder;
+
+use crate::spawn_http_server;
+
+#[tokio::test]
+async fn host_connection_limit() -> Result<()> {
+    // Track the maximum number of requests in flight at once.
+    let in_flight = Arc::new(AtomicUsize::new(0));
+    let max_in_flight = Arc::new(AtomicUsize::new(0));
+    let addr = {
+        let in_flight = in_flight.clone();
+        let max_in_flight = max_in_flight.clone();
+        spawn_http_server(move |_req| {
+            let in_flight = in_flight.clone();
+            let max_in_flight = max_in_flight.clone();
+            async move {
+                let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                max_in_flight.fetch_max(current, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                Response::new(Full::new(Bytes::new()))
+            }
+        })
+        .await?
+    };
+
+    let client = BaseClientBuilder::new()
+        .max_connections_per_host(4)
+        .host_connection_limit("127.0.0.1", 1)
+        .build();
+
+    let url = Url::parse(&format!("http://{addr}"))?;
+    let responses =
+        futures::future::try_join_all((0..4).map(|_| client.send_get(url.clone()))).await?;
+    assert!(responses
+        .iter()
+        .all(|response| response.status().is_success()));
+    assert_eq!(max_in_flight.load(Ordering::SeqCst), 1);
+
+    Ok(())
+}
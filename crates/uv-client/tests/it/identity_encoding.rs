@@ -0,0 +1,62 @@
+use anyhow::Result;
+use http_body_util::Full;
+use hyper::body::Bytes;
+use hyper::header::ACCEPT_ENCODING;
+use hyper::Response;
+use url::Url;
+
+use uv_client::BaseClientBuilder;
+
+use crate::spawn_http_server;
+
+#[tokio::test]
+async fn identity_encoding() -> Result<()> {
+    // Echo the `Accept-Encoding` header back in the response body.
+    let addr = spawn_http_server(|req| {
+        let encoding = req
+            .headers()
+            .get(ACCEPT_ENCODING)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        async move { Response::new(Full::new(Bytes::from(encoding))) }
+    })
+    .await?;
+    let url = Url::parse(&format!("http://{addr}"))?;
+
+    // By default, compressed responses are requested.
+    let client = BaseClientBuilder::new().build();
+    let encoding = client.send_get(url.clone()).await?.text().await?;
+    assert!(encoding.contains("gzip"), "{encoding}");
+
+    // Identity encoding can be forced for every host...
+    let client = BaseClientBuilder::new().identity_encoding(true).build();
+    assert_eq!(
+        client.send_get(url.clone()).await?.text().await?,
+        "identity"
+    );
+
+    // ...even if the request sets its own header...
+    let response = client
+        .get(url.clone())
+        .header(ACCEPT_ENCODING, "gzip")
+        .send()
+        .await?;
+    assert_eq!(response.text().await?, "identity");
+
+    // ...or for specific hosts only.
+    let client = BaseClientBuilder::new()
+        .identity_encoding_hosts(vec!["127.0.0.1".to_string()])
+        .build();
+    assert_eq!(
+        client.send_get(url.clone()).await?.text().await?,
+        "identity"
+    );
+    let client = BaseClientBuilder::new()
+        .identity_encoding_hosts(vec!["example.com".to_string()])
+        .build();
+    let encoding = client.send_get(url).await?.text().await?;
+    assert!(encoding.contains("gzip"), "{encoding}");
+
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use anyhow::Result;
+use reqwest::StatusCode;
+use url::Url;
+
+use uv_client::{BaseClientBuilder, Connectivity};
+
+#[tokio::test]
+async fn local_index_directory_listing() -> Result<()> {
+    let index = tempfile::tempdir()?;
+    fs_err::create_dir(index.path().join("flask"))?;
+    fs_err::write(
+        index
+            .path()
+            .join("flask")
+            .join("flask-3.0.3-py3-none-any.whl"),
+        "",
+    )?;
+
+    // Local files should be readable regardless of connectivity.
+    for connectivity in [Connectivity::Online, Connectivity::Offline] {
+        let client = BaseClientBuilder::new().connectivity(connectivity).build();
+
+        // The root of the index should list each project.
+        let url = Url::from_directory_path(index.path()).unwrap();
+        let response = client.get(url).send().await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["content-type"], "text/html");
+        assert!(response
+            .text()
+            .await?
+            .contains(r#"<a href="flask/">flask/</a>"#));
+
+        // The project page should list each distribution.
+        let url = Url::from_directory_path(index.path().join("flask")).unwrap();
+        let response = client.get(url.clone()).send().await?;
+        assert_eq!(response.url(), &url);
+        assert!(response.text().await?.contains(
+            r#"<a href="flask-3.0.3-py3-none-any.whl">flask-3.0.3-py3-none-any.whl</a>"#
+        ));
+
+        // Missing projects should be reported as such.
+        let url = Url::from_directory_path(index.path().join("django")).unwrap();
+        let response = client.get(url).send().await?;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn local_index_page() -> Result<()> {
+    let index = tempfile::tempdir()?;
+    fs_err::create_dir(index.path().join("flask"))?;
+    fs_err::write(
+        index.path().join("flask").join("index.html"),
+        "<a href=\"../../packages/flask-3.0.3-py3-none-any.whl\">flask-3.0.3-py3-none-any.whl</a>",
+    )?;
+
+    // An existing `index.html` should take precedence over the directory listing.
+    let client = BaseClientBuilder::new().build();
+    let url = Url::from_directory_path(index.path().join("flask")).unwrap();
+    let response = client.get(url).send().await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response
+        .text()
+        .await?
+        .contains("../../packages/flask-3.0.3-py3-none-any.whl"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn local_index_escaped_names() -> Result<()> {
+    let index = tempfile::tempdir()?;
+    fs_err::create_dir(index.path().join("flask"))?;
+    fs_err::write(
+        index
+            .path()
+            .join("flask")
+            .join("flask-3.0.3+local-py3-none-any.whl"),
+        "",
+    )?;
+    fs_err::write(index.path().join("flask").join("notes #1 & 2.txt"), "")?;
+
+    // Links should be percent-encoded (and HTML-escaped), while the text is only HTML-escaped.
+    let client = BaseClientBuilder::new().build();
+    let url = Url::from_directory_path(index.path().join("flask")).unwrap();
+    let text = client.get(url.clone()).send().await?.text().await?;
+    assert!(text.contains(
+        r#"<a href="flask-3.0.3%2Blocal-py3-none-any.whl">flask-3.0.3+local-py3-none-any.whl</a>"#
+    ));
+    assert!(text.contains(r#"<a href="notes%20%231%20%26%202.txt">notes #1 &amp; 2.txt</a>"#));
+
+    // The encoded links should resolve to the original files.
+    let href = url.join("flask-3.0.3%2Blocal-py3-none-any.whl")?;
+    let response = client.get(href).send().await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    Ok(())
+}
@@ -0,0 +1,81 @@
+//! Integration tests for `uv-client`, compiled into a single binary.
+
+use std::convert::Infallible;
+use std::future::Future;
+use std::net::SocketAddr;
+
+use anyhow::Result;
+use http_body_util::Full;
+use hyper::body::{Bytes, Incoming};
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Request, Response};
+use hyper_util::rt::TokioIo;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpListener;
+
+mod blocking;
+mod cancellation;
+mod cassette;
+mod contacted_hosts;
+mod content_length;
+mod correlation_id;
+mod counters;
+mod deprecated_index;
+mod environment_snapshot;
+mod extra_middleware;
+mod flat_index;
+mod from_env;
+mod host_limit;
+mod identity_encoding;
+mod insecure_redirect;
+mod local_index;
+mod max_response_bytes;
+mod middleware_order;
+mod offline;
+mod original_url;
+mod proxy;
+mod rate_limit;
+mod remote_metadata;
+mod retry_connect;
+mod retry_count;
+mod retry_exclusion;
+mod retry_status;
+mod simple_api_accept;
+mod url_rewrite;
+mod user_agent_version;
+
+/// Spawn an HTTP/1 server on a random local port, answering every request with `handler`.
+///
+/// The server runs until the test's runtime shuts down.
+pub(crate) async fn spawn_http_server<F, Fut>(handler: F) -> Result<SocketAddr>
+where
+    F: Fn(Request<Incoming>) -> Fut + Clone + Send + Sync + 'static,
+    Fut: Future<Output = Response<Full<Bytes>>> + Send + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    tokio::spawn(async move {
+        loop {
+            let (socket, _) = listener.accept().await.unwrap();
+            tokio::spawn(serve_connection(socket, handler.clone()));
+        }
+    });
+    Ok(addr)
+}
+
+/// Serve HTTP/1 requests on a single connection with `handler`, until the connection closes.
+pub(crate) async fn serve_connection<I, F, Fut>(io: I, handler: F)
+where
+    I: AsyncRead + AsyncWrite + Send + Unpin + 'static,
+    F: Fn(Request<Incoming>) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Response<Full<Bytes>>> + Send + 'static,
+{
+    let svc = service_fn(move |req| {
+        let response = handler(req);
+        async move { Ok::<_, Infallible>(response.await) }
+    });
+    let _ = http1::Builder::new()
+        .serve_connection(TokioIo::new(io), svc)
+        .await;
+}
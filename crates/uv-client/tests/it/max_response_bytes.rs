@@ -0,0 +1,57 @@
+use std::str::FromStr;
+
+use anyhow::Result;
+use http_body_util::Full;
+use hyper::body::Bytes;
+use hyper::header::CONTENT_TYPE;
+use hyper::Response;
+use url::Url;
+
+use distribution_types::{IndexLocations, IndexUrl, IndexUrls};
+use pep508_rs::VerbatimUrl;
+use uv_cache::Cache;
+use uv_client::{ErrorKind, RegistryClientBuilder};
+use uv_normalize::PackageName;
+
+use crate::spawn_http_server;
+
+#[tokio::test]
+async fn max_response_bytes() -> Result<()> {
+    // Serve a (padded) HTML index of 1 KiB.
+    let addr = spawn_http_server(|_req| {
+        let body = format!("<html>{}</html>", " ".repeat(1024 - 13));
+        let mut response = Response::new(Full::new(Bytes::from(body)));
+        response
+            .headers_mut()
+            .insert(CONTENT_TYPE, "text/html".parse().unwrap());
+        async { response }
+    })
+    .await?;
+
+    let index = IndexUrl::from(VerbatimUrl::from_url(Url::parse(&format!(
+        "http://{addr}/simple/"
+    ))?));
+    let index_urls = IndexUrls::from(IndexLocations::new(Some(index), vec![], vec![], false));
+    let package = PackageName::from_str("flask")?;
+
+    // Responses that exceed the limit are rejected...
+    let client = RegistryClientBuilder::new(Cache::temp()?.init()?)
+        .index_urls(index_urls.clone())
+        .max_response_bytes(512)
+        .build();
+    let err = client.simple(&package).await.unwrap_err();
+    let ErrorKind::ResponseTooLarge(url, limit) = err.kind() else {
+        panic!("expected an oversized response, got: {err}");
+    };
+    assert_eq!(url.as_str(), format!("http://{addr}/simple/flask/"));
+    assert_eq!(*limit, 512);
+
+    // ...while responses within the limit are accepted.
+    let client = RegistryClientBuilder::new(Cache::temp()?.init()?)
+        .index_urls(index_urls)
+        .max_response_bytes(1024)
+        .build();
+    client.simple(&package).await?;
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use http::Extensions;
+use http_body_util::Full;
+use hyper::body::Bytes;
+use hyper::{Response, StatusCode};
+use reqwest_middleware::{Middleware, Next};
+use url::Url;
+
+use uv_client::BaseClientBuilder;
+
+use crate::spawn_http_server;
+
+/// A middleware that records whether each request it sees has an `Authorization` header.
+#[derive(Clone, Default)]
+struct RecordAuthorization(Arc<Mutex<Vec<bool>>>);
+
+#[async_trait::async_trait]
+impl Middleware for RecordAuthorization {
+    async fn handle(
+        &self,
+        req: reqwest::Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<reqwest::Response> {
+        self.0
+            .lock()
+            .unwrap()
+            .push(req.headers().contains_key(reqwest::header::AUTHORIZATION));
+        next.run(req, extensions).await
+    }
+}
+
+#[tokio::test]
+async fn middleware_order() -> Result<()> {
+    // Require authentication for every request.
+    let addr = spawn_http_server(|req| async move {
+        let mut response = Response::new(Full::new(Bytes::new()));
+        if !req.headers().contains_key(hyper::header::AUTHORIZATION) {
+            *response.status_mut() = StatusCode::UNAUTHORIZED;
+        }
+        response
+    })
+    .await?;
+
+    // Provide credentials for the server via a netrc file.
+    let netrc = tempfile::NamedTempFile::new()?;
+    fs_err::write(
+        netrc.path(),
+        "machine 127.0.0.1 login ferris password hunter2\n",
+    )?;
+    std::env::set_var("NETRC", netrc.path());
+
+    let before = RecordAuthorization::default();
+    let after = RecordAuthorization::default();
+    let client = BaseClientBuilder::new()
+        .with_before_auth(before.clone())
+        .with_after_auth(after.clone())
+        .build();
+
+    // The netrc file is read when the client is built, so avoid leaking it into other tests.
+    std::env::remove_var("NETRC");
+
+    let url = Url::parse(&format!("http://{addr}/simple/flask/"))?;
+    let response = client.send_get(url).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // The middleware before authentication never sees credentials, while the middleware after
+    // authentication sees both the anonymous attempt and the authenticated retry.
+    assert_eq!(*before.0.lock().unwrap(), vec![false]);
+    assert_eq!(*after.0.lock().unwrap(), vec![false, true]);
+
+    Ok(())
+}
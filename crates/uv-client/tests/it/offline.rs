@@ -0,0 +1,37 @@
+use anyhow::Result;
+use url::Url;
+
+use uv_client::{BaseClientBuilder, Connectivity, ErrorKind};
+
+#[tokio::test]
+async fn offline_request() -> Result<()> {
+    let client = BaseClientBuilder::new()
+        .connectivity(Connectivity::Offline)
+        .build();
+
+    let url = Url::parse("https://pypi.org/simple/flask/")?;
+    for result in [
+        client.send_get(url.clone()).await,
+        client.send_head(url).await,
+    ] {
+        let err = result.unwrap_err();
+        assert!(
+            matches!(err.kind(), ErrorKind::OfflineRequest(url) if url.as_str() == "https://pypi.org/simple/flask/"),
+            "{err:?}"
+        );
+
+        // The message should explain that the request was blocked, rather than failed.
+        let message = err.to_string();
+        assert!(
+            message.contains("`https://pypi.org/simple/flask/`"),
+            "{message}"
+        );
+        assert!(
+            message.contains("Network connectivity is disabled"),
+            "{message}"
+        );
+        assert!(message.contains("--offline"), "{message}");
+    }
+
+    Ok(())
+}
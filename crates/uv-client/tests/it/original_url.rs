@@ -0,0 +1,53 @@
+use anyhow::Result;
+use http_body_util::Full;
+use hyper::body::Bytes;
+use hyper::header::LOCATION;
+use hyper::{Response, StatusCode};
+use url::Url;
+
+use uv_client::{BaseClientBuilder, OriginalUrl};
+
+use crate::spawn_http_server;
+
+#[tokio::test]
+async fn original_url() -> Result<()> {
+    // Redirect downloads from the index to a signed object storage URL.
+    let addr = spawn_http_server(|req| async move {
+        let mut response = Response::new(Full::new(Bytes::new()));
+        if req.uri().path().starts_with("/packages/") {
+            *response.status_mut() = StatusCode::FOUND;
+            response.headers_mut().insert(
+                LOCATION,
+                "/storage/flask-3.0.3-py3-none-any.whl?signature=abc"
+                    .parse()
+                    .unwrap(),
+            );
+        }
+        response
+    })
+    .await?;
+
+    let client = BaseClientBuilder::new().build();
+
+    // Both the original and the final URL should be retrievable.
+    let url = Url::parse(&format!(
+        "http://{addr}/packages/flask-3.0.3-py3-none-any.whl"
+    ))?;
+    let response = client.send_get(url.clone()).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(OriginalUrl::of(&response), &url);
+    assert_eq!(
+        response.url().as_str(),
+        format!("http://{addr}/storage/flask-3.0.3-py3-none-any.whl?signature=abc")
+    );
+    assert!(OriginalUrl::is_redirected(&response));
+
+    // Without a redirect, the URLs should match.
+    let url = Url::parse(&format!("http://{addr}/simple/flask/"))?;
+    let response = client.send_get(url.clone()).await?;
+    assert_eq!(OriginalUrl::of(&response), &url);
+    assert_eq!(response.url(), &url);
+    assert!(!OriginalUrl::is_redirected(&response));
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use std::env;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use anyhow::Result;
+use http_body_util::Full;
+use hyper::body::Bytes;
+use hyper::{Response, StatusCode};
+use url::Url;
+
+use uv_client::BaseClientBuilder;
+
+use crate::spawn_http_server;
+
+/// Start a server that responds with the given status, counting the requests it receives.
+async fn serve(status: StatusCode) -> Result<(SocketAddr, Arc<AtomicUsize>)> {
+    let requests = Arc::new(AtomicUsize::new(0));
+    let addr = {
+        let requests = requests.clone();
+        spawn_http_server(move |_req| {
+            requests.fetch_add(1, Ordering::SeqCst);
+            let mut response = Response::new(Full::new(Bytes::new()));
+            *response.status_mut() = status;
+            async { response }
+        })
+        .await?
+    };
+    Ok((addr, requests))
+}
+
+#[tokio::test]
+async fn no_proxy_for_localhost() -> Result<()> {
+    let (proxy, proxied) = serve(StatusCode::BAD_GATEWAY).await?;
+    let (index, _) = serve(StatusCode::OK).await?;
+
+    for name in ["http_proxy", "https_proxy", "all_proxy", "no_proxy"] {
+        env::remove_var(name);
+        env::remove_var(name.to_ascii_uppercase());
+    }
+    env::set_var("HTTP_PROXY", format!("http://{proxy}"));
+
+    // The proxies are read from the environment when each client is built, so build both
+    // clients up front, to avoid leaking the proxy into other tests.
+    let client = BaseClientBuilder::new().build();
+    let proxied_client = BaseClientBuilder::new()
+        .retries(0)
+        .no_proxy_for_localhost(false)
+        .build();
+    env::remove_var("HTTP_PROXY");
+
+    let url = Url::parse(&format!("http://{index}/simple/flask/"))?;
+
+    // By default, requests to a loopback address should bypass the proxy.
+    let response = client.send_get(url.clone()).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(proxied.load(Ordering::SeqCst), 0);
+
+    // Unless explicitly disabled.
+    let response = proxied_client.send_get(url).await?;
+    assert_eq!(response.status(), StatusCode::BAD_GATEWAY);
+    assert_eq!(proxied.load(Ordering::SeqCst), 1);
+
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use http_body_util::Full;
+use hyper::body::Bytes;
+use hyper::{Response, StatusCode};
+use url::Url;
+
+use uv_client::{BaseClientBuilder, RetryCount};
+
+use crate::spawn_http_server;
+
+#[tokio::test]
+async fn max_download_rate() -> Result<()> {
+    // Serve a 10 KB body for every request.
+    let addr = spawn_http_server(|_req| async {
+        Response::new(Full::new(Bytes::from(vec![0u8; 10_000])))
+    })
+    .await?;
+
+    let client = BaseClientBuilder::new().max_download_rate(20_000).build();
+
+    // Two concurrent 10 KB downloads share a single 20 KB/s budget, and so should take around a
+    // second in total, rather than completing immediately.
+    let url = Url::parse(&format!(
+        "http://{addr}/packages/flask-3.0.3-py3-none-any.whl"
+    ))?;
+    let start = Instant::now();
+    let bodies = futures::future::try_join_all((0..2).map(|_| {
+        let client = client.clone();
+        let url = url.clone();
+        async move { anyhow::Ok(client.send_get(url).await?.bytes().await?) }
+    }))
+    .await?;
+    assert!(bodies.iter().all(|body| body.len() == 10_000));
+    assert!(start.elapsed() >= Duration::from_millis(800));
+
+    // The response metadata should survive the rate-limited body.
+    let response = client.send_get(url.clone()).await?;
+    assert_eq!(response.url(), &url);
+    assert_eq!(response.content_length(), Some(10_000));
+    assert_eq!(response.remote_addr(), Some(addr));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn max_download_rate_retry_count() -> Result<()> {
+    // Fail the first request with a transient error, then serve a small body.
+    let requests = Arc::new(AtomicUsize::new(0));
+    let addr = spawn_http_server(move |_req| {
+        let mut response = Response::new(Full::new(Bytes::from(vec![0u8; 100])));
+        if requests.fetch_add(1, Ordering::SeqCst) == 0 {
+            *response.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
+        }
+        async { response }
+    })
+    .await?;
+
+    // Rebuilding the response around the rate-limited body should preserve its extensions,
+    // including the number of retries.
+    let client = BaseClientBuilder::new()
+        .retries(3)
+        .max_download_rate(1_000_000)
+        .build();
+    let url = Url::parse(&format!("http://{addr}/simple/flask/"))?;
+    let response = client.send_get(url).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response
+            .extensions()
+            .get::<RetryCount>()
+            .map(|count| count.get()),
+        Some(1)
+    );
+    assert_eq!(response.bytes().await?.len(), 100);
+
+    Ok(())
+}
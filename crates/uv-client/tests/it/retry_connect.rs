@@ -0,0 +1,49 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use http_body_util::Full;
+use hyper::body::Bytes;
+use hyper::{Response, StatusCode};
+use tokio::net::TcpListener;
+use url::Url;
+
+use uv_client::{BaseClientBuilder, ErrorKind};
+
+use crate::serve_connection;
+
+#[tokio::test]
+async fn retry_connect_errors() -> Result<()> {
+    // Reserve a port, then release it, such that connections are refused until the server starts.
+    let addr = TcpListener::bind("127.0.0.1:0").await?.local_addr()?;
+    let url = Url::parse(&format!("http://{addr}/simple/flask/"))?;
+
+    // By default, connection errors aren't retried.
+    let client = BaseClientBuilder::new().retries(3).build();
+    let err = client.send_get(url.clone()).await.unwrap_err();
+    assert!(
+        matches!(err.kind(), ErrorKind::ReqwestError(err) if err.is_connect()),
+        "{err}"
+    );
+
+    // Start the server after a delay shorter than the first backoff interval.
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        let listener = TcpListener::bind(addr).await.unwrap();
+        loop {
+            let (socket, _) = listener.accept().await.unwrap();
+            tokio::spawn(serve_connection(socket, |_req| async {
+                Response::new(Full::new(Bytes::new()))
+            }));
+        }
+    });
+
+    // With retries enabled, the request should succeed once the server is up.
+    let client = BaseClientBuilder::new()
+        .retries(3)
+        .retry_connect_errors(true)
+        .build();
+    let response = client.send_get(url).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use anyhow::Result;
+use http_body_util::Full;
+use hyper::body::Bytes;
+use hyper::{Response, StatusCode};
+use url::Url;
+
+use uv_client::{BaseClientBuilder, RetryCount};
+
+use crate::spawn_http_server;
+
+#[tokio::test]
+async fn retry_count() -> Result<()> {
+    // Fail the first request with a transient error, then succeed.
+    let requests = Arc::new(AtomicUsize::new(0));
+    let addr = spawn_http_server(move |_req| {
+        let mut response = Response::new(Full::new(Bytes::new()));
+        if requests.fetch_add(1, Ordering::SeqCst) == 0 {
+            *response.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
+        }
+        async { response }
+    })
+    .await?;
+
+    // The successful response should record the single retry.
+    let client = BaseClientBuilder::new().retries(3).build();
+    let url = Url::parse(&format!("http://{addr}/simple/flask/"))?;
+    let response = client.send_get(url.clone()).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response
+            .extensions()
+            .get::<RetryCount>()
+            .map(|count| count.get()),
+        Some(1)
+    );
+
+    // Subsequent requests succeed on the first attempt.
+    let response = client.send_get(url.clone()).await?;
+    assert_eq!(
+        response
+            .extensions()
+            .get::<RetryCount>()
+            .map(|count| count.get()),
+        Some(0)
+    );
+
+    // With retries disabled, the count is omitted.
+    let client = BaseClientBuilder::new().retries(0).build();
+    let response = client.send_get(url).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.extensions().get::<RetryCount>().is_none());
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use anyhow::Result;
+use http_body_util::Full;
+use hyper::body::Bytes;
+use hyper::{Response, StatusCode};
+use reqwest::Method;
+use url::Url;
+
+use uv_client::BaseClientBuilder;
+
+use crate::spawn_http_server;
+
+#[tokio::test]
+async fn retry_exclusion() -> Result<()> {
+    // Always fail with a transient error, counting the requests to each path.
+    let upload = Arc::new(AtomicUsize::new(0));
+    let metadata = Arc::new(AtomicUsize::new(0));
+    let addr = {
+        let upload = upload.clone();
+        let metadata = metadata.clone();
+        spawn_http_server(move |req| {
+            if req.uri().path().starts_with("/upload") {
+                upload.fetch_add(1, Ordering::SeqCst);
+            } else {
+                metadata.fetch_add(1, Ordering::SeqCst);
+            }
+            let mut response = Response::new(Full::new(Bytes::new()));
+            *response.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
+            async { response }
+        })
+        .await?
+    };
+
+    let client = BaseClientBuilder::new()
+        .retries(1)
+        .retry_exclusion(|method, url| method == Method::PUT && url.path().starts_with("/upload"))
+        .build();
+
+    // Excluded requests are sent exactly once.
+    let url = Url::parse(&format!(
+        "http://{addr}/upload/flask-3.0.3-py3-none-any.whl"
+    ))?;
+    let response = client.put(url).send().await?;
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(upload.load(Ordering::SeqCst), 1);
+
+    // Other idempotent requests are still retried.
+    let url = Url::parse(&format!("http://{addr}/simple/flask/"))?;
+    let response = client.send_get(url).await?;
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(metadata.load(Ordering::SeqCst), 2);
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use anyhow::Result;
+use http_body_util::Full;
+use hyper::body::Bytes;
+use hyper::{Response, StatusCode};
+use url::Url;
+
+use uv_client::BaseClientBuilder;
+
+use crate::spawn_http_server;
+
+#[tokio::test]
+async fn retry_statuses() -> Result<()> {
+    // Respond with the status given in the path, counting the requests.
+    let requests = Arc::new(AtomicUsize::new(0));
+    let counter = requests.clone();
+    let addr = spawn_http_server(move |req| {
+        counter.fetch_add(1, Ordering::SeqCst);
+        let status = req.uri().path().trim_start_matches('/').parse().unwrap();
+        let mut response = Response::new(Full::new(Bytes::new()));
+        *response.status_mut() = StatusCode::from_u16(status).unwrap();
+        async { response }
+    })
+    .await?;
+
+    let client = BaseClientBuilder::new()
+        .retries(1)
+        .retry_statuses(&[StatusCode::CONFLICT])
+        .build();
+
+    // Idempotent requests are retried on the configured statuses...
+    let url = Url::parse(&format!("http://{addr}/409"))?;
+    let response = client.get(url.clone()).send().await?;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    assert_eq!(requests.swap(0, Ordering::SeqCst), 2);
+
+    // ...while non-idempotent requests aren't...
+    let response = client.post(url).send().await?;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    assert_eq!(requests.swap(0, Ordering::SeqCst), 1);
+
+    // ...but are still retried on the statuses that are transient by default.
+    let url = Url::parse(&format!("http://{addr}/503"))?;
+    let response = client.post(url).send().await?;
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(requests.swap(0, Ordering::SeqCst), 2);
+
+    Ok(())
+}
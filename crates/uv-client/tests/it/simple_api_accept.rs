@@ -0,0 +1,83 @@
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use http_body_util::Full;
+use hyper::body::Bytes;
+use hyper::header::{ACCEPT, CONTENT_TYPE};
+use hyper::Response;
+use url::Url;
+
+use distribution_types::{IndexLocations, IndexUrl, IndexUrls};
+use pep508_rs::VerbatimUrl;
+use uv_cache::Cache;
+use uv_client::{RegistryClientBuilder, SimpleApiAccept};
+use uv_normalize::PackageName;
+
+use crate::spawn_http_server;
+
+#[tokio::test]
+async fn simple_api_accept() -> Result<()> {
+    // Record the `Accept` header of each request.
+    let accepts = Arc::new(Mutex::new(Vec::new()));
+    let recorded = accepts.clone();
+    let addr = spawn_http_server(move |req| {
+        recorded.lock().unwrap().push(
+            req.headers()
+                .get(ACCEPT)
+                .map(|value| value.to_str().unwrap().to_string()),
+        );
+        let mut response = Response::new(Full::new(Bytes::from("<html></html>")));
+        response
+            .headers_mut()
+            .insert(CONTENT_TYPE, "text/html".parse().unwrap());
+        async { response }
+    })
+    .await?;
+    let index = IndexUrl::from(VerbatimUrl::from_url(Url::parse(&format!(
+        "http://{addr}/simple/"
+    ))?));
+    let index_urls = IndexUrls::from(IndexLocations::new(
+        Some(index.clone()),
+        vec![],
+        vec![],
+        false,
+    ));
+    let package = PackageName::from_str("flask")?;
+
+    // By default, prefer JSON, with an HTML fallback.
+    let client = RegistryClientBuilder::new(Cache::temp()?.init()?)
+        .index_urls(index_urls.clone())
+        .build();
+    client.simple(&package).await?;
+
+    // The preference can be set for every index...
+    let client = RegistryClientBuilder::new(Cache::temp()?.init()?)
+        .index_urls(index_urls.clone())
+        .simple_api_accept(SimpleApiAccept::JsonOnly)
+        .build();
+    client.simple(&package).await?;
+
+    // ...and overridden for a specific index.
+    let client = RegistryClientBuilder::new(Cache::temp()?.init()?)
+        .index_urls(index_urls)
+        .simple_api_accept(SimpleApiAccept::JsonOnly)
+        .index_simple_api_accept(index, SimpleApiAccept::HtmlOnly)
+        .build();
+    client.simple(&package).await?;
+
+    assert_eq!(
+        *accepts.lock().unwrap(),
+        vec![
+            Some(SimpleApiAccept::PreferJson.header().to_string()),
+            Some(SimpleApiAccept::JsonOnly.header().to_string()),
+            Some(SimpleApiAccept::HtmlOnly.header().to_string()),
+        ]
+    );
+    assert_eq!(
+        SimpleApiAccept::PreferJson.header(),
+        "application/vnd.pypi.simple.v1+json, application/vnd.pypi.simple.v1+html;q=0.2, text/html;q=0.01"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use anyhow::Result;
+use reqwest::StatusCode;
+use url::Url;
+
+use uv_client::{BaseClientBuilder, Connectivity, UrlRewrite};
+
+#[tokio::test]
+async fn url_rewrite_to_local_mirror() -> Result<()> {
+    let mirror = tempfile::tempdir()?;
+    fs_err::create_dir(mirror.path().join("flask"))?;
+    fs_err::write(
+        mirror.path().join("flask").join("index.html"),
+        "<a href=\"flask-3.0.3-py3-none-any.whl\">flask-3.0.3-py3-none-any.whl</a>",
+    )?;
+    let mirror_url = Url::from_directory_path(mirror.path()).unwrap();
+
+    // Rewrite PyPI to the local mirror; in offline mode, any request that isn't rewritten fails.
+    let client = BaseClientBuilder::new()
+        .connectivity(Connectivity::Offline)
+        .url_rewrites(vec![UrlRewrite::new(
+            "https://pypi.org/simple/",
+            mirror_url.as_str(),
+        )])
+        .build();
+
+    let response = client
+        .send_get(Url::parse("https://pypi.org/simple/flask/")?)
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.url(), &mirror_url.join("flask/")?);
+    assert!(response
+        .text()
+        .await?
+        .contains("flask-3.0.3-py3-none-any.whl"));
+
+    // Non-matching URLs should be left untouched.
+    let err = client
+        .send_get(Url::parse("https://example.com/simple/flask/")?)
+        .await
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("https://example.com/simple/flask/"));
+
+    Ok(())
+}
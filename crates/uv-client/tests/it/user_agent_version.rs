@@ -1,51 +1,38 @@
+use std::net::SocketAddr;
+
 use anyhow::Result;
 use futures::future;
 use http_body_util::Full;
 use hyper::body::Bytes;
 use hyper::header::USER_AGENT;
-use hyper::server::conn::http1;
-use hyper::service::service_fn;
-use hyper::{Request, Response};
-use hyper_util::rt::TokioIo;
+use hyper::Response;
 use insta::{assert_json_snapshot, assert_snapshot, with_settings};
 use pep508_rs::{MarkerEnvironment, MarkerEnvironmentBuilder};
 use platform_tags::{Arch, Os, Platform};
-use tokio::net::TcpListener;
 use uv_cache::Cache;
 use uv_client::LineHaul;
-use uv_client::RegistryClientBuilder;
+use uv_client::{BaseClientBuilder, RegistryClientBuilder};
 use uv_version::version;
 
+use crate::spawn_http_server;
+
+/// Spawn a server that echoes the `User-Agent` header of each request in the response body.
+async fn spawn_user_agent_server() -> Result<SocketAddr> {
+    spawn_http_server(|req| {
+        let user_agent = req
+            .headers()
+            .get(USER_AGENT)
+            .and_then(|v| v.to_str().ok())
+            .map(|s| s.to_string())
+            .unwrap_or_default(); // Empty Default
+        future::ready(Response::new(Full::new(Bytes::from(user_agent))))
+    })
+    .await
+}
+
 #[tokio::test]
 async fn test_user_agent_has_version() -> Result<()> {
-    // Set up the TCP listener on a random available port
-    let listener = TcpListener::bind("127.0.0.1:0").await?;
-    let addr = listener.local_addr()?;
-
-    // Spawn the server loop in a background task
-    let server_task = tokio::spawn(async move {
-        let svc = service_fn(move |req: Request<hyper::body::Incoming>| {
-            // Get User Agent Header and send it back in the response
-            let user_agent = req
-                .headers()
-                .get(USER_AGENT)
-                .and_then(|v| v.to_str().ok())
-                .map(|s| s.to_string())
-                .unwrap_or_default(); // Empty Default
-            future::ok::<_, hyper::Error>(Response::new(Full::new(Bytes::from(user_agent))))
-        });
-        // Start Server (not wrapped in loop {} since we want a single response server)
-        // If you want server to accept multiple connections, wrap it in loop {}
-        let (socket, _) = listener.accept().await.unwrap();
-        let socket = TokioIo::new(socket);
-        tokio::task::spawn(async move {
-            http1::Builder::new()
-                .serve_connection(socket, svc)
-                .with_upgrades()
-                .await
-                .expect("Server Started");
-        });
-    });
+    let addr = spawn_user_agent_server().await?;
 
     // Initialize uv-client
     let cache = Cache::temp()?.init()?;
@@ -68,42 +55,15 @@ async fn test_user_agent_has_version() -> Result<()> {
     // Verify body matches regex
     assert_eq!(body, format!("uv/{}", version()));
 
-    // Wait for the server task to complete, to be a good citizen.
-    server_task.await?;
+    // Verify the user agent matches the one exposed by the client
+    assert_eq!(body, client.uncached_client().user_agent());
 
     Ok(())
 }
 
 #[tokio::test]
 async fn test_user_agent_has_linehaul() -> Result<()> {
-    // Set up the TCP listener on a random available port
-    let listener = TcpListener::bind("127.0.0.1:0").await?;
-    let addr = listener.local_addr()?;
-
-    // Spawn the server loop in a background task
-    let server_task = tokio::spawn(async move {
-        let svc = service_fn(move |req: Request<hyper::body::Incoming>| {
-            // Get User Agent Header and send it back in the response
-            let user_agent = req
-                .headers()
-                .get(USER_AGENT)
-                .and_then(|v| v.to_str().ok())
-                .map(|s| s.to_string())
-                .unwrap_or_default(); // Empty Default
-            future::ok::<_, hyper::Error>(Response::new(Full::new(Bytes::from(user_agent))))
-        });
-        // Start Server (not wrapped in loop {} since we want a single response server)
-        // If you want server to accept multiple connections, wrap it in loop {}
-        let (socket, _) = listener.accept().await.unwrap();
-        let socket = TokioIo::new(socket);
-        tokio::task::spawn(async move {
-            http1::Builder::new()
-                .serve_connection(socket, svc)
-                .with_upgrades()
-                .await
-                .expect("Server Started");
-        });
-    });
+    let addr = spawn_user_agent_server().await?;
 
     // Add some representative markers for an Ubuntu CI runner
     let markers = MarkerEnvironment::try_from(MarkerEnvironmentBuilder {
@@ -160,8 +120,8 @@ async fn test_user_agent_has_linehaul() -> Result<()> {
     // Check User Agent
     let body = res.text().await?;
 
-    // Wait for the server task to complete, to be a good citizen.
-    server_task.await?;
+    // Verify the user agent matches the one exposed by the client
+    assert_eq!(body, client.uncached_client().user_agent());
 
     // Unpack User-Agent with linehaul
     let (uv_version, uv_linehaul) = body
@@ -251,3 +211,20 @@ async fn test_user_agent_has_linehaul() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_user_agent_prefix() {
+    let client = BaseClientBuilder::new()
+        .user_agent_prefix("mycorp-ci/1.0")
+        .build();
+    assert_eq!(
+        client.user_agent(),
+        format!("mycorp-ci/1.0 uv/{}", version())
+    );
+
+    // Invalid prefixes should be ignored, rather than corrupting the header.
+    for prefix in ["", "mycorp ci\r\n", "mycorp  ci", "mycorp(ci)"] {
+        let client = BaseClientBuilder::new().user_agent_prefix(prefix).build();
+        assert_eq!(client.user_agent(), format!("uv/{}", version()));
+    }
+}
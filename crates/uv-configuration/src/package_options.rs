@@ -1,6 +1,8 @@
+use distribution_types::Requirement;
 use pep508_rs::PackageName;
 
-use rustc_hash::FxHashSet;
+use either::Either;
+use rustc_hash::FxHashMap;
 
 /// Whether to reinstall packages.
 #[derive(Debug, Default, Clone)]
@@ -53,13 +55,14 @@ pub enum Upgrade {
     /// Allow package upgrades for all packages, ignoring the existing lockfile.
     All,
 
-    /// Allow package upgrades, but only for the specified packages.
-    Packages(FxHashSet<PackageName>),
+    /// Allow package upgrades, but only for the specified packages, within the given requirements
+    /// (e.g., `requests<2.32`).
+    Packages(FxHashMap<PackageName, Vec<Requirement>>),
 }
 
 impl Upgrade {
     /// Determine the upgrade strategy from the command-line arguments.
-    pub fn from_args(upgrade: Option<bool>, upgrade_package: Vec<PackageName>) -> Self {
+    pub fn from_args(upgrade: Option<bool>, upgrade_package: Vec<Requirement>) -> Self {
         match upgrade {
             Some(true) => Self::All,
             Some(false) => Self::None,
@@ -67,7 +70,15 @@ impl Upgrade {
                 if upgrade_package.is_empty() {
                     Self::None
                 } else {
-                    Self::Packages(upgrade_package.into_iter().collect())
+                    let mut packages: FxHashMap<PackageName, Vec<Requirement>> =
+                        FxHashMap::default();
+                    for requirement in upgrade_package {
+                        packages
+                            .entry(requirement.name.clone())
+                            .or_default()
+                            .push(requirement);
+                    }
+                    Self::Packages(packages)
                 }
             }
         }
@@ -82,4 +93,23 @@ impl Upgrade {
     pub fn is_all(&self) -> bool {
         matches!(self, Self::All)
     }
+
+    /// Returns `true` if the given package should be upgraded.
+    pub fn contains(&self, package_name: &PackageName) -> bool {
+        match self {
+            Self::None => false,
+            Self::All => true,
+            Self::Packages(packages) => packages.contains_key(package_name),
+        }
+    }
+
+    /// Returns the requirements that the upgraded packages are constrained to (e.g., `requests<2.32`
+    /// for `--upgrade-package "requests<2.32"`).
+    pub fn constraints(&self) -> impl Iterator<Item = &Requirement> {
+        if let Self::Packages(packages) = self {
+            Either::Right(packages.values().flatten())
+        } else {
+            Either::Left(std::iter::empty())
+        }
+    }
 }
@@ -145,7 +145,7 @@ impl<'a> BuildContext for BuildDispatch<'a> {
         let tags = self.interpreter.tags()?;
         let resolver = Resolver::new(
             Manifest::simple(requirements.to_vec()),
-            self.options,
+            self.options.clone(),
             &python_requirement,
             Some(markers),
             tags,
@@ -3,6 +3,7 @@ use std::io;
 use std::path::Path;
 use std::pin::Pin;
 use std::rc::Rc;
+use std::str::FromStr;
 use std::sync::Arc;
 use std::task::{Context, Poll};
 
@@ -52,6 +53,7 @@ pub struct DistributionDatabase<'a, Context: BuildContext> {
     locks: Rc<Locks>,
     client: ManagedClient<'a>,
     reporter: Option<Arc<dyn Reporter>>,
+    strict_filenames: bool,
 }
 
 impl<'a, Context: BuildContext> DistributionDatabase<'a, Context> {
@@ -66,6 +68,21 @@ impl<'a, Context: BuildContext> DistributionDatabase<'a, Context> {
             locks: Rc::new(Locks::default()),
             client: ManagedClient::new(client, concurrent_downloads),
             reporter: None,
+            strict_filenames: false,
+        }
+    }
+
+    /// Sets whether to validate the filename of each downloaded wheel, as served by the index
+    /// (via the `Content-Disposition` header or the final URL, after any redirects), against the
+    /// expected filename.
+    ///
+    /// If enabled, a mismatched name, version, or set of tags is reported as an error, which
+    /// catches misconfigured mirrors. Wheels served without a recognizable filename are accepted.
+    #[must_use]
+    pub fn with_strict_filenames(self, strict_filenames: bool) -> Self {
+        Self {
+            strict_filenames,
+            ..self
         }
     }
 
@@ -467,20 +484,31 @@ impl<'a, Context: BuildContext> DistributionDatabase<'a, Context> {
 
         let download = |response: reqwest::Response| {
             async {
+                if self.strict_filenames {
+                    validate_wheel_filename(filename, response.headers(), response.url())?;
+                }
+
                 let progress = self
                     .reporter
                     .as_ref()
                     .map(|reporter| (reporter, reporter.on_download_start(dist.name(), size)));
 
-                let reader = response
-                    .bytes_stream()
-                    .map_err(|err| self.handle_response_errors(err))
-                    .into_async_read();
+                let reader = uv_client::checked_bytes_stream(response, |err| {
+                    self.handle_response_errors(err)
+                })
+                .into_async_read();
 
                 // Create a hasher for each hash algorithm.
                 let algorithms = hashes.algorithms();
                 let mut hashers = algorithms.into_iter().map(Hasher::from).collect::<Vec<_>>();
-                let mut hasher = uv_extract::hash::HashReader::new(reader.compat(), &mut hashers);
+                let reader = tokio::io::BufReader::with_capacity(
+                    self.client
+                        .unmanaged
+                        .uncached_client()
+                        .download_buffer_size(),
+                    reader.compat(),
+                );
+                let mut hasher = uv_extract::hash::HashReader::new(reader, &mut hashers);
 
                 // Download and unzip the wheel to a temporary directory.
                 let temp_dir = tempfile::tempdir_in(self.build_context.cache().root())
@@ -587,35 +615,54 @@ impl<'a, Context: BuildContext> DistributionDatabase<'a, Context> {
 
         let download = |response: reqwest::Response| {
             async {
+                if self.strict_filenames {
+                    validate_wheel_filename(filename, response.headers(), response.url())?;
+                }
+
                 let progress = self
                     .reporter
                     .as_ref()
                     .map(|reporter| (reporter, reporter.on_download_start(dist.name(), size)));
 
-                let reader = response
-                    .bytes_stream()
-                    .map_err(|err| self.handle_response_errors(err))
-                    .into_async_read();
+                let content_length = response.content_length();
+                let reader = uv_client::checked_bytes_stream(response, |err| {
+                    self.handle_response_errors(err)
+                })
+                .into_async_read();
 
-                // Download the wheel to a temporary file.
+                // Download the wheel to a temporary file, pre-allocated to the declared size.
                 let temp_file = tempfile::tempfile_in(self.build_context.cache().root())
                     .map_err(Error::CacheWrite)?;
-                let mut writer = tokio::io::BufWriter::new(tokio::fs::File::from_std(temp_file));
+                if let Some(content_length) = content_length {
+                    temp_file
+                        .set_len(content_length)
+                        .map_err(Error::CacheWrite)?;
+                }
+                let download_buffer_size = self
+                    .client
+                    .unmanaged
+                    .uncached_client()
+                    .download_buffer_size();
+                let mut reader =
+                    tokio::io::BufReader::with_capacity(download_buffer_size, reader.compat());
+                let mut writer = tokio::io::BufWriter::with_capacity(
+                    download_buffer_size,
+                    tokio::fs::File::from_std(temp_file),
+                );
 
                 match progress {
                     Some((reporter, progress)) => {
                         // Wrap the reader in a progress reporter. This will report 100% progress
                         // after the download is complete, even if we still have to unzip and hash
                         // part of the file.
-                        let mut reader =
-                            ProgressReader::new(reader.compat(), progress, &**reporter);
+                        let mut reader = ProgressReader::new(reader, progress, &**reporter);
 
                         tokio::io::copy(&mut reader, &mut writer)
                             .await
                             .map_err(Error::CacheWrite)?;
                     }
                     None => {
-                        tokio::io::copy(&mut reader.compat(), &mut writer)
+                        tokio::io::copy(&mut reader, &mut writer)
                             .await
                             .map_err(Error::CacheWrite)?;
                     }
@@ -901,6 +948,54 @@ fn content_length(req: &reqwest::Request) -> Option<u64> {
         .and_then(|val| val.parse::<u64>().ok())
 }
 
+/// Returns the filename of the wheel served in the given response, as indicated by the
+/// `Content-Disposition` header, or otherwise by the last segment of the final URL.
+///
+/// Returns `None` if neither looks like a wheel filename (e.g., for an opaque object storage
+/// URL).
+fn served_wheel_filename(headers: &reqwest::header::HeaderMap, url: &Url) -> Option<String> {
+    headers
+        .get(reqwest::header::CONTENT_DISPOSITION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| {
+            value.split(';').find_map(|param| {
+                let (key, value) = param.split_once('=')?;
+                key.trim()
+                    .eq_ignore_ascii_case("filename")
+                    .then(|| value.trim().trim_matches('"').to_string())
+            })
+        })
+        .or_else(|| {
+            url.path_segments()
+                .and_then(Iterator::last)
+                .map(ToString::to_string)
+        })
+        .filter(|filename| {
+            std::path::Path::new(filename)
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("whl"))
+        })
+}
+
+/// Validate that the wheel served with the given headers, from the given URL, matches the
+/// expected filename.
+fn validate_wheel_filename(
+    expected: &WheelFilename,
+    headers: &reqwest::header::HeaderMap,
+    url: &Url,
+) -> Result<(), Error> {
+    let Some(served) = served_wheel_filename(headers, url) else {
+        return Ok(());
+    };
+    match WheelFilename::from_str(&served) {
+        Ok(filename) if filename == *expected => Ok(()),
+        _ => Err(Error::WheelFilenameMismatch {
+            expected: expected.clone(),
+            served,
+        }),
+    }
+}
+
 /// An asynchronous reader that reports progress as bytes are read.
 struct ProgressReader<'a, R> {
     reader: R,
@@ -1001,3 +1096,63 @@ impl LocalArchivePointer {
         self.archive
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use reqwest::header::{HeaderMap, HeaderValue, CONTENT_DISPOSITION};
+    use url::Url;
+
+    use distribution_filename::WheelFilename;
+
+    use crate::Error;
+
+    use super::{served_wheel_filename, validate_wheel_filename};
+
+    #[test]
+    fn served_filename() {
+        let url = Url::parse("https://files.example.com/flask-3.0.3-py3-none-any.whl").unwrap();
+        let expected = WheelFilename::from_str("flask-3.0.3-py3-none-any.whl").unwrap();
+
+        // By default, use the last segment of the URL.
+        let served = served_wheel_filename(&HeaderMap::new(), &url).unwrap();
+        assert_eq!(WheelFilename::from_str(&served).unwrap(), expected);
+
+        // Prefer the `Content-Disposition` header, if present.
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            CONTENT_DISPOSITION,
+            HeaderValue::from_static("attachment; filename=\"flask-3.0.2-py3-none-any.whl\""),
+        );
+        let served = served_wheel_filename(&headers, &url).unwrap();
+        assert_eq!(served, "flask-3.0.2-py3-none-any.whl");
+        assert_ne!(WheelFilename::from_str(&served).unwrap(), expected);
+
+        // Ignore URLs that don't look like wheels (e.g., signed object storage URLs).
+        let url = Url::parse("https://storage.example.com/blobs/abc123?signature=def").unwrap();
+        assert_eq!(served_wheel_filename(&HeaderMap::new(), &url), None);
+    }
+
+    #[test]
+    fn wheel_filename_mismatch() {
+        let expected = WheelFilename::from_str("flask-3.0.3-py3-none-any.whl").unwrap();
+
+        // A wheel served under the expected filename should be accepted.
+        let url = Url::parse("https://mirror.example.com/flask-3.0.3-py3-none-any.whl").unwrap();
+        assert!(validate_wheel_filename(&expected, &HeaderMap::new(), &url).is_ok());
+
+        // A wheel for a different version should be rejected.
+        let url = Url::parse("https://mirror.example.com/flask-3.0.2-py3-none-any.whl").unwrap();
+        let err = validate_wheel_filename(&expected, &HeaderMap::new(), &url).unwrap_err();
+        assert!(
+            matches!(&err, Error::WheelFilenameMismatch { served, .. } if served == "flask-3.0.2-py3-none-any.whl"),
+            "{err}"
+        );
+
+        // As should a wheel for different tags.
+        let url =
+            Url::parse("https://mirror.example.com/flask-3.0.3-cp312-cp312-win_amd64.whl").unwrap();
+        assert!(validate_wheel_filename(&expected, &HeaderMap::new(), &url).is_err());
+    }
+}
@@ -3,7 +3,7 @@ use std::path::PathBuf;
 use tokio::task::JoinError;
 use zip::result::ZipError;
 
-use distribution_filename::WheelFilenameError;
+use distribution_filename::{WheelFilename, WheelFilenameError};
 use pep440_rs::Version;
 use pypi_types::HashDigest;
 use uv_client::BetterReqwestError;
@@ -55,6 +55,11 @@ pub enum Error {
     },
     #[error("Package metadata version `{metadata}` does not match given version `{given}`")]
     VersionMismatch { given: Version, metadata: Version },
+    #[error("Downloaded wheel `{served}` does not match the expected wheel `{expected}`")]
+    WheelFilenameMismatch {
+        expected: WheelFilename,
+        served: String,
+    },
     #[error("Failed to parse metadata from built wheel")]
     Metadata(#[from] pypi_types::MetadataError),
     #[error("Failed to read `dist-info` metadata from built wheel")]
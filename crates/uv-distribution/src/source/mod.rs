@@ -569,6 +569,7 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
             Connectivity::Offline => CacheControl::AllowStale,
         };
 
+        let download_buffer_size = client.unmanaged.uncached_client().download_buffer_size();
         let download = |response| {
             async {
                 // At this point, we're seeing a new or updated source distribution. Initialize a
@@ -579,7 +580,14 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
                 debug!("Downloading source distribution: {source}");
                 let entry = cache_shard.shard(revision.id()).entry(filename);
                 let hashes = self
-                    .download_archive(response, source, filename, entry.path(), hashes)
+                    .download_archive(
+                        response,
+                        source,
+                        filename,
+                        entry.path(),
+                        hashes,
+                        download_buffer_size,
+                    )
                     .await?;
 
                 Ok(revision.with_hashes(hashes))
@@ -1185,19 +1193,21 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
         filename: &str,
         target: &Path,
         hashes: HashPolicy<'_>,
+        download_buffer_size: usize,
     ) -> Result<Vec<HashDigest>, Error> {
         let temp_dir =
             tempfile::tempdir_in(self.build_context.cache().bucket(CacheBucket::BuiltWheels))
                 .map_err(Error::CacheWrite)?;
-        let reader = response
-            .bytes_stream()
-            .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))
-            .into_async_read();
+        let reader = uv_client::checked_bytes_stream(response, |err| {
+            std::io::Error::new(std::io::ErrorKind::Other, err)
+        })
+        .into_async_read();
 
         // Create a hasher for each hash algorithm.
         let algorithms = hashes.algorithms();
         let mut hashers = algorithms.into_iter().map(Hasher::from).collect::<Vec<_>>();
-        let mut hasher = uv_extract::hash::HashReader::new(reader.compat(), &mut hashers);
+        let reader = tokio::io::BufReader::with_capacity(download_buffer_size, reader.compat());
+        let mut hasher = uv_extract::hash::HashReader::new(reader, &mut hashers);
 
         // Download and unzip the source distribution into a temporary directory.
         let span = info_span!("download_source_dist", filename = filename, source_dist = %source);
@@ -1329,6 +1339,9 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
         // Validate the metadata.
         validate(source, &metadata)?;
 
+        // Record the build requirements, which aren't part of the wheel's metadata.
+        let metadata = with_build_requires(metadata, source_root, subdirectory).await;
+
         debug!("Finished building: {source}");
         Ok((disk_filename, filename, metadata))
     }
@@ -1351,7 +1364,9 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
                 // Validate the metadata.
                 validate(source, &metadata)?;
 
-                return Ok(Some(metadata));
+                return Ok(Some(
+                    with_build_requires(metadata, source_root, subdirectory).await,
+                ));
             }
             Err(err @ (Error::MissingPkgInfo | Error::DynamicPkgInfo(_))) => {
                 debug!("No static `PKG-INFO` available for: {source} ({err:?})");
@@ -1407,7 +1422,9 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
         // Validate the metadata.
         validate(source, &metadata)?;
 
-        Ok(Some(metadata))
+        Ok(Some(
+            with_build_requires(metadata, source_root, subdirectory).await,
+        ))
     }
 
     /// Build a single directory into an editable wheel
@@ -1614,6 +1631,32 @@ async fn read_pyproject_toml(
     Ok(metadata)
 }
 
+/// Record the PEP 518 build requirements from a source distribution's `pyproject.toml` file, if
+/// any, on metadata that was read from elsewhere (e.g., `PKG-INFO`, or a built wheel).
+///
+/// The build requirements are informational, so a missing or invalid `pyproject.toml` is ignored.
+async fn with_build_requires(
+    mut metadata: Metadata23,
+    source_tree: &Path,
+    subdirectory: Option<&Path>,
+) -> Metadata23 {
+    let pyproject_toml = match subdirectory {
+        Some(subdirectory) => source_tree.join(subdirectory).join("pyproject.toml"),
+        None => source_tree.join("pyproject.toml"),
+    };
+    let Ok(content) = fs::read_to_string(&pyproject_toml).await else {
+        return metadata;
+    };
+    match Metadata23::parse_build_requires(&content) {
+        Ok(build_requires) => metadata.build_requires = build_requires,
+        Err(err) => debug!(
+            "Ignoring invalid build requirements in {}: {err}",
+            pyproject_toml.display()
+        ),
+    }
+    metadata
+}
+
 /// Read an existing cached [`Metadata23`], if it exists.
 async fn read_cached_metadata(cache_entry: &CacheEntry) -> Result<Option<Metadata23>, Error> {
     match fs::read(&cache_entry.path()).await {
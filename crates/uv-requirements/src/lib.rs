@@ -7,6 +7,8 @@ pub use crate::workspace::*;
 
 mod confirm;
 mod lookahead;
+mod pipenv;
+mod poetry;
 pub mod pyproject;
 mod source_tree;
 mod sources;
@@ -0,0 +1,607 @@
+//! Support for importing [Pipenv](https://pipenv.pypa.io/) `Pipfile` and `Pipfile.lock` files.
+//!
+//! Pipenv groups packages into categories: the default category (`[packages]` in a `Pipfile`, or
+//! `default` in a `Pipfile.lock`) is always included, while the development category
+//! (`[dev-packages]`, or `develop`) and any custom categories are treated as extras, such that
+//! they can be included via, e.g., `--extra dev`.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use serde::de::IgnoredAny;
+use serde::Deserialize;
+use url::Url;
+
+use distribution_types::{IndexUrl, UnresolvedRequirementSpecification};
+use pep508_rs::{expand_env_vars, Pep508Error};
+use pypi_types::{HashAlgorithm, VerbatimParsedUrl};
+use requirements_txt::{
+    EditableRequirement, RequirementEntry, RequirementsTxtParserError, RequirementsTxtRequirement,
+};
+use uv_normalize::{ExtraName, InvalidNameError, PackageName};
+
+use crate::{ExtrasSpecification, RequirementsSpecification};
+
+/// The environment markers that may be specified as keys on a package (e.g.,
+/// `pywin32 = { version = "*", sys_platform = "== 'win32'" }`).
+const MARKER_KEYS: &[&str] = &[
+    "os_name",
+    "sys_platform",
+    "platform_machine",
+    "platform_python_implementation",
+    "platform_release",
+    "platform_system",
+    "platform_version",
+    "python_version",
+    "python_full_version",
+    "implementation_name",
+    "implementation_version",
+];
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum PipenvError {
+    #[error("Invalid package name in `[{0}]`")]
+    InvalidName(String, #[source] InvalidNameError),
+    #[error("Package `{0}` uses an unsupported version control system: `{1}`")]
+    UnsupportedVcs(PackageName, String),
+    #[error("Invalid source URL: `{0}`")]
+    InvalidSourceUrl(String, #[source] url::ParseError),
+    #[error("Package `{0}` requires an undeclared source: `{1}`")]
+    UnknownIndex(PackageName, String),
+    #[error("Failed to convert path to URL: `{}`", _0.display())]
+    InvalidPath(PathBuf),
+    #[error(transparent)]
+    Requirement(#[from] Box<Pep508Error<VerbatimParsedUrl>>),
+    #[error(transparent)]
+    Editable(#[from] RequirementsTxtParserError),
+}
+
+/// A `Pipfile`, which declares a project's (unpinned) dependencies.
+#[derive(Debug, Deserialize)]
+pub(crate) struct Pipfile {
+    #[serde(default)]
+    source: Vec<PipenvSource>,
+    #[serde(flatten)]
+    categories: BTreeMap<String, BTreeMap<String, PipenvEntry>>,
+}
+
+/// A `Pipfile.lock`, which pins every package required by a project, along with its hashes.
+#[derive(Debug, Deserialize)]
+pub(crate) struct PipfileLock {
+    #[serde(rename = "_meta", default)]
+    meta: PipfileLockMeta,
+    #[serde(flatten)]
+    categories: BTreeMap<String, BTreeMap<String, PipenvEntry>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct PipfileLockMeta {
+    #[serde(default)]
+    sources: Vec<PipenvSource>,
+}
+
+/// A package index, as declared in a `[[source]]` table.
+#[derive(Debug, Deserialize)]
+struct PipenvSource {
+    url: String,
+    name: Option<String>,
+}
+
+/// A package in a Pipenv category.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum PipenvEntry {
+    /// A bare version specifier, e.g., `requests = "*"` or `requests = ">=2.0"`.
+    Specifier(String),
+    /// A package table, e.g., `requests = { version = "*", extras = ["socks"] }`.
+    Table(PipenvTable),
+    /// Any other value (e.g., in a non-package table like `[pipenv]`).
+    Other(IgnoredAny),
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+struct PipenvTable {
+    version: Option<String>,
+    #[serde(default)]
+    extras: Vec<ExtraName>,
+    markers: Option<String>,
+    #[serde(default)]
+    hashes: Vec<String>,
+    /// The name of the `[[source]]` from which the package should be installed.
+    index: Option<String>,
+    #[serde(default)]
+    editable: bool,
+    path: Option<String>,
+    file: Option<String>,
+    git: Option<String>,
+    hg: Option<String>,
+    svn: Option<String>,
+    bzr: Option<String>,
+    r#ref: Option<String>,
+    subdirectory: Option<String>,
+    /// Any other keys, including environment markers (e.g., `sys_platform = "== 'win32'"`).
+    #[serde(flatten)]
+    other: BTreeMap<String, PipenvValue>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum PipenvValue {
+    String(String),
+    Other(IgnoredAny),
+}
+
+impl Pipfile {
+    /// Convert the `Pipfile` into a [`RequirementsSpecification`].
+    ///
+    /// Relative paths are resolved against `install_path` (i.e., the directory containing the
+    /// `Pipfile`).
+    pub(crate) fn to_specification(
+        &self,
+        extras: &ExtrasSpecification,
+        install_path: &Path,
+    ) -> Result<RequirementsSpecification, PipenvError> {
+        to_specification(
+            &self.source,
+            &self.categories,
+            Category::from_pipfile,
+            extras,
+            install_path,
+        )
+    }
+}
+
+impl PipfileLock {
+    /// Convert the `Pipfile.lock` into a [`RequirementsSpecification`] that pins every package,
+    /// along with its markers and hashes.
+    ///
+    /// Relative paths are resolved against `install_path` (i.e., the directory containing the
+    /// `Pipfile.lock`).
+    pub(crate) fn to_specification(
+        &self,
+        extras: &ExtrasSpecification,
+        install_path: &Path,
+    ) -> Result<RequirementsSpecification, PipenvError> {
+        to_specification(
+            &self.meta.sources,
+            &self.categories,
+            Category::from_pipfile_lock,
+            extras,
+            install_path,
+        )
+    }
+}
+
+/// A category of packages.
+enum Category<'a> {
+    /// The default category, which is always included.
+    Default,
+    /// A category that's included when requested as an extra.
+    Extra(&'a str),
+    /// A table that doesn't contain packages (e.g., `[requires]`).
+    Ignored,
+}
+
+impl<'a> Category<'a> {
+    fn from_pipfile(name: &'a str) -> Self {
+        match name {
+            "packages" => Self::Default,
+            "dev-packages" => Self::Extra("dev"),
+            "requires" | "scripts" | "pipenv" => Self::Ignored,
+            name => Self::Extra(name),
+        }
+    }
+
+    fn from_pipfile_lock(name: &'a str) -> Self {
+        match name {
+            "default" => Self::Default,
+            "develop" => Self::Extra("dev"),
+            name => Self::Extra(name),
+        }
+    }
+}
+
+/// Convert the packages in the included categories into a [`RequirementsSpecification`], using
+/// the first source as the index URL.
+///
+/// As in Pipenv, any subsequent sources are only used by the packages that request them by name
+/// (e.g., `requests = { version = "*", index = "internal" }`), and are added as extra index URLs.
+fn to_specification<'a>(
+    sources: &[PipenvSource],
+    categories: &'a BTreeMap<String, BTreeMap<String, PipenvEntry>>,
+    category: fn(&'a str) -> Category<'a>,
+    extras: &ExtrasSpecification,
+    install_path: &Path,
+) -> Result<RequirementsSpecification, PipenvError> {
+    let mut spec = RequirementsSpecification::default();
+
+    let index_urls = sources
+        .iter()
+        .map(|source| {
+            let url = expand_env_vars(&source.url);
+            IndexUrl::from_str(&url)
+                .map_err(|err| PipenvError::InvalidSourceUrl(source.url.clone(), err))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    spec.index_url = index_urls.first().cloned();
+
+    for (name, packages) in categories {
+        match category(name) {
+            Category::Default => {}
+            Category::Extra(extra) => {
+                let Ok(extra) = ExtraName::from_str(extra) else {
+                    continue;
+                };
+                if !extras.contains(&extra) {
+                    continue;
+                }
+                spec.extras.insert(extra);
+            }
+            Category::Ignored => continue,
+        }
+
+        for (package, entry) in packages {
+            let package = PackageName::from_str(package)
+                .map_err(|err| PipenvError::InvalidName(name.clone(), err))?;
+            let table = match entry {
+                PipenvEntry::Specifier(version) => PipenvTable {
+                    version: Some(version.clone()),
+                    ..PipenvTable::default()
+                },
+                PipenvEntry::Table(table) => table.clone(),
+                PipenvEntry::Other(_) => continue,
+            };
+
+            if let Some(index) = &table.index {
+                let Some(position) = sources
+                    .iter()
+                    .position(|source| source.name.as_deref() == Some(index.as_str()))
+                else {
+                    return Err(PipenvError::UnknownIndex(package, index.clone()));
+                };
+                let index_url = &index_urls[position];
+                if position > 0 && !spec.extra_index_urls.contains(index_url) {
+                    spec.extra_index_urls.push(index_url.clone());
+                }
+            }
+
+            let marker = table
+                .marker()
+                .map(|marker| format!(" ; {marker}"))
+                .unwrap_or_default();
+
+            // Editable local paths are installed as editables.
+            if let (true, Some(path)) = (table.editable, &table.path) {
+                let editable = EditableRequirement::parse(
+                    &format!("{path}{}{marker}", table.extras()),
+                    None,
+                    install_path,
+                )?;
+                spec.editables.push(editable);
+                continue;
+            }
+
+            let (requirement, hashes) = table.to_requirement(&package, install_path)?;
+            let requirement =
+                RequirementsTxtRequirement::parse(&format!("{requirement}{marker}"), install_path)?;
+            spec.requirements
+                .push(UnresolvedRequirementSpecification::from(RequirementEntry {
+                    requirement,
+                    hashes,
+                }));
+        }
+    }
+
+    Ok(spec)
+}
+
+impl PipenvTable {
+    /// Return the PEP 508 requirement (without markers) for the package, along with its hashes.
+    fn to_requirement(
+        &self,
+        name: &PackageName,
+        install_path: &Path,
+    ) -> Result<(String, Vec<String>), PipenvError> {
+        let extras = self.extras();
+
+        for (vcs, url) in [
+            ("git", &self.git),
+            ("hg", &self.hg),
+            ("svn", &self.svn),
+            ("bzr", &self.bzr),
+        ] {
+            let Some(url) = url else {
+                continue;
+            };
+            if vcs != "git" {
+                return Err(PipenvError::UnsupportedVcs(name.clone(), vcs.to_string()));
+            }
+            let mut requirement = if url.starts_with("git+") {
+                format!("{name}{extras} @ {url}")
+            } else {
+                format!("{name}{extras} @ git+{url}")
+            };
+            if let Some(reference) = &self.r#ref {
+                requirement.push('@');
+                requirement.push_str(reference);
+            }
+            if let Some(subdirectory) = &self.subdirectory {
+                requirement.push_str("#subdirectory=");
+                requirement.push_str(subdirectory);
+            }
+            return Ok((requirement, vec![]));
+        }
+
+        if let Some(location) = self.file.as_ref().or(self.path.as_ref()) {
+            let url = if location.contains("://") {
+                location.clone()
+            } else {
+                let path = install_path.join(location);
+                let path = uv_fs::normalize_path(&path)
+                    .map_err(|_| PipenvError::InvalidPath(path.clone()))?;
+                Url::from_file_path(&path)
+                    .map_err(|()| PipenvError::InvalidPath(path.clone()))?
+                    .to_string()
+            };
+            return Ok((format!("{name}{extras} @ {url}"), self.hashes()));
+        }
+
+        let version = match self.version.as_deref().map(str::trim) {
+            None | Some("*" | "") => String::new(),
+            // Pipenv treats a bare version (e.g., `1.0`) as an exact match.
+            Some(version) if version.starts_with(|c: char| c.is_ascii_digit()) => {
+                format!("=={version}")
+            }
+            Some(version) => version.to_string(),
+        };
+        Ok((format!("{name}{extras}{version}"), self.hashes()))
+    }
+
+    /// Return the extras for the package, formatted for inclusion in a requirement (e.g.,
+    /// `[socks]`).
+    fn extras(&self) -> String {
+        if self.extras.is_empty() {
+            String::new()
+        } else {
+            format!(
+                "[{}]",
+                self.extras
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(",")
+            )
+        }
+    }
+
+    /// Return the marker for the package, combining the `markers` key with any environment
+    /// marker keys (e.g., `sys_platform = "== 'win32'"`).
+    fn marker(&self) -> Option<String> {
+        let markers = self
+            .markers
+            .iter()
+            .cloned()
+            .chain(MARKER_KEYS.iter().filter_map(|key| {
+                let PipenvValue::String(value) = self.other.get(*key)? else {
+                    return None;
+                };
+                Some(format!("{key} {}", value.trim()))
+            }))
+            .collect::<Vec<_>>();
+        match markers.as_slice() {
+            [] => None,
+            [marker] => Some(marker.clone()),
+            markers => Some(
+                markers
+                    .iter()
+                    .map(|marker| format!("({marker})"))
+                    .collect::<Vec<_>>()
+                    .join(" and "),
+            ),
+        }
+    }
+
+    /// Return the supported hashes for the package (e.g., `sha256:...`).
+    fn hashes(&self) -> Vec<String> {
+        self.hashes
+            .iter()
+            .filter(|hash| {
+                hash.split_once(':')
+                    .is_some_and(|(algorithm, _)| HashAlgorithm::from_str(algorithm).is_ok())
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn requirements(spec: &RequirementsSpecification) -> Vec<(String, Vec<String>)> {
+        spec.requirements
+            .iter()
+            .map(|entry| (entry.requirement.to_string(), entry.hashes.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn pipfile() {
+        let data = r#"
+[[source]]
+url = "https://pypi.org/simple"
+verify_ssl = true
+name = "pypi"
+
+[[source]]
+url = "https://example.com/simple"
+verify_ssl = true
+name = "internal"
+
+[[source]]
+url = "https://example.com/unused/simple"
+verify_ssl = true
+name = "unused"
+
+[packages]
+anyio = "*"
+Requests = { version = ">=2.31", extras = ["socks"] }
+pywin32 = { version = "*", sys_platform = "== 'win32'" }
+iniconfig = { git = "https://github.com/pytest-dev/iniconfig", ref = "v2.0.0" }
+private = { version = "*", index = "internal" }
+
+[dev-packages]
+pytest = "==8.0.0"
+
+[docs]
+sphinx = "*"
+
+[requires]
+python_version = "3.12"
+
+[pipenv]
+allow_prereleases = false
+"#;
+        let pipfile: Pipfile = toml::from_str(data).unwrap();
+
+        let spec = pipfile
+            .to_specification(&ExtrasSpecification::None, Path::new("/"))
+            .unwrap();
+        assert_eq!(
+            spec.index_url.as_ref().map(ToString::to_string).as_deref(),
+            Some("https://pypi.org/simple")
+        );
+        assert_eq!(
+            spec.extra_index_urls
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec!["https://example.com/simple"]
+        );
+        insta::assert_debug_snapshot!(requirements(&spec), @r###"
+        [
+            (
+                "requests[socks]>=2.31",
+                [],
+            ),
+            (
+                "anyio",
+                [],
+            ),
+            (
+                "iniconfig @ git+https://github.com/pytest-dev/iniconfig@v2.0.0",
+                [],
+            ),
+            (
+                "private",
+                [],
+            ),
+            (
+                "pywin32 ; sys_platform == 'win32'",
+                [],
+            ),
+        ]
+        "###);
+
+        let extras = ExtrasSpecification::Some(vec![ExtraName::from_str("dev").unwrap()]);
+        let spec = pipfile.to_specification(&extras, Path::new("/")).unwrap();
+        assert!(requirements(&spec)
+            .iter()
+            .any(|(requirement, _)| requirement == "pytest==8.0.0"));
+        assert!(!requirements(&spec)
+            .iter()
+            .any(|(requirement, _)| requirement == "sphinx"));
+    }
+
+    #[test]
+    fn pipfile_unknown_index() {
+        let data = r#"
+[[source]]
+url = "https://pypi.org/simple"
+name = "pypi"
+
+[packages]
+private = { version = "*", index = "internal" }
+"#;
+        let pipfile: Pipfile = toml::from_str(data).unwrap();
+        let err = pipfile
+            .to_specification(&ExtrasSpecification::None, Path::new("/"))
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Package `private` requires an undeclared source: `internal`"
+        );
+    }
+
+    #[test]
+    fn pipfile_lock() {
+        let data = r#"{
+    "_meta": {
+        "hash": {
+            "sha256": "0000000000000000000000000000000000000000000000000000000000000000"
+        },
+        "pipfile-spec": 6,
+        "requires": {
+            "python_version": "3.12"
+        },
+        "sources": [
+            {
+                "name": "pypi",
+                "url": "https://pypi.org/simple",
+                "verify_ssl": true
+            }
+        ]
+    },
+    "default": {
+        "anyio": {
+            "hashes": [
+                "sha256:048e05d0f6caeed70d731f3db756d35dcc1f35747c8c403364a8332c630441b8",
+                "sha256:f75253795a87df48568485fd18cdd2a3fa5c4f7c5be8e5e36637733fce06fed6"
+            ],
+            "index": "pypi",
+            "markers": "python_version >= '3.8'",
+            "version": "==4.3.0"
+        },
+        "project": {
+            "editable": true,
+            "path": "."
+        }
+    },
+    "develop": {
+        "iniconfig": {
+            "hashes": [
+                "sha256:b6a85871a79d2e3b22d2d1b94ac2824226a63c6b741c88f7ae975f18b6778374"
+            ],
+            "index": "pypi",
+            "markers": "python_version >= '3.7'",
+            "version": "==2.0.0"
+        }
+    }
+}"#;
+        let lock: PipfileLock = serde_json::from_str(data).unwrap();
+
+        let extras = ExtrasSpecification::Some(vec![ExtraName::from_str("dev").unwrap()]);
+        let spec = lock
+            .to_specification(&extras, Path::new("/project"))
+            .unwrap();
+        assert_eq!(spec.editables.len(), 1);
+        insta::assert_debug_snapshot!(requirements(&spec), @r###"
+        [
+            (
+                "anyio==4.3.0 ; python_version >= '3.8'",
+                [
+                    "sha256:048e05d0f6caeed70d731f3db756d35dcc1f35747c8c403364a8332c630441b8",
+                    "sha256:f75253795a87df48568485fd18cdd2a3fa5c4f7c5be8e5e36637733fce06fed6",
+                ],
+            ),
+            (
+                "iniconfig==2.0.0 ; python_version >= '3.7'",
+                [
+                    "sha256:b6a85871a79d2e3b22d2d1b94ac2824226a63c6b741c88f7ae975f18b6778374",
+                ],
+            ),
+        ]
+        "###);
+    }
+}
@@ -0,0 +1,1190 @@
+//! Support for importing [Poetry](https://python-poetry.org/) lockfiles (`poetry.lock`).
+//!
+//! A `poetry.lock` doesn't record which packages the project itself requires, nor the markers
+//! under which each locked package applies. Instead, the dependencies declared in the adjacent
+//! `pyproject.toml` are used as the roots of the locked dependency graph, and the markers along
+//! each path through the graph are combined to determine when each locked package is required.
+
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use itertools::Itertools;
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde::Deserialize;
+use url::Url;
+
+use distribution_types::{IndexUrl, UnresolvedRequirementSpecification};
+use pep440_rs::{Version, VersionSpecifier, VersionSpecifiers};
+use pep508_rs::{MarkerTree, Pep508Error};
+use pypi_types::{HashAlgorithm, VerbatimParsedUrl};
+use requirements_txt::{
+    EditableRequirement, RequirementEntry, RequirementsTxtParserError, RequirementsTxtRequirement,
+};
+use uv_normalize::{ExtraName, PackageName};
+
+use crate::{ExtrasSpecification, RequirementsSpecification};
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum PoetryLockError {
+    #[error("Unsupported `poetry.lock` version `{0}` (expected `1.x` or `2.x`)")]
+    UnsupportedVersion(String),
+    #[error("Package `{0}` is required, but missing from `poetry.lock` (run `poetry lock` to update it)")]
+    MissingPackage(PackageName),
+    #[error("Package `{0}` is required with `{1}`, but no matching version is locked in `poetry.lock` (run `poetry lock` to update it)")]
+    MissingVersion(PackageName, String),
+    #[error("Package `{0}` has an invalid marker `{1}`")]
+    InvalidMarker(PackageName, String),
+    #[error("Package `{0}` has an invalid Python constraint `{1}`")]
+    InvalidPython(PackageName, String),
+    #[error("Package `{0}` uses an unsupported source type: `{1}`")]
+    UnsupportedSource(PackageName, String),
+    #[error("Package `{0}` has an invalid source URL: `{1}`")]
+    InvalidSourceUrl(PackageName, String, #[source] url::ParseError),
+    #[error("Failed to convert path to URL: `{}`", _0.display())]
+    InvalidPath(PathBuf),
+    #[error(transparent)]
+    Requirement(#[from] Box<Pep508Error<VerbatimParsedUrl>>),
+    #[error(transparent)]
+    Editable(#[from] RequirementsTxtParserError),
+}
+
+/// A `poetry.lock` file.
+#[derive(Debug, Deserialize)]
+pub(crate) struct PoetryLock {
+    #[serde(default)]
+    package: Vec<PoetryLockPackage>,
+    #[serde(default)]
+    metadata: PoetryLockMetadata,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct PoetryLockMetadata {
+    lock_version: Option<String>,
+    /// The artifacts for each package, in lockfiles prior to version 2.0.
+    #[serde(default)]
+    files: BTreeMap<PackageName, Vec<PoetryLockFile>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct PoetryLockPackage {
+    name: PackageName,
+    version: String,
+    #[serde(default)]
+    develop: bool,
+    #[serde(default)]
+    files: Vec<PoetryLockFile>,
+    #[serde(default)]
+    dependencies: BTreeMap<PackageName, PoetryDependency>,
+    #[serde(default)]
+    extras: BTreeMap<ExtraName, Vec<String>>,
+    source: Option<PoetryLockSource>,
+}
+
+/// A locked artifact, along with its hash (e.g., `sha256:...`).
+#[derive(Debug, Deserialize)]
+struct PoetryLockFile {
+    hash: String,
+}
+
+/// The source of a locked package, if not the default index.
+///
+/// Unlike the rest of the lockfile, Poetry writes these keys in snake case.
+#[derive(Debug, Deserialize)]
+struct PoetryLockSource {
+    r#type: String,
+    url: String,
+    reference: Option<String>,
+    resolved_reference: Option<String>,
+    subdirectory: Option<String>,
+}
+
+/// A dependency, as declared in `[tool.poetry.dependencies]` or in the `[package.dependencies]`
+/// of a locked package.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum PoetryDependency {
+    /// A bare version constraint, e.g., `anyio = "^3.0"`.
+    Constraint(String),
+    /// A dependency table, e.g., `anyio = { version = "^3.0", optional = true }`.
+    Table(PoetryDependencyTable),
+    /// Multiple constraints, each applying under its own markers.
+    Multiple(Vec<PoetryDependencyTable>),
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct PoetryDependencyTable {
+    version: Option<String>,
+    markers: Option<String>,
+    python: Option<String>,
+    #[serde(default)]
+    extras: BTreeSet<ExtraName>,
+    #[serde(default)]
+    optional: bool,
+}
+
+/// The subset of a `pyproject.toml` that declares the dependencies of a Poetry project, either via
+/// `[tool.poetry]` or via PEP 621 metadata (as in Poetry 2.0 and later).
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct PoetryPyProjectToml {
+    project: Option<Project>,
+    tool: Option<Tool>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct Project {
+    #[serde(default)]
+    dependencies: Vec<String>,
+    #[serde(default)]
+    optional_dependencies: BTreeMap<ExtraName, Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct Tool {
+    poetry: Option<ToolPoetry>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct ToolPoetry {
+    #[serde(default)]
+    dependencies: BTreeMap<PackageName, PoetryDependency>,
+    #[serde(default)]
+    dev_dependencies: BTreeMap<PackageName, PoetryDependency>,
+    #[serde(default)]
+    group: BTreeMap<String, ToolPoetryGroup>,
+    #[serde(default)]
+    extras: BTreeMap<ExtraName, Vec<PackageName>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct ToolPoetryGroup {
+    #[serde(default)]
+    optional: bool,
+    #[serde(default)]
+    dependencies: BTreeMap<PackageName, PoetryDependency>,
+}
+
+/// An edge in the locked dependency graph.
+#[derive(Debug)]
+struct Edge {
+    name: PackageName,
+    version: Option<String>,
+    marker: Option<MarkerTree>,
+    extras: BTreeSet<ExtraName>,
+}
+
+impl PoetryLock {
+    /// Convert the `poetry.lock` into a [`RequirementsSpecification`] that pins every package
+    /// required by the project in `pyproject`, along with its hashes and source.
+    ///
+    /// Relative paths are resolved against `install_path` (i.e., the directory containing the
+    /// `poetry.lock` file). Packages from non-default (`legacy`) sources are fetched from those
+    /// sources, which are added as extra index URLs.
+    pub(crate) fn to_specification(
+        &self,
+        pyproject: &PoetryPyProjectToml,
+        extras: &ExtrasSpecification,
+        install_path: &Path,
+    ) -> Result<RequirementsSpecification, PoetryLockError> {
+        if let Some(lock_version) = &self.metadata.lock_version {
+            if !matches!(lock_version.split('.').next(), Some("1" | "2")) {
+                return Err(PoetryLockError::UnsupportedVersion(lock_version.clone()));
+            }
+        }
+
+        let (roots, used_extras) = pyproject.roots(extras)?;
+
+        let mut by_name: FxHashMap<&PackageName, Vec<usize>> = FxHashMap::default();
+        for (index, package) in self.package.iter().enumerate() {
+            by_name.entry(&package.name).or_default().push(index);
+        }
+
+        // Walk the locked dependency graph from the project's dependencies, tracking the paths
+        // under which each package is reachable with a given set of extras, as the (conjoined)
+        // markers along each path. A path without markers indicates that the package is required
+        // unconditionally.
+        let mut reachable: FxHashMap<(usize, BTreeSet<ExtraName>), Vec<Vec<MarkerTree>>> =
+            FxHashMap::default();
+        let mut queue = VecDeque::new();
+
+        for root in roots {
+            let path = root.marker.iter().flat_map(conjuncts).collect::<Vec<_>>();
+            for index in self.candidates(&by_name, &root.name, root.version.as_deref())? {
+                visit(
+                    &mut reachable,
+                    &mut queue,
+                    (index, root.extras.clone()),
+                    vec![path.clone()],
+                );
+            }
+        }
+
+        // Propagate the paths to each package's dependencies until they no longer change. Each
+        // package is revisited only when a new (non-redundant) path to it is found.
+        while let Some((index, extras)) = queue.pop_front() {
+            let package = &self.package[index];
+            let paths = reachable[&(index, extras.clone())].clone();
+
+            // Determine the optional dependencies enabled by the requested extras.
+            let active = extras.iter().cloned().collect::<Vec<_>>();
+            let enabled = extras
+                .iter()
+                .filter_map(|extra| package.extras.get(extra))
+                .flatten()
+                .map(String::as_str)
+                .filter_map(extra_requirement_name)
+                .collect::<FxHashSet<_>>();
+
+            for (name, dependency) in &package.dependencies {
+                for table in dependency.tables() {
+                    if table.optional && !enabled.contains(name) {
+                        continue;
+                    }
+                    // Optional dependencies are gated on `extra == "..."` markers, which are
+                    // satisfied once the extra is enabled.
+                    let marker = table
+                        .marker(name)?
+                        .and_then(|marker| marker.simplify_extras(&active));
+                    let paths = paths
+                        .iter()
+                        .map(|path| {
+                            let mut path = path.clone();
+                            for conjunct in marker.iter().flat_map(conjuncts) {
+                                if !path.contains(&conjunct) {
+                                    path.push(conjunct);
+                                }
+                            }
+                            path
+                        })
+                        .collect::<Vec<_>>();
+                    for index in self.candidates(&by_name, name, table.version.as_deref())? {
+                        visit(
+                            &mut reachable,
+                            &mut queue,
+                            (index, table.extras.clone()),
+                            paths.clone(),
+                        );
+                    }
+                }
+            }
+        }
+
+        // A package is required if it's reachable with any set of extras.
+        let mut markers: BTreeMap<usize, Vec<Vec<MarkerTree>>> = BTreeMap::new();
+        for ((index, _), paths) in reachable {
+            let existing = markers.entry(index).or_default();
+            for path in paths {
+                insert_path(existing, path);
+            }
+        }
+
+        let mut requirements = Vec::with_capacity(markers.len());
+        let mut editables = Vec::new();
+        let mut extra_index_urls: Vec<IndexUrl> = Vec::new();
+        for (index, paths) in markers {
+            let package = &self.package[index];
+            let marker = if paths.iter().any(Vec::is_empty) {
+                String::new()
+            } else {
+                let marker = or_markers(paths.into_iter().map(and_markers).collect());
+                format!(" ; {marker}")
+            };
+
+            if let Some(source) = &package.source {
+                // Editable source trees are installed as editables.
+                if source.r#type == "directory" && package.develop {
+                    let path = subdirectory(install_path.join(&source.url), source);
+                    let editable = EditableRequirement::parse(
+                        &format!("{}{marker}", path.display()),
+                        None,
+                        install_path,
+                    )?;
+                    editables.push(editable);
+                    continue;
+                }
+
+                // Packages from alternate indexes are fetched from those indexes.
+                if source.r#type == "legacy" {
+                    let index_url = IndexUrl::from_str(&source.url).map_err(|err| {
+                        PoetryLockError::InvalidSourceUrl(
+                            package.name.clone(),
+                            source.url.clone(),
+                            err,
+                        )
+                    })?;
+                    if !extra_index_urls.contains(&index_url) {
+                        extra_index_urls.push(index_url);
+                    }
+                }
+            }
+
+            let files = if package.files.is_empty() {
+                self.metadata
+                    .files
+                    .get(&package.name)
+                    .map(Vec::as_slice)
+                    .unwrap_or_default()
+            } else {
+                package.files.as_slice()
+            };
+
+            let (requirement, hashes) = package.to_requirement(files, install_path)?;
+            let requirement =
+                RequirementsTxtRequirement::parse(&format!("{requirement}{marker}"), install_path)?;
+            requirements.push(UnresolvedRequirementSpecification::from(RequirementEntry {
+                requirement,
+                hashes,
+            }));
+        }
+
+        Ok(RequirementsSpecification {
+            requirements,
+            editables,
+            extras: used_extras,
+            extra_index_urls,
+            ..RequirementsSpecification::default()
+        })
+    }
+
+    /// Return the locked packages that satisfy a dependency on `name`.
+    ///
+    /// Poetry may lock multiple versions of the same package (e.g., for different Python versions),
+    /// in which case only those that satisfy the dependency's version constraint are selected, and
+    /// it's an error if none do.
+    fn candidates(
+        &self,
+        by_name: &FxHashMap<&PackageName, Vec<usize>>,
+        name: &PackageName,
+        version: Option<&str>,
+    ) -> Result<Vec<usize>, PoetryLockError> {
+        let Some(indices) = by_name.get(name) else {
+            return Err(PoetryLockError::MissingPackage(name.clone()));
+        };
+        if indices.len() == 1 {
+            return Ok(indices.clone());
+        }
+        let Some(alternatives) = version.and_then(specifiers) else {
+            return Ok(indices.clone());
+        };
+        let matching = indices
+            .iter()
+            .copied()
+            .filter(|index| {
+                Version::from_str(&self.package[*index].version).is_ok_and(|version| {
+                    alternatives
+                        .iter()
+                        .any(|specifiers| specifiers.contains(&version))
+                })
+            })
+            .collect::<Vec<_>>();
+        if matching.is_empty() {
+            return Err(PoetryLockError::MissingVersion(
+                name.clone(),
+                version.unwrap_or_default().to_string(),
+            ));
+        }
+        Ok(matching)
+    }
+}
+
+impl PoetryLockPackage {
+    /// Return the PEP 508 requirement (without markers) that pins this package to its locked
+    /// source, along with the hashes of its artifacts.
+    fn to_requirement(
+        &self,
+        files: &[PoetryLockFile],
+        install_path: &Path,
+    ) -> Result<(String, Vec<String>), PoetryLockError> {
+        let name = &self.name;
+        let hashes = files
+            .iter()
+            .filter(|file| {
+                file.hash
+                    .split_once(':')
+                    .is_some_and(|(algorithm, _)| HashAlgorithm::from_str(algorithm).is_ok())
+            })
+            .map(|file| file.hash.clone())
+            .collect();
+
+        let Some(source) = &self.source else {
+            return Ok((format!("{name}=={}", self.version), hashes));
+        };
+
+        match source.r#type.as_str() {
+            "legacy" => Ok((format!("{name}=={}", self.version), hashes)),
+            "git" => {
+                let reference = source
+                    .resolved_reference
+                    .as_deref()
+                    .or(source.reference.as_deref());
+                let mut requirement = format!("{name} @ git+{}", source.url);
+                if let Some(reference) = reference {
+                    requirement.push('@');
+                    requirement.push_str(reference);
+                }
+                if let Some(subdirectory) = &source.subdirectory {
+                    requirement.push_str("#subdirectory=");
+                    requirement.push_str(subdirectory);
+                }
+                Ok((requirement, vec![]))
+            }
+            "directory" => {
+                let path = subdirectory(install_path.join(&source.url), source);
+                let path = uv_fs::normalize_path(&path)
+                    .map_err(|_| PoetryLockError::InvalidPath(path.clone()))?;
+                let url = Url::from_directory_path(&path)
+                    .map_err(|()| PoetryLockError::InvalidPath(path.clone()))?;
+                Ok((format!("{name} @ {url}"), vec![]))
+            }
+            "file" => {
+                let path = install_path.join(&source.url);
+                let path = uv_fs::normalize_path(&path)
+                    .map_err(|_| PoetryLockError::InvalidPath(path.clone()))?;
+                let url = Url::from_file_path(&path)
+                    .map_err(|()| PoetryLockError::InvalidPath(path.clone()))?;
+                Ok((format!("{name} @ {url}"), hashes))
+            }
+            "url" => {
+                let mut url = Url::parse(&source.url).map_err(|err| {
+                    PoetryLockError::InvalidSourceUrl(name.clone(), source.url.clone(), err)
+                })?;
+                if let Some(subdirectory) = &source.subdirectory {
+                    url.set_fragment(Some(&format!("subdirectory={subdirectory}")));
+                }
+                Ok((format!("{name} @ {url}"), hashes))
+            }
+            r#type => Err(PoetryLockError::UnsupportedSource(
+                name.clone(),
+                r#type.to_string(),
+            )),
+        }
+    }
+}
+
+impl PoetryDependency {
+    /// Return the constraints that make up the dependency.
+    fn tables(&self) -> Vec<PoetryDependencyTable> {
+        match self {
+            Self::Constraint(version) => vec![PoetryDependencyTable {
+                version: Some(version.clone()),
+                ..PoetryDependencyTable::default()
+            }],
+            Self::Table(table) => vec![table.clone()],
+            Self::Multiple(tables) => tables.clone(),
+        }
+    }
+}
+
+impl PoetryDependencyTable {
+    /// Return the marker under which the dependency applies, combining its `markers` and `python`
+    /// constraints.
+    fn marker(&self, name: &PackageName) -> Result<Option<MarkerTree>, PoetryLockError> {
+        let markers = self
+            .markers
+            .as_deref()
+            .map(|markers| {
+                MarkerTree::from_str(markers)
+                    .map_err(|_| PoetryLockError::InvalidMarker(name.clone(), markers.to_string()))
+            })
+            .transpose()?;
+        let python = self
+            .python
+            .as_deref()
+            .map(|python| {
+                python_marker(python)
+                    .ok_or_else(|| PoetryLockError::InvalidPython(name.clone(), python.to_string()))
+            })
+            .transpose()?
+            .flatten();
+        Ok(and(markers.as_ref(), python))
+    }
+}
+
+impl PoetryPyProjectToml {
+    /// Return the dependencies of the project, along with the extras that were used to collect
+    /// them.
+    ///
+    /// Optional dependencies are only included if they're enabled by one of the requested
+    /// `extras`. All non-optional dependency groups are included, as with `poetry install`.
+    fn roots(
+        &self,
+        extras: &ExtrasSpecification,
+    ) -> Result<(Vec<Edge>, FxHashSet<ExtraName>), PoetryLockError> {
+        let mut roots = Vec::new();
+        let mut used_extras = FxHashSet::default();
+
+        if let Some(project) = &self.project {
+            let optional = project
+                .optional_dependencies
+                .iter()
+                .filter(|(extra, _)| extras.contains(extra))
+                .inspect(|(extra, _)| {
+                    used_extras.insert((*extra).clone());
+                })
+                .flat_map(|(_, requirements)| requirements);
+            for requirement in project.dependencies.iter().chain(optional) {
+                let requirement =
+                    pep508_rs::Requirement::<VerbatimParsedUrl>::from_str(requirement)
+                        .map_err(Box::new)?;
+                let version = match requirement.version_or_url {
+                    Some(pep508_rs::VersionOrUrl::VersionSpecifier(specifiers)) => {
+                        Some(specifiers.to_string())
+                    }
+                    _ => None,
+                };
+                roots.push(Edge {
+                    name: requirement.name,
+                    version,
+                    marker: requirement.marker,
+                    extras: requirement.extras.into_iter().collect(),
+                });
+            }
+        }
+
+        if let Some(poetry) = self.tool.as_ref().and_then(|tool| tool.poetry.as_ref()) {
+            let enabled = poetry
+                .extras
+                .iter()
+                .filter(|(extra, _)| extras.contains(extra))
+                .inspect(|(extra, _)| {
+                    used_extras.insert((*extra).clone());
+                })
+                .flat_map(|(_, names)| names)
+                .collect::<FxHashSet<_>>();
+
+            let groups = poetry
+                .group
+                .values()
+                .filter(|group| !group.optional)
+                .map(|group| &group.dependencies);
+            for dependencies in std::iter::once(&poetry.dependencies)
+                .chain(std::iter::once(&poetry.dev_dependencies))
+                .chain(groups)
+            {
+                for (name, dependency) in dependencies {
+                    // The `python` key declares the project's supported Python versions.
+                    if name.as_ref() == "python" {
+                        continue;
+                    }
+                    for table in dependency.tables() {
+                        if table.optional && !enabled.contains(name) {
+                            continue;
+                        }
+                        roots.push(Edge {
+                            name: name.clone(),
+                            marker: table.marker(name)?,
+                            version: table.version,
+                            extras: table.extras,
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok((roots, used_extras))
+    }
+}
+
+/// Combine two markers, such that the result applies only when both apply.
+fn and(left: Option<&MarkerTree>, right: Option<MarkerTree>) -> Option<MarkerTree> {
+    match (left, right) {
+        (None, right) => right,
+        (Some(left), None) => Some(left.clone()),
+        (Some(left), Some(right)) => {
+            let mut conjuncts = match left {
+                MarkerTree::And(conjuncts) => conjuncts.clone(),
+                left => vec![left.clone()],
+            };
+            if !conjuncts.contains(&right) {
+                conjuncts.push(right);
+            }
+            if conjuncts.len() == 1 {
+                conjuncts.pop()
+            } else {
+                Some(MarkerTree::And(conjuncts))
+            }
+        }
+    }
+}
+
+/// Add the given paths to the paths under which a package is reachable with a given set of
+/// extras, queueing the package to be revisited if any of them are new.
+fn visit(
+    reachable: &mut FxHashMap<(usize, BTreeSet<ExtraName>), Vec<Vec<MarkerTree>>>,
+    queue: &mut VecDeque<(usize, BTreeSet<ExtraName>)>,
+    key: (usize, BTreeSet<ExtraName>),
+    paths: Vec<Vec<MarkerTree>>,
+) {
+    let existing = reachable.entry(key.clone()).or_default();
+    let mut changed = false;
+    for path in paths {
+        changed |= insert_path(existing, path);
+    }
+    if changed && !queue.contains(&key) {
+        queue.push_back(key);
+    }
+}
+
+/// Split a marker into the markers that must all apply for it to apply.
+fn conjuncts(marker: &MarkerTree) -> Vec<MarkerTree> {
+    match marker {
+        MarkerTree::And(markers) => markers.clone(),
+        marker => vec![marker.clone()],
+    }
+}
+
+/// Add a path, given as the (conjoined) markers along it, to a set of paths, returning `true` if
+/// the path is new.
+///
+/// A path is redundant if some existing path requires a subset of its markers; likewise, any
+/// existing paths made redundant by the new path are removed.
+fn insert_path(paths: &mut Vec<Vec<MarkerTree>>, path: Vec<MarkerTree>) -> bool {
+    let is_subset = |subset: &[MarkerTree], superset: &[MarkerTree]| {
+        subset.iter().all(|marker| superset.contains(marker))
+    };
+    if paths.iter().any(|existing| is_subset(existing, &path)) {
+        return false;
+    }
+    paths.retain(|existing| !is_subset(&path, existing));
+    paths.push(path);
+    true
+}
+
+/// Combine the given (non-empty) markers into a single marker that applies if all of them apply.
+fn and_markers(mut markers: Vec<MarkerTree>) -> MarkerTree {
+    if markers.len() == 1 {
+        markers.remove(0)
+    } else {
+        MarkerTree::And(markers)
+    }
+}
+
+/// Combine the given (non-empty) markers into a single marker that applies if any of them apply.
+fn or_markers(mut markers: Vec<MarkerTree>) -> MarkerTree {
+    if markers.len() == 1 {
+        markers.remove(0)
+    } else {
+        MarkerTree::Or(markers)
+    }
+}
+
+/// Apply the `subdirectory`, if any, to the path of a source tree.
+fn subdirectory(path: PathBuf, source: &PoetryLockSource) -> PathBuf {
+    match &source.subdirectory {
+        Some(subdirectory) => path.join(subdirectory),
+        None => path,
+    }
+}
+
+/// Extract the package name from a requirement in a locked package's `extras` table (e.g.,
+/// `PySocks (>=1.5.6,!=1.5.7)`).
+fn extra_requirement_name(requirement: &str) -> Option<PackageName> {
+    let end = requirement
+        .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+        .unwrap_or(requirement.len());
+    PackageName::from_str(&requirement[..end]).ok()
+}
+
+/// Convert a Poetry version constraint (e.g., `^1.2`, `~1.2.3`, or `>=1.0,<2.0 || >=3.0`) into
+/// the equivalent PEP 440 clauses (e.g., `(">=", "1.2")`), one set per alternative.
+///
+/// Returns `None` if the constraint can't be represented.
+fn clauses(constraint: &str) -> Option<Vec<Vec<(&'static str, String)>>> {
+    constraint
+        .split('|')
+        .filter(|alternative| !alternative.trim().is_empty())
+        .map(|alternative| {
+            // Constraints are separated by commas or whitespace, and operators may be separated
+            // from their versions by whitespace (e.g., `>= 1.0`).
+            let mut clauses = Vec::new();
+            let mut pending = String::new();
+            for token in alternative
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|token| !token.is_empty())
+            {
+                pending.push_str(token);
+                if pending.chars().all(|c| "<>=!~^".contains(c)) {
+                    continue;
+                }
+                clauses.extend(clause(&std::mem::take(&mut pending))?);
+            }
+            if !pending.is_empty() {
+                return None;
+            }
+            Some(clauses)
+        })
+        .collect()
+}
+
+/// Convert a single Poetry constraint (e.g., `^1.2`) into PEP 440 clauses.
+fn clause(constraint: &str) -> Option<Vec<(&'static str, String)>> {
+    if constraint == "*" {
+        return Some(vec![]);
+    }
+
+    // Caret constraints allow any change that doesn't modify the left-most non-zero component
+    // (e.g., `^1.2.3` is `>=1.2.3,<2.0.0`, and `^0.2.3` is `>=0.2.3,<0.3.0`).
+    if let Some(version) = constraint.strip_prefix('^') {
+        let release = Version::from_str(version).ok()?.release().to_vec();
+        let position = release
+            .iter()
+            .position(|part| *part != 0)
+            .unwrap_or(release.len() - 1);
+        return Some(vec![
+            (">=", version.to_string()),
+            ("<", upper_bound(&release, position)),
+        ]);
+    }
+
+    if let Some(version) = constraint.strip_prefix("~=") {
+        return Some(vec![("~=", version.to_string())]);
+    }
+
+    // Tilde constraints allow patch-level changes (e.g., `~1.2.3` is `>=1.2.3,<1.3.0`), or
+    // minor-level changes if only a major version is given (e.g., `~1` is `>=1,<2`).
+    if let Some(version) = constraint.strip_prefix('~') {
+        let release = Version::from_str(version).ok()?.release().to_vec();
+        let position = usize::from(release.len() > 1);
+        return Some(vec![
+            (">=", version.to_string()),
+            ("<", upper_bound(&release, position)),
+        ]);
+    }
+
+    // A bare version (e.g., `1.2.3` or `1.2.*`) is an exact match.
+    let (operator, version) = [
+        (">=", ">="),
+        ("<=", "<="),
+        ("==", "=="),
+        ("!=", "!="),
+        (">", ">"),
+        ("<", "<"),
+        ("=", "=="),
+    ]
+    .into_iter()
+    .find_map(|(prefix, operator)| {
+        constraint
+            .strip_prefix(prefix)
+            .map(|version| (operator, version))
+    })
+    .unwrap_or(("==", constraint));
+
+    // Expand wildcards into ranges (e.g., `3.12.*` is `>=3.12,<3.13`), since wildcards aren't
+    // preserved when markers are displayed.
+    if operator == "==" {
+        if let Some(prefix) = version.strip_suffix(".*") {
+            let release = Version::from_str(prefix).ok()?.release().to_vec();
+            return Some(vec![
+                (">=", prefix.to_string()),
+                ("<", upper_bound(&release, release.len() - 1)),
+            ]);
+        }
+    }
+
+    Some(vec![(operator, version.to_string())])
+}
+
+/// Increment the release segment at `position`, discarding any later segments.
+fn upper_bound(release: &[u64], position: usize) -> String {
+    release[..=position]
+        .iter()
+        .enumerate()
+        .map(
+            |(index, part)| {
+                if index == position {
+                    part + 1
+                } else {
+                    *part
+                }
+            },
+        )
+        .join(".")
+}
+
+/// Convert a Poetry version constraint into a set of PEP 440 specifiers, one per alternative.
+fn specifiers(constraint: &str) -> Option<Vec<VersionSpecifiers>> {
+    clauses(constraint)?
+        .into_iter()
+        .map(|clauses| {
+            clauses
+                .into_iter()
+                .map(|(operator, version)| {
+                    VersionSpecifier::from_str(&format!("{operator}{version}"))
+                })
+                .collect::<Result<VersionSpecifiers, _>>()
+                .ok()
+        })
+        .collect()
+}
+
+/// Convert a Poetry Python constraint (e.g., `^3.8`) into the equivalent marker.
+///
+/// Returns `Some(None)` if the constraint applies to all Python versions, and `None` if the
+/// constraint can't be represented.
+fn python_marker(constraint: &str) -> Option<Option<MarkerTree>> {
+    let alternatives = clauses(constraint)?;
+    if alternatives.iter().any(Vec::is_empty) {
+        return Some(None);
+    }
+    let marker = alternatives
+        .iter()
+        .map(|clauses| {
+            let expression = clauses
+                .iter()
+                .map(|(operator, version)| format!("python_full_version {operator} '{version}'"))
+                .join(" and ");
+            format!("({expression})")
+        })
+        .join(" or ");
+    MarkerTree::from_str(&marker).ok().map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_specification() {
+        let pyproject = r#"
+[tool.poetry]
+name = "project"
+version = "0.1.0"
+
+[tool.poetry.dependencies]
+python = "^3.8"
+anyio = "^3"
+iniconfig = { git = "https://github.com/pytest-dev/iniconfig", optional = true }
+colorama = { version = "*", markers = "sys_platform == 'win32'" }
+
+[tool.poetry.group.dev.dependencies]
+private = { version = "^1.0", source = "internal", python = "<3.10" }
+
+[tool.poetry.extras]
+test = ["iniconfig"]
+"#;
+        let lock = r#"
+[[package]]
+name = "anyio"
+version = "3.7.1"
+optional = false
+python-versions = ">=3.7"
+files = [
+    {file = "anyio-3.7.1-py3-none-any.whl", hash = "sha256:91dee416e570e92c64041bd18b900d1d6fa78dff7048769ce5ac5ddad004fbb5"},
+    {file = "anyio-3.7.1.tar.gz", hash = "sha256:44a3c9aba0f5defa43261a8b3efb97891f2bd7d804e0e1f56419befa1adfc780"},
+]
+
+[package.dependencies]
+exceptiongroup = {version = "*", markers = "python_version < \"3.11\""}
+idna = ">=2.8"
+sniffio = ">=1.1"
+
+[[package]]
+name = "colorama"
+version = "0.4.6"
+optional = false
+python-versions = "*"
+files = []
+
+[[package]]
+name = "exceptiongroup"
+version = "1.2.0"
+optional = false
+python-versions = ">=3.7"
+files = []
+
+[[package]]
+name = "idna"
+version = "3.6"
+optional = false
+python-versions = ">=3.5"
+files = []
+
+[[package]]
+name = "iniconfig"
+version = "2.0.0"
+optional = true
+python-versions = ">=3.7"
+files = []
+develop = false
+
+[package.source]
+type = "git"
+url = "https://github.com/pytest-dev/iniconfig"
+reference = "HEAD"
+resolved_reference = "93f5930e668c0d1ddf4597e38dd0dea4e2665e7a"
+
+[[package]]
+name = "private"
+version = "1.0.0"
+optional = false
+python-versions = "*"
+files = []
+
+[package.source]
+type = "legacy"
+url = "https://example.com/simple"
+reference = "internal"
+
+[[package]]
+name = "sniffio"
+version = "1.3.1"
+optional = false
+python-versions = ">=3.7"
+files = []
+
+[metadata]
+lock-version = "2.0"
+python-versions = "^3.8"
+content-hash = "0000000000000000000000000000000000000000000000000000000000000000"
+"#;
+        let pyproject: PoetryPyProjectToml = toml::from_str(pyproject).unwrap();
+        let lock: PoetryLock = toml::from_str(lock).unwrap();
+        let extras = ExtrasSpecification::Some(vec![ExtraName::from_str("test").unwrap()]);
+        let spec = lock
+            .to_specification(&pyproject, &extras, Path::new("/"))
+            .unwrap();
+        assert!(spec.editables.is_empty());
+        assert_eq!(
+            spec.extra_index_urls
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec!["https://example.com/simple"]
+        );
+
+        let requirements = spec
+            .requirements
+            .iter()
+            .map(|entry| (entry.requirement.to_string(), entry.hashes.clone()))
+            .collect::<Vec<_>>();
+        insta::assert_debug_snapshot!(requirements, @r###"
+        [
+            (
+                "anyio==3.7.1",
+                [
+                    "sha256:91dee416e570e92c64041bd18b900d1d6fa78dff7048769ce5ac5ddad004fbb5",
+                    "sha256:44a3c9aba0f5defa43261a8b3efb97891f2bd7d804e0e1f56419befa1adfc780",
+                ],
+            ),
+            (
+                "colorama==0.4.6 ; sys_platform == 'win32'",
+                [],
+            ),
+            (
+                "exceptiongroup==1.2.0 ; python_version < '3.11'",
+                [],
+            ),
+            (
+                "idna==3.6",
+                [],
+            ),
+            (
+                "iniconfig @ git+https://github.com/pytest-dev/iniconfig@93f5930e668c0d1ddf4597e38dd0dea4e2665e7a",
+                [],
+            ),
+            (
+                "private==1.0.0 ; python_full_version < '3.10'",
+                [],
+            ),
+            (
+                "sniffio==1.3.1",
+                [],
+            ),
+        ]
+        "###);
+    }
+
+    #[test]
+    fn package_extras() {
+        let pyproject = r#"
+[tool.poetry]
+name = "project"
+version = "0.1.0"
+
+[tool.poetry.dependencies]
+python = "^3.8"
+requests = { version = "^2.31", extras = ["socks"] }
+"#;
+        let lock = r#"
+[[package]]
+name = "pysocks"
+version = "1.7.1"
+optional = false
+python-versions = ">=2.7, !=3.0.*, !=3.1.*, !=3.2.*, !=3.3.*"
+files = []
+
+[[package]]
+name = "requests"
+version = "2.31.0"
+optional = false
+python-versions = ">=3.7"
+files = []
+
+[package.dependencies]
+idna = ">=2.5,<4"
+PySocks = {version = ">=1.5.6,<1.5.7 || >1.5.7", optional = true, markers = "extra == \"socks\""}
+
+[package.extras]
+socks = ["PySocks (>=1.5.6,!=1.5.7)"]
+use-chardet-on-py3 = ["chardet (>=3.0.2,<6)"]
+
+[[package]]
+name = "idna"
+version = "3.6"
+optional = false
+python-versions = ">=3.5"
+files = []
+
+[metadata]
+lock-version = "2.0"
+python-versions = "^3.8"
+content-hash = "0000000000000000000000000000000000000000000000000000000000000000"
+"#;
+        let pyproject: PoetryPyProjectToml = toml::from_str(pyproject).unwrap();
+        let lock: PoetryLock = toml::from_str(lock).unwrap();
+        let spec = lock
+            .to_specification(&pyproject, &ExtrasSpecification::None, Path::new("/"))
+            .unwrap();
+
+        let requirements = spec
+            .requirements
+            .iter()
+            .map(|entry| entry.requirement.to_string())
+            .collect::<Vec<_>>();
+        insta::assert_debug_snapshot!(requirements, @r###"
+        [
+            "pysocks==1.7.1",
+            "requests==2.31.0",
+            "idna==3.6",
+        ]
+        "###);
+    }
+
+    #[test]
+    fn markers_diamond() {
+        let pyproject = r#"
+[tool.poetry]
+name = "project"
+version = "0.1.0"
+
+[tool.poetry.dependencies]
+python = "^3.8"
+a = { version = "*", markers = "sys_platform == 'win32'" }
+b = { version = "*", markers = "sys_platform == 'linux'" }
+"#;
+        let lock = r#"
+[[package]]
+name = "a"
+version = "1.0.0"
+optional = false
+python-versions = "*"
+files = []
+
+[package.dependencies]
+c = "*"
+
+[[package]]
+name = "b"
+version = "1.0.0"
+optional = false
+python-versions = "*"
+files = []
+
+[package.dependencies]
+d = "*"
+
+[[package]]
+name = "c"
+version = "1.0.0"
+optional = false
+python-versions = "*"
+files = []
+
+[package.dependencies]
+d = "*"
+
+[[package]]
+name = "d"
+version = "1.0.0"
+optional = false
+python-versions = "*"
+files = []
+
+[package.dependencies]
+c = "*"
+
+[metadata]
+lock-version = "2.0"
+python-versions = "^3.8"
+content-hash = "0000000000000000000000000000000000000000000000000000000000000000"
+"#;
+        let pyproject: PoetryPyProjectToml = toml::from_str(pyproject).unwrap();
+        let lock: PoetryLock = toml::from_str(lock).unwrap();
+        let spec = lock
+            .to_specification(&pyproject, &ExtrasSpecification::None, Path::new("/"))
+            .unwrap();
+
+        // `c` and `d` depend on each other, and are reachable from both `a` and `b`, so they're
+        // required under either marker.
+        let requirements = spec
+            .requirements
+            .iter()
+            .map(|entry| entry.requirement.to_string())
+            .collect::<Vec<_>>();
+        insta::assert_debug_snapshot!(requirements, @r###"
+        [
+            "a==1.0.0 ; sys_platform == 'win32'",
+            "b==1.0.0 ; sys_platform == 'linux'",
+            "c==1.0.0 ; sys_platform == 'win32' or sys_platform == 'linux'",
+            "d==1.0.0 ; sys_platform == 'linux' or sys_platform == 'win32'",
+        ]
+        "###);
+    }
+
+    #[test]
+    fn python_constraints() {
+        let markers = [
+            "^3.8",
+            "~3.9",
+            ">=3.7, <3.11",
+            "3.12.*",
+            ">=3.13 || <3.8",
+            "*",
+        ]
+        .into_iter()
+        .map(|constraint| {
+            python_marker(constraint)
+                .unwrap()
+                .map(|marker| marker.to_string())
+        })
+        .collect::<Vec<_>>();
+        insta::assert_debug_snapshot!(markers, @r###"
+        [
+            Some(
+                "python_full_version >= '3.8' and python_full_version < '4'",
+            ),
+            Some(
+                "python_full_version >= '3.9' and python_full_version < '3.10'",
+            ),
+            Some(
+                "python_full_version >= '3.7' and python_full_version < '3.11'",
+            ),
+            Some(
+                "python_full_version >= '3.12' and python_full_version < '3.13'",
+            ),
+            Some(
+                "python_full_version >= '3.13' or python_full_version < '3.8'",
+            ),
+            None,
+        ]
+        "###);
+    }
+}
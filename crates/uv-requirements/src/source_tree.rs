@@ -109,8 +109,8 @@ impl<'a, Context: BuildContext> SourceTreeResolver<'a, Context> {
         // Determine the hash policy. Since we don't have a package name, we perform a
         // manual match.
         let hashes = match self.hasher {
-            HashStrategy::None => HashPolicy::None,
-            HashStrategy::Generate => HashPolicy::Generate,
+            HashStrategy::None | HashStrategy::Verify(_) => HashPolicy::None,
+            HashStrategy::Generate(algorithms) => HashPolicy::Generate(algorithms),
             HashStrategy::Validate { .. } => {
                 return Err(anyhow::anyhow!(
                     "Hash-checking is not supported for local directories: {}",
@@ -22,6 +22,15 @@ pub enum RequirementsSource {
     SetupPy(PathBuf),
     /// Dependencies were provided via a `setup.cfg` file (e.g., `pip-compile setup.cfg`).
     SetupCfg(PathBuf),
+    /// Dependencies were provided via a PEP 751 `pylock.toml` file (e.g., `pip install -r pylock.toml`).
+    PylockToml(PathBuf),
+    /// Dependencies were provided via a Poetry `poetry.lock` file, alongside the `pyproject.toml`
+    /// that declares the project's dependencies (e.g., `pip sync poetry.lock`).
+    PoetryLock(PathBuf),
+    /// Dependencies were provided via a Pipenv `Pipfile` (e.g., `pip install -r Pipfile`).
+    Pipfile(PathBuf),
+    /// Dependencies were provided via a Pipenv `Pipfile.lock` (e.g., `pip sync Pipfile.lock`).
+    PipfileLock(PathBuf),
     /// Dependencies were provided via a path to a source tree (e.g., `pip install .`).
     SourceTree(PathBuf),
 }
@@ -36,6 +45,14 @@ impl RequirementsSource {
             Self::SetupPy(path)
         } else if path.ends_with("setup.cfg") {
             Self::SetupCfg(path)
+        } else if is_pylock_toml(&path) {
+            Self::PylockToml(path)
+        } else if path.ends_with("poetry.lock") {
+            Self::PoetryLock(path)
+        } else if path.ends_with("Pipfile") {
+            Self::Pipfile(path)
+        } else if path.ends_with("Pipfile.lock") {
+            Self::PipfileLock(path)
         } else {
             Self::RequirementsTxt(path)
         }
@@ -134,7 +151,21 @@ impl RequirementsSource {
     pub fn allows_extras(&self) -> bool {
         matches!(
             self,
-            Self::PyprojectToml(_) | Self::SetupPy(_) | Self::SetupCfg(_)
+            Self::PyprojectToml(_)
+                | Self::SetupPy(_)
+                | Self::SetupCfg(_)
+                | Self::PoetryLock(_)
+                | Self::Pipfile(_)
+                | Self::PipfileLock(_)
+        )
+    }
+
+    /// Returns `true` if the source is a lockfile, such that any hashes it provides should be
+    /// verified on install.
+    pub fn is_lockfile(&self) -> bool {
+        matches!(
+            self,
+            Self::PylockToml(_) | Self::PoetryLock(_) | Self::PipfileLock(_)
         )
     }
 }
@@ -148,6 +179,10 @@ impl std::fmt::Display for RequirementsSource {
             | Self::PyprojectToml(path)
             | Self::SetupPy(path)
             | Self::SetupCfg(path)
+            | Self::PylockToml(path)
+            | Self::PoetryLock(path)
+            | Self::Pipfile(path)
+            | Self::PipfileLock(path)
             | Self::SourceTree(path) => {
                 write!(f, "{}", path.simplified_display())
             }
@@ -155,6 +190,20 @@ impl std::fmt::Display for RequirementsSource {
     }
 }
 
+/// Returns `true` if the path refers to a PEP 751 lockfile, i.e., `pylock.toml` or
+/// `pylock.<name>.toml`.
+fn is_pylock_toml(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| {
+            name == "pylock.toml"
+                || name
+                    .strip_prefix("pylock.")
+                    .and_then(|name| name.strip_suffix(".toml"))
+                    .is_some_and(|name| !name.is_empty() && !name.contains('.'))
+        })
+}
+
 #[derive(Debug, Default, Clone)]
 pub enum ExtrasSpecification {
     #[default]
@@ -20,7 +20,10 @@ use uv_client::BaseClientBuilder;
 use uv_configuration::{NoBinary, NoBuild, PreviewMode};
 use uv_fs::Simplified;
 use uv_normalize::{ExtraName, PackageName};
+use uv_resolver::PylockToml;
 
+use crate::pipenv::{Pipfile, PipfileLock};
+use crate::poetry::{PoetryLock, PoetryPyProjectToml};
 use crate::pyproject::{Pep621Metadata, PyProjectToml};
 use crate::{ExtrasSpecification, RequirementsSource};
 
@@ -125,6 +128,70 @@ impl RequirementsSpecification {
                 Self::parse_direct_pyproject_toml(&contents, extras, path.as_ref(), preview)
                     .with_context(|| format!("Failed to parse: `{}`", path.user_display()))?
             }
+            RequirementsSource::PylockToml(path) => {
+                let contents = uv_fs::read_to_string(&path).await?;
+                let pylock: PylockToml = toml::from_str(&contents)
+                    .with_context(|| format!("Failed to parse: `{}`", path.user_display()))?;
+                let install_path = uv_fs::absolutize_path(path)?;
+                let install_path = install_path.parent().unwrap_or(&install_path);
+                let (requirements, editables, extra_index_urls) = pylock
+                    .to_requirements(install_path)
+                    .with_context(|| format!("Failed to parse: `{}`", path.user_display()))?;
+                Self {
+                    requirements: requirements
+                        .into_iter()
+                        .map(UnresolvedRequirementSpecification::from)
+                        .collect(),
+                    editables,
+                    extra_index_urls,
+                    ..Self::default()
+                }
+            }
+            RequirementsSource::PoetryLock(path) => {
+                let contents = uv_fs::read_to_string(&path).await?;
+                let lock: PoetryLock = toml::from_str(&contents)
+                    .with_context(|| format!("Failed to parse: `{}`", path.user_display()))?;
+                let install_path = uv_fs::absolutize_path(path)?;
+                let install_path = install_path.parent().unwrap_or(&install_path);
+
+                // The project's dependencies are read from the adjacent `pyproject.toml`.
+                let pyproject_path = install_path.join("pyproject.toml");
+                let contents = uv_fs::read_to_string(&pyproject_path)
+                    .await
+                    .with_context(|| {
+                        format!(
+                            "Failed to read `{}`, which is required to install from `{}`",
+                            pyproject_path.user_display(),
+                            path.user_display()
+                        )
+                    })?;
+                let pyproject: PoetryPyProjectToml =
+                    toml::from_str(&contents).with_context(|| {
+                        format!("Failed to parse: `{}`", pyproject_path.user_display())
+                    })?;
+
+                lock.to_specification(&pyproject, extras, install_path)
+                    .with_context(|| format!("Failed to parse: `{}`", path.user_display()))?
+            }
+            RequirementsSource::Pipfile(path) => {
+                let contents = uv_fs::read_to_string(&path).await?;
+                let pipfile: Pipfile = toml::from_str(&contents)
+                    .with_context(|| format!("Failed to parse: `{}`", path.user_display()))?;
+                let install_path = uv_fs::absolutize_path(path)?;
+                let install_path = install_path.parent().unwrap_or(&install_path);
+                pipfile
+                    .to_specification(extras, install_path)
+                    .with_context(|| format!("Failed to parse: `{}`", path.user_display()))?
+            }
+            RequirementsSource::PipfileLock(path) => {
+                let contents = uv_fs::read_to_string(&path).await?;
+                let lock: PipfileLock = serde_json::from_str(&contents)
+                    .with_context(|| format!("Failed to parse: `{}`", path.user_display()))?;
+                let install_path = uv_fs::absolutize_path(path)?;
+                let install_path = install_path.parent().unwrap_or(&install_path);
+                lock.to_specification(extras, install_path)
+                    .with_context(|| format!("Failed to parse: `{}`", path.user_display()))?
+            }
             RequirementsSource::SetupPy(path) | RequirementsSource::SetupCfg(path) => Self {
                 source_trees: vec![path.clone()],
                 ..Self::default()
@@ -13,10 +13,18 @@ pub async fn read_lockfile(
     upgrade: Upgrade,
 ) -> Result<Vec<Preference>> {
     // As an optimization, skip reading the lockfile is we're upgrading all packages anyway.
-    let Some(output_file) = output_file
-        .filter(|_| !upgrade.is_all())
-        .filter(|output_file| output_file.exists())
-    else {
+    if upgrade.is_all() {
+        return Ok(Vec::new());
+    }
+
+    let preferences = read_pins(output_file).await?;
+    Ok(apply_upgrade(preferences, upgrade))
+}
+
+/// Load the pinned requirements from an existing lockfile, if present, without applying any
+/// upgrade strategy.
+pub async fn read_pins(output_file: Option<&Path>) -> Result<Vec<Preference>> {
+    let Some(output_file) = output_file.filter(|output_file| output_file.exists()) else {
         return Ok(Vec::new());
     };
 
@@ -34,8 +42,12 @@ pub async fn read_lockfile(
         .filter_map(Result::transpose)
         .collect::<Result<Vec<_>, PreferenceError>>()?;
 
-    // Apply the upgrade strategy to the requirements.
-    Ok(match upgrade {
+    Ok(preferences)
+}
+
+/// Apply the upgrade strategy to the pinned requirements from an existing lockfile.
+pub fn apply_upgrade(preferences: Vec<Preference>, upgrade: Upgrade) -> Vec<Preference> {
+    match upgrade {
         // Respect all pinned versions from the existing lockfile.
         Upgrade::None => preferences,
         // Ignore all pinned versions from the existing lockfile.
@@ -43,7 +55,7 @@ pub async fn read_lockfile(
         // Ignore pinned versions for the specified packages.
         Upgrade::Packages(packages) => preferences
             .into_iter()
-            .filter(|preference| !packages.contains(preference.name()))
+            .filter(|preference| !packages.contains_key(preference.name()))
             .collect(),
-    })
+    }
 }
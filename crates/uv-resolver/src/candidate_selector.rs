@@ -27,7 +27,7 @@ pub(crate) struct CandidateSelector {
 impl CandidateSelector {
     /// Return a [`CandidateSelector`] for the given [`Manifest`].
     pub(crate) fn for_resolution(
-        options: Options,
+        options: &Options,
         manifest: &Manifest,
         markers: Option<&MarkerEnvironment>,
     ) -> Self {
@@ -40,6 +40,7 @@ impl CandidateSelector {
             ),
             prerelease_strategy: PreReleaseStrategy::from_mode(
                 options.prerelease_mode,
+                &options.prerelease_packages,
                 manifest,
                 markers,
                 options.dependency_mode,
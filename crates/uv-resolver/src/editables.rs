@@ -37,6 +37,11 @@ impl Editables {
         self.0.contains_key(name)
     }
 
+    /// Add the editables from another set, replacing any existing editables with the same name.
+    pub(crate) fn extend(&mut self, other: Editables) {
+        self.0.extend(other.0);
+    }
+
     /// Iterate over all editables.
     pub(crate) fn iter(&self) -> impl Iterator<Item = &BuiltEditableMetadata> {
         self.0.values()
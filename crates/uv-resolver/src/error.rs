@@ -9,9 +9,9 @@ use pubgrub::report::{DefaultStringReporter, DerivationTree, External, Reporter}
 use rustc_hash::{FxHashMap, FxHashSet};
 
 use dashmap::DashMap;
-use distribution_types::{BuiltDist, IndexLocations, InstalledDist, SourceDist};
-use pep440_rs::Version;
-use pep508_rs::Requirement;
+use distribution_types::{BuiltDist, IndexLocations, InstalledDist, ResolvedDist, SourceDist};
+use pep440_rs::{Version, VersionSpecifiers};
+use pep508_rs::{MarkerTree, Requirement};
 use uv_normalize::PackageName;
 
 use crate::candidate_selector::CandidateSelector;
@@ -82,6 +82,12 @@ pub enum ResolveError {
     #[error(transparent)]
     NoSolution(#[from] NoSolutionError),
 
+    #[error(transparent)]
+    RequiresPython(#[from] RequiresPythonError),
+
+    #[error(transparent)]
+    DuplicatePackage(#[from] DuplicatePackageError),
+
     #[error("{package} {version} depends on itself")]
     SelfDependency {
         /// Package whose dependencies we want.
@@ -219,6 +225,18 @@ impl std::fmt::Display for NoSolutionError {
 }
 
 impl NoSolutionError {
+    /// Explain the resolution failure as a tree of the incompatibilities that led to it.
+    ///
+    /// Unlike the report, which summarizes the failure in prose, the explanation includes every
+    /// intermediate derivation, which can help to track down deep transitive conflicts.
+    pub fn explain(&self) -> String {
+        let formatter = PubGrubReportFormatter {
+            available_versions: &self.available_versions,
+            python_requirement: self.python_requirement.as_ref(),
+        };
+        formatter.format_tree(&self.derivation_tree)
+    }
+
     /// Update the available versions attached to the error using the given package version index.
     ///
     /// Only packages used in the error's derivation tree will be retrieved.
@@ -337,3 +355,126 @@ impl NoSolutionError {
         self
     }
 }
+
+/// An error indicating that the `Requires-Python` of one or more resolved distributions can't be
+/// satisfied.
+#[derive(Debug, thiserror::Error)]
+pub enum RequiresPythonError {
+    /// One or more distributions exclude the target Python version.
+    #[error("The resolution contains packages that are incompatible with the target Python version ({python_version}):{}", format_requires_python(.dists))]
+    Incompatible {
+        python_version: Version,
+        dists: Vec<(ResolvedDist, VersionSpecifiers)>,
+    },
+    /// The distributions' `Requires-Python` specifiers have no Python version in common.
+    #[error("The resolution contains packages that don't support any Python version in common:{}", format_requires_python(.dists))]
+    NoCommonVersion {
+        dists: Vec<(ResolvedDist, VersionSpecifiers)>,
+    },
+}
+
+impl RequiresPythonError {
+    /// Return the offending distributions, along with their `Requires-Python` specifiers.
+    pub fn dists(&self) -> &[(ResolvedDist, VersionSpecifiers)] {
+        match self {
+            Self::Incompatible { dists, .. } | Self::NoCommonVersion { dists } => dists,
+        }
+    }
+}
+
+/// Format each distribution and its `Requires-Python` as a list item.
+fn format_requires_python(dists: &[(ResolvedDist, VersionSpecifiers)]) -> String {
+    dists
+        .iter()
+        .map(|(dist, requires_python)| format!("\n- {dist} requires Python {requires_python}"))
+        .collect()
+}
+
+/// An error indicating that one or more packages in a universal resolution are only required
+/// under some markers, but would be exported without them (and so installed everywhere).
+#[derive(Debug)]
+pub struct MissingMarkersError {
+    /// The distributions that are missing markers, along with the markers under which each is
+    /// required.
+    pub(crate) packages: Vec<(ResolvedDist, MarkerTree)>,
+}
+
+impl MissingMarkersError {
+    /// Return the distributions that are missing markers, along with the markers under which each
+    /// is required.
+    pub fn packages(&self) -> &[(ResolvedDist, MarkerTree)] {
+        &self.packages
+    }
+}
+
+impl std::error::Error for MissingMarkersError {}
+
+impl std::fmt::Display for MissingMarkersError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "The resolution contains packages that are only required under some markers, but would be written without them:"
+        )?;
+        for (dist, marker) in &self.packages {
+            write!(f, "\n- {dist} is only required if `{marker}`")?;
+        }
+        Ok(())
+    }
+}
+
+/// An error indicating that a resolution contains more than one distribution for the same
+/// package.
+#[derive(Debug)]
+pub struct DuplicatePackageError {
+    /// The duplicated distributions, in the order in which they were added to the resolution.
+    pub(crate) duplicates: Vec<(ResolvedDist, ResolvedDist)>,
+}
+
+impl DuplicatePackageError {
+    /// Return the duplicated distributions, in the order in which they were added to the
+    /// resolution.
+    pub fn duplicates(&self) -> &[(ResolvedDist, ResolvedDist)] {
+        &self.duplicates
+    }
+}
+
+impl std::error::Error for DuplicatePackageError {}
+
+impl std::fmt::Display for DuplicatePackageError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "The resolution contains multiple distributions for the same package:"
+        )?;
+        for (first, second) in &self.duplicates {
+            write!(f, "\n- {first} and {second}")?;
+        }
+        Ok(())
+    }
+}
+
+/// An error that occurs when merging resolutions that pin the same package to different versions.
+#[derive(Debug)]
+pub struct ConflictError {
+    /// The conflicting distributions, from the first and second resolution, respectively.
+    pub(crate) conflicts: Vec<(ResolvedDist, ResolvedDist)>,
+}
+
+impl ConflictError {
+    /// Return the conflicting distributions, from the first and second resolution, respectively.
+    pub fn conflicts(&self) -> &[(ResolvedDist, ResolvedDist)] {
+        &self.conflicts
+    }
+}
+
+impl std::error::Error for ConflictError {}
+
+impl std::fmt::Display for ConflictError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "The resolutions contain conflicting versions:")?;
+        for (first, second) in &self.conflicts {
+            write!(f, "\n- {first} conflicts with {second}")?;
+        }
+        Ok(())
+    }
+}
@@ -2,8 +2,10 @@ use std::str::FromStr;
 
 use chrono::{DateTime, Days, NaiveDate, NaiveTime, Utc};
 
+use uv_normalize::PackageName;
+
 /// A timestamp that excludes files newer than it.
-#[derive(Debug, Copy, Clone, serde::Deserialize, serde::Serialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
 pub struct ExcludeNewer(DateTime<Utc>);
 
 impl ExcludeNewer {
@@ -52,6 +54,30 @@ impl std::fmt::Display for ExcludeNewer {
     }
 }
 
+/// An [`ExcludeNewer`] timestamp that only applies to a single package, overriding the global
+/// timestamp (if any) for that package.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExcludeNewerPackage {
+    pub package: PackageName,
+    pub timestamp: ExcludeNewer,
+}
+
+impl FromStr for ExcludeNewerPackage {
+    type Err = String;
+
+    /// Parse an [`ExcludeNewerPackage`] from a string in the format `PACKAGE=DATE`.
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let Some((package, timestamp)) = input.split_once('=') else {
+            return Err(format!(
+                "`{input}` is not a valid package timestamp (expected `PACKAGE=DATE`)"
+            ));
+        };
+        let package = PackageName::from_str(package.trim()).map_err(|err| err.to_string())?;
+        let timestamp = ExcludeNewer::from_str(timestamp.trim())?;
+        Ok(Self { package, timestamp })
+    }
+}
+
 #[cfg(feature = "schemars")]
 impl schemars::JsonSchema for ExcludeNewer {
     fn schema_name() -> String {
@@ -1,7 +1,9 @@
 pub use dependency_mode::DependencyMode;
 pub use editables::BuiltEditableMetadata;
-pub use error::ResolveError;
-pub use exclude_newer::ExcludeNewer;
+pub use error::{
+    ConflictError, DuplicatePackageError, MissingMarkersError, RequiresPythonError, ResolveError,
+};
+pub use exclude_newer::{ExcludeNewer, ExcludeNewerPackage};
 pub use exclusions::Exclusions;
 pub use flat_index::FlatIndex;
 pub use lock::{Lock, LockError};
@@ -9,8 +11,13 @@ pub use manifest::Manifest;
 pub use options::{Options, OptionsBuilder};
 pub use preferences::{Preference, PreferenceError};
 pub use prerelease_mode::PreReleaseMode;
+pub use pylock::{PylockToml, PylockTomlError};
 pub use python_requirement::PythonRequirement;
-pub use resolution::{AnnotationStyle, DisplayResolutionGraph, ResolutionGraph};
+pub use resolution::{
+    AnnotatedDist, AnnotationStyle, ArtifactType, ArtifactUrlPolicy, DependencyTree,
+    DisplayResolutionGraph, HashStyle, PinDiff, RequirementsAndConstraints, RequirementsTxtOptions,
+    ResolutionDiff, ResolutionGraph, ResolutionSize, ResolutionStats, SourceKind, UrlRenderers,
+};
 pub use resolution_mode::ResolutionMode;
 pub use resolver::{
     BuildId, DefaultResolverProvider, InMemoryIndex, MetadataResponse, PackageVersionsResult,
@@ -38,6 +45,7 @@ mod pins;
 mod preferences;
 mod prerelease_mode;
 mod pubgrub;
+mod pylock;
 mod python_requirement;
 mod redirect;
 mod resolution;
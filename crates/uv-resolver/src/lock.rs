@@ -6,7 +6,7 @@ use std::collections::{BTreeMap, VecDeque};
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 use url::Url;
 
 use distribution_filename::WheelFilename;
@@ -16,12 +16,16 @@ use distribution_types::{
     RegistrySourceDist, RemoteSource, Resolution, ResolvedDist, ToUrlError,
 };
 use pep440_rs::Version;
-use pep508_rs::{MarkerEnvironment, VerbatimUrl};
+use pep508_rs::{MarkerEnvironment, MarkerTree, VerbatimUrl};
 use platform_tags::{TagCompatibility, TagPriority, Tags};
 use pypi_types::{HashDigest, ParsedArchiveUrl, ParsedGitUrl};
 use uv_git::{GitReference, GitSha};
 use uv_normalize::PackageName;
 
+use crate::pylock::{
+    hash_table, PylockToml, PylockTomlArchive, PylockTomlArtifact, PylockTomlDirectory,
+    PylockTomlPackage, PylockTomlVcs,
+};
 use crate::resolution::AnnotatedDist;
 
 #[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
@@ -52,6 +56,11 @@ impl Lock {
         Lock::try_from(wire)
     }
 
+    /// Convert the [`Lock`] to a [`Resolution`] for the given environment, without re-resolving.
+    ///
+    /// Starting from the root distribution, only those dependencies whose markers apply to the
+    /// given [`MarkerEnvironment`] are included, such that a single (universal) lock can be
+    /// installed on any supported platform.
     pub fn to_resolution(
         &self,
         marker_env: &MarkerEnvironment,
@@ -68,13 +77,19 @@ impl Lock {
             .expect("found too many distributions matching root")
             .expect("could not find root");
         let mut queue: VecDeque<&Distribution> = VecDeque::new();
+        let mut seen = FxHashSet::default();
         queue.push_back(root);
+        seen.insert(&root.id);
 
         let mut map = BTreeMap::default();
         while let Some(dist) = queue.pop_front() {
             for dep in &dist.dependencies {
-                let dep_dist = self.find_by_id(&dep.id);
-                queue.push_back(dep_dist);
+                if !dep.applies(marker_env) {
+                    continue;
+                }
+                if seen.insert(&dep.id) {
+                    queue.push_back(self.find_by_id(&dep.id));
+                }
             }
             let name = dist.id.name.clone();
             let resolved_dist = ResolvedDist::Installable(dist.to_dist(marker_env, tags));
@@ -84,6 +99,19 @@ impl Lock {
         Resolution::new(map, diagnostics)
     }
 
+    /// Convert the [`Lock`] to a PEP 751 `pylock.toml` lockfile.
+    ///
+    /// Local paths are written relative to `install_path` (i.e., the directory that will contain
+    /// the `pylock.toml` file) where possible.
+    pub fn to_pylock(&self, install_path: &Path) -> PylockToml {
+        PylockToml::new(
+            self.distributions
+                .iter()
+                .map(|dist| dist.to_pylock(install_path))
+                .collect(),
+        )
+    }
+
     /// Returns the distribution with the given name. If there are multiple
     /// matching distributions, then an error is returned. If there are no
     /// matching distributions, then `Ok(None)` is returned.
@@ -159,6 +187,21 @@ impl TryFrom<LockWire> for Lock {
         // it implies we somehow have a dependency with no corresponding locked
         // distribution.
         for dist in &wire.distributions {
+            // Check that every marker expression is valid, such that they can
+            // be evaluated when installing from the lock.
+            for marker in dist.marker.iter().chain(
+                dist.dependencies
+                    .iter()
+                    .filter_map(|dep| dep.marker.as_ref()),
+            ) {
+                if let Err(err) = MarkerTree::from_str(marker) {
+                    return Err(LockError::invalid_marker(
+                        dist.id.clone(),
+                        marker.clone(),
+                        err.message.to_string(),
+                    ));
+                }
+            }
             for dep in &dist.dependencies {
                 if !by_id.contains_key(&dep.id) {
                     return Err(LockError::unrecognized_dependency(
@@ -197,6 +240,8 @@ impl TryFrom<LockWire> for Lock {
 pub(crate) struct Distribution {
     #[serde(flatten)]
     pub(crate) id: DistributionId,
+    /// The markers under which the distribution is required, if it isn't required
+    /// unconditionally.
     #[serde(default)]
     pub(crate) marker: Option<String>,
     #[serde(default)]
@@ -210,24 +255,27 @@ pub(crate) struct Distribution {
 impl Distribution {
     pub(crate) fn from_annotated_dist(
         annotated_dist: &AnnotatedDist,
+        marker: Option<&MarkerTree>,
     ) -> Result<Distribution, LockError> {
         let id = DistributionId::from_annotated_dist(annotated_dist);
         let wheels = Wheel::from_annotated_dist(annotated_dist)?;
         let sdist = SourceDist::from_annotated_dist(annotated_dist)?;
         Ok(Distribution {
             id,
-            // TODO: Refactoring is needed to get the marker expressions for a
-            // particular resolved dist to this point.
-            marker: None,
+            marker: marker.map(ToString::to_string),
             sdist,
             wheels,
             dependencies: vec![],
         })
     }
 
-    pub(crate) fn add_dependency(&mut self, annotated_dist: &AnnotatedDist) {
+    pub(crate) fn add_dependency(
+        &mut self,
+        annotated_dist: &AnnotatedDist,
+        marker: Option<&MarkerTree>,
+    ) {
         self.dependencies
-            .push(Dependency::from_annotated_dist(annotated_dist));
+            .push(Dependency::from_annotated_dist(annotated_dist, marker));
     }
 
     /// Convert the [`Distribution`] to a [`Dist`] that can be used in installation.
@@ -380,6 +428,109 @@ impl Distribution {
         panic!("invalid lock distribution")
     }
 
+    /// Convert the [`Distribution`] to a `pylock.toml` package entry.
+    fn to_pylock(&self, install_path: &Path) -> PylockTomlPackage {
+        let mut package = PylockTomlPackage {
+            name: self.id.name.clone(),
+            version: Some(self.id.version.clone()),
+            marker: self.marker.clone(),
+            index: None,
+            vcs: None,
+            directory: None,
+            archive: None,
+            sdist: None,
+            wheels: vec![],
+        };
+
+        // Strip any query parameters and fragments (e.g., the Git reference), which are recorded
+        // separately in `pylock.toml`.
+        let mut url = self.id.source.url.clone();
+        url.set_query(None);
+        url.set_fragment(None);
+
+        match &self.id.source.kind {
+            SourceKind::Registry => {
+                package.index = Some(url);
+                package.sdist = self.sdist.as_ref().map(|sdist| PylockTomlArtifact {
+                    name: sdist
+                        .url
+                        .filename()
+                        .ok()
+                        .map(|filename| filename.to_string()),
+                    url: Some(sdist.url.clone()),
+                    path: None,
+                    size: sdist.size,
+                    hashes: hash_table(sdist.hash.iter().map(|hash| &hash.0)),
+                });
+                package.wheels = self
+                    .wheels
+                    .iter()
+                    .map(|wheel| PylockTomlArtifact {
+                        name: Some(wheel.filename.to_string()),
+                        url: Some(wheel.url.clone()),
+                        path: None,
+                        size: wheel.size,
+                        hashes: hash_table(wheel.hash.iter().map(|hash| &hash.0)),
+                    })
+                    .collect();
+            }
+            SourceKind::Git(git) => {
+                let requested_revision = match &git.kind {
+                    GitSourceKind::Tag(rev)
+                    | GitSourceKind::Branch(rev)
+                    | GitSourceKind::Rev(rev) => Some(rev.clone()),
+                    GitSourceKind::DefaultBranch => None,
+                };
+                package.vcs = Some(PylockTomlVcs {
+                    r#type: "git".to_string(),
+                    url: Some(url),
+                    path: None,
+                    requested_revision,
+                    commit_id: git.precise.to_string(),
+                    subdirectory: git.subdirectory.clone(),
+                });
+            }
+            SourceKind::Direct(direct) => {
+                package.archive = Some(PylockTomlArchive {
+                    url: Some(url),
+                    path: None,
+                    size: None,
+                    subdirectory: direct.subdirectory.clone(),
+                    hashes: hash_table(self.artifact_hash()),
+                });
+            }
+            SourceKind::Path => {
+                package.archive = Some(PylockTomlArchive {
+                    url: None,
+                    path: Some(pylock_path(&url, install_path)),
+                    size: None,
+                    subdirectory: None,
+                    hashes: hash_table(self.artifact_hash()),
+                });
+            }
+            SourceKind::Directory | SourceKind::Editable => {
+                package.directory = Some(PylockTomlDirectory {
+                    path: pylock_path(&url, install_path),
+                    editable: Some(matches!(self.id.source.kind, SourceKind::Editable)),
+                    subdirectory: None,
+                });
+            }
+        }
+
+        package
+    }
+
+    /// Returns the hash of the (single) wheel or source distribution for a direct URL or path
+    /// distribution.
+    fn artifact_hash(&self) -> Option<&HashDigest> {
+        self.wheels
+            .iter()
+            .filter_map(|wheel| wheel.hash.as_ref())
+            .chain(self.sdist.iter().filter_map(|sdist| sdist.hash.as_ref()))
+            .map(|hash| &hash.0)
+            .next()
+    }
+
     fn find_best_wheel(&self, tags: &Tags) -> Option<usize> {
         let mut best: Option<(TagPriority, usize)> = None;
         for (i, wheel) in self.wheels.iter().enumerate() {
@@ -902,6 +1053,19 @@ impl From<GitSourceKind> for GitReference {
     }
 }
 
+/// Convert a `file://` URL into a path for `pylock.toml`, relative to the `install_path` if the
+/// path is contained within it.
+fn pylock_path(url: &Url, install_path: &Path) -> PathBuf {
+    let path = url
+        .to_file_path()
+        .unwrap_or_else(|()| PathBuf::from(url.path()));
+    match path.strip_prefix(install_path) {
+        Ok(relative) if relative.as_os_str().is_empty() => PathBuf::from("."),
+        Ok(relative) => relative.to_path_buf(),
+        Err(_) => path,
+    }
+}
+
 /// Construct the lockfile-compatible [`URL`] for a [`GitSourceDist`].
 fn locked_git_url(git_dist: &GitSourceDist) -> Url {
     let mut url = git_dist.git.repository().clone();
@@ -1132,12 +1296,29 @@ impl TryFrom<WheelWire> for Wheel {
 pub(crate) struct Dependency {
     #[serde(flatten)]
     id: DistributionId,
+    /// The markers under which the dependency applies, if it isn't required
+    /// unconditionally (e.g., `sys_platform == 'win32'`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    marker: Option<String>,
 }
 
 impl Dependency {
-    fn from_annotated_dist(annotated_dist: &AnnotatedDist) -> Dependency {
+    fn from_annotated_dist(
+        annotated_dist: &AnnotatedDist,
+        marker: Option<&MarkerTree>,
+    ) -> Dependency {
         let id = DistributionId::from_annotated_dist(annotated_dist);
-        Dependency { id }
+        let marker = marker.map(ToString::to_string);
+        Dependency { id, marker }
+    }
+
+    /// Returns `true` if the dependency applies in the given [`MarkerEnvironment`].
+    fn applies(&self, marker_env: &MarkerEnvironment) -> bool {
+        self.marker.as_ref().map_or(true, |marker| {
+            MarkerTree::from_str(marker)
+                .expect("markers are validated when the lock is constructed")
+                .evaluate(marker_env, &[])
+        })
     }
 }
 
@@ -1237,6 +1418,17 @@ impl LockError {
         }
     }
 
+    fn invalid_marker(id: DistributionId, marker: String, message: String) -> LockError {
+        let kind = LockErrorKind::InvalidMarker {
+            id,
+            marker,
+            message,
+        };
+        LockError {
+            kind: Box::new(kind),
+        }
+    }
+
     fn hash(id: DistributionId, artifact_type: &'static str, expected: bool) -> LockError {
         let kind = LockErrorKind::Hash {
             id,
@@ -1256,6 +1448,7 @@ impl std::error::Error for LockError {
             LockErrorKind::DuplicateDependency { .. } => None,
             LockErrorKind::InvalidFileUrl { ref err } => Some(err),
             LockErrorKind::UnrecognizedDependency { ref err } => Some(err),
+            LockErrorKind::InvalidMarker { .. } => None,
             LockErrorKind::Hash { .. } => None,
         }
     }
@@ -1282,6 +1475,16 @@ impl std::fmt::Display for LockError {
             LockErrorKind::UnrecognizedDependency { .. } => {
                 write!(f, "found unrecognized dependency")
             }
+            LockErrorKind::InvalidMarker {
+                ref id,
+                ref marker,
+                ref message,
+            } => {
+                write!(
+                    f,
+                    "for distribution `{id}`, found invalid marker `{marker}`: {message}"
+                )
+            }
             LockErrorKind::Hash {
                 ref id,
                 artifact_type,
@@ -1341,6 +1544,16 @@ enum LockErrorKind {
         /// The actual error.
         err: UnrecognizedDependencyError,
     },
+    /// An error that occurs when a marker expression for a distribution, or
+    /// for one of its dependencies, could not be parsed.
+    InvalidMarker {
+        /// The ID of the distribution with the invalid marker.
+        id: DistributionId,
+        /// The marker expression that could not be parsed.
+        marker: String,
+        /// A description of the parse error.
+        message: String,
+    },
     /// An error that occurs when a hash is expected (or not) for a particular
     /// artifact, but one was not found (or was).
     Hash {
@@ -1484,6 +1697,8 @@ impl std::fmt::Display for HashParseError {
 
 #[cfg(test)]
 mod tests {
+    use requirements_txt::RequirementsTxtRequirement;
+
     use super::*;
 
     #[test]
@@ -1520,4 +1735,105 @@ hash = "sha256:048e05d0f6caeed70d731f3db756d35dcc1f35747c8c403364a8332c630441b8"
         let result: Result<Lock, _> = toml::from_str(data);
         insta::assert_debug_snapshot!(result);
     }
+
+    #[test]
+    fn to_pylock_round_trip() {
+        let data = r#"
+version = 1
+
+[[distribution]]
+name = "anyio"
+version = "4.3.0"
+source = "registry+https://pypi.org/simple"
+marker = "python_version >= '3.8'"
+
+[distribution.sdist]
+url = "https://files.pythonhosted.org/packages/db/4d/3970183622f0330d3c23d9b8a5f52e365e50381fd484d08e3285104333d3/anyio-4.3.0.tar.gz"
+hash = "sha256:f75253795a87df48568485fd18cdd2a3fa5c4f7c5be8e5e36637733fce06fed6"
+size = 159642
+
+[[distribution.wheel]]
+url = "https://files.pythonhosted.org/packages/14/fd/2f20c40b45e4fb4324834aea24bd4afdf1143390242c0b33774da0e2e34f/anyio-4.3.0-py3-none-any.whl"
+hash = "sha256:048e05d0f6caeed70d731f3db756d35dcc1f35747c8c403364a8332c630441b8"
+size = 85584
+
+[[distribution]]
+name = "iniconfig"
+version = "2.0.0"
+source = "git+https://github.com/pytest-dev/iniconfig?rev=main#93f5930e668c0d1ddf4597e38dd0dea4e2665e7a"
+
+[[distribution]]
+name = "project"
+version = "0.1.0"
+source = "editable+file:///foo/bar/project"
+"#;
+        let lock: Lock = toml::from_str(data).unwrap();
+        let install_path = Path::new("/foo/bar");
+
+        // Write the `pylock.toml`, then read it back.
+        let encoded = toml::to_string_pretty(&lock.to_pylock(install_path)).unwrap();
+        let pylock: PylockToml = toml::from_str(&encoded).unwrap();
+        let (requirements, editables, indexes) = pylock.to_requirements(install_path).unwrap();
+        assert!(indexes.is_empty());
+
+        let requirements = requirements
+            .iter()
+            .map(|entry| {
+                let RequirementsTxtRequirement::Named(requirement) = &entry.requirement else {
+                    panic!("expected a named requirement");
+                };
+                (requirement.to_string(), entry.hashes.clone())
+            })
+            .collect::<Vec<_>>();
+        insta::assert_debug_snapshot!(requirements, @r###"
+        [
+            (
+                "anyio==4.3.0 ; python_version >= '3.8'",
+                [
+                    "sha256:f75253795a87df48568485fd18cdd2a3fa5c4f7c5be8e5e36637733fce06fed6",
+                    "sha256:048e05d0f6caeed70d731f3db756d35dcc1f35747c8c403364a8332c630441b8",
+                ],
+            ),
+            (
+                "iniconfig @ git+https://github.com/pytest-dev/iniconfig@93f5930e668c0d1ddf4597e38dd0dea4e2665e7a",
+                [],
+            ),
+        ]
+        "###);
+
+        assert_eq!(
+            editables
+                .iter()
+                .map(|editable| editable.path.clone())
+                .collect::<Vec<_>>(),
+            vec![PathBuf::from("/foo/bar/project")]
+        );
+    }
+
+    #[test]
+    fn dependency_marker_invalid() {
+        let data = r#"
+version = 1
+
+[[distribution]]
+name = "anyio"
+version = "4.3.0"
+source = "path+file:///foo/bar"
+
+[[distribution.dependencies]]
+name = "idna"
+version = "3.7"
+source = "path+file:///foo/bar"
+marker = "sys_platform = 'win32'"
+
+[[distribution]]
+name = "idna"
+version = "3.7"
+source = "path+file:///foo/bar"
+"#;
+        let err = toml::from_str::<Lock>(data).unwrap_err();
+        assert!(err.to_string().contains(
+            "for distribution `anyio 4.3.0 path+file:///foo/bar`, found invalid marker `sys_platform = 'win32'`"
+        ));
+    }
 }
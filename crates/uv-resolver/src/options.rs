@@ -1,15 +1,21 @@
+use rustc_hash::FxHashMap;
+
 use uv_configuration::IndexStrategy;
+use uv_normalize::PackageName;
 
 use crate::{DependencyMode, ExcludeNewer, PreReleaseMode, ResolutionMode};
 
 /// Options for resolving a manifest.
-#[derive(Debug, Default, Copy, Clone)]
+#[derive(Debug, Default, Clone)]
 pub struct Options {
     pub resolution_mode: ResolutionMode,
     pub prerelease_mode: PreReleaseMode,
+    pub prerelease_packages: Vec<PackageName>,
     pub dependency_mode: DependencyMode,
     pub exclude_newer: Option<ExcludeNewer>,
+    pub exclude_newer_packages: FxHashMap<PackageName, ExcludeNewer>,
     pub index_strategy: IndexStrategy,
+    pub validate_requires_python: bool,
 }
 
 /// Builder for [`Options`].
@@ -17,9 +23,12 @@ pub struct Options {
 pub struct OptionsBuilder {
     resolution_mode: ResolutionMode,
     prerelease_mode: PreReleaseMode,
+    prerelease_packages: Vec<PackageName>,
     dependency_mode: DependencyMode,
     exclude_newer: Option<ExcludeNewer>,
+    exclude_newer_packages: FxHashMap<PackageName, ExcludeNewer>,
     index_strategy: IndexStrategy,
+    validate_requires_python: bool,
 }
 
 impl OptionsBuilder {
@@ -42,6 +51,14 @@ impl OptionsBuilder {
         self
     }
 
+    /// Sets the packages for which pre-release versions are allowed, regardless of the
+    /// [`PreReleaseMode`] (e.g., to allow a release candidate of a single package).
+    #[must_use]
+    pub fn prerelease_packages(mut self, prerelease_packages: Vec<PackageName>) -> Self {
+        self.prerelease_packages = prerelease_packages;
+        self
+    }
+
     /// Sets the dependency mode.
     #[must_use]
     pub fn dependency_mode(mut self, dependency_mode: DependencyMode) -> Self {
@@ -56,6 +73,17 @@ impl OptionsBuilder {
         self
     }
 
+    /// Sets the per-package exclusion dates, which take precedence over the global exclusion date
+    /// for the given packages.
+    #[must_use]
+    pub fn exclude_newer_packages(
+        mut self,
+        exclude_newer_packages: FxHashMap<PackageName, ExcludeNewer>,
+    ) -> Self {
+        self.exclude_newer_packages = exclude_newer_packages;
+        self
+    }
+
     /// Sets the index strategy.
     #[must_use]
     pub fn index_strategy(mut self, index_strategy: IndexStrategy) -> Self {
@@ -63,14 +91,25 @@ impl OptionsBuilder {
         self
     }
 
+    /// Sets whether to validate that every resolved distribution's `Requires-Python` is compatible
+    /// with the target Python version.
+    #[must_use]
+    pub fn validate_requires_python(mut self, validate_requires_python: bool) -> Self {
+        self.validate_requires_python = validate_requires_python;
+        self
+    }
+
     /// Builds the options.
     pub fn build(self) -> Options {
         Options {
             resolution_mode: self.resolution_mode,
             prerelease_mode: self.prerelease_mode,
+            prerelease_packages: self.prerelease_packages,
             dependency_mode: self.dependency_mode,
             exclude_newer: self.exclude_newer,
+            exclude_newer_packages: self.exclude_newer_packages,
             index_strategy: self.index_strategy,
+            validate_requires_python: self.validate_requires_python,
         }
     }
 }
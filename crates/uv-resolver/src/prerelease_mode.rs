@@ -55,44 +55,40 @@ pub(crate) enum PreReleaseStrategy {
 impl PreReleaseStrategy {
     pub(crate) fn from_mode(
         mode: PreReleaseMode,
+        packages: &[PackageName],
         manifest: &Manifest,
         markers: Option<&MarkerEnvironment>,
         dependencies: DependencyMode,
     ) -> Self {
+        // The packages with an explicit pre-release marker in their version requirements.
+        let explicit = || {
+            manifest
+                .requirements(markers, dependencies)
+                .filter(|requirement| {
+                    let RequirementSource::Registry { specifier, .. } = &requirement.source else {
+                        return false;
+                    };
+                    specifier
+                        .iter()
+                        .any(pep440_rs::VersionSpecifier::any_prerelease)
+                })
+                .map(|requirement| requirement.name.clone())
+                .chain(packages.iter().cloned())
+                .collect()
+        };
+
+        // Pre-releases are allowed for the given packages in any mode, so modes that don't
+        // otherwise track packages are promoted to their explicit counterparts.
         match mode {
-            PreReleaseMode::Disallow => Self::Disallow,
+            PreReleaseMode::Disallow if packages.is_empty() => Self::Disallow,
+            PreReleaseMode::Disallow => Self::Explicit(packages.iter().cloned().collect()),
             PreReleaseMode::Allow => Self::Allow,
-            PreReleaseMode::IfNecessary => Self::IfNecessary,
-            PreReleaseMode::Explicit => Self::Explicit(
-                manifest
-                    .requirements(markers, dependencies)
-                    .filter(|requirement| {
-                        let RequirementSource::Registry { specifier, .. } = &requirement.source
-                        else {
-                            return false;
-                        };
-                        specifier
-                            .iter()
-                            .any(pep440_rs::VersionSpecifier::any_prerelease)
-                    })
-                    .map(|requirement| requirement.name.clone())
-                    .collect(),
-            ),
-            PreReleaseMode::IfNecessaryOrExplicit => Self::IfNecessaryOrExplicit(
-                manifest
-                    .requirements(markers, dependencies)
-                    .filter(|requirement| {
-                        let RequirementSource::Registry { specifier, .. } = &requirement.source
-                        else {
-                            return false;
-                        };
-                        specifier
-                            .iter()
-                            .any(pep440_rs::VersionSpecifier::any_prerelease)
-                    })
-                    .map(|requirement| requirement.name.clone())
-                    .collect(),
-            ),
+            PreReleaseMode::IfNecessary if packages.is_empty() => Self::IfNecessary,
+            PreReleaseMode::IfNecessary => {
+                Self::IfNecessaryOrExplicit(packages.iter().cloned().collect())
+            }
+            PreReleaseMode::Explicit => Self::Explicit(explicit()),
+            PreReleaseMode::IfNecessaryOrExplicit => Self::IfNecessaryOrExplicit(explicit()),
         }
     }
 
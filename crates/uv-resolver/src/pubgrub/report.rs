@@ -12,7 +12,7 @@ use pubgrub::range::Range;
 use pubgrub::report::{DerivationTree, Derived, External, ReportFormatter};
 use pubgrub::term::Term;
 use pubgrub::type_aliases::Map;
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 use uv_normalize::PackageName;
 
 use crate::candidate_selector::CandidateSelector;
@@ -557,6 +557,61 @@ impl PubGrubReportFormatter<'_> {
         }
         hints
     }
+
+    /// Render a derivation tree as an indented tree, in which each derived incompatibility is
+    /// followed by the two incompatibilities it was derived from, down to the external facts
+    /// (e.g., a package's dependencies, or the lack of matching versions) that caused the failure.
+    ///
+    /// Derived incompatibilities that are shared between branches are only expanded once.
+    pub(crate) fn format_tree(
+        &self,
+        derivation_tree: &DerivationTree<PubGrubPackage, Range<Version>, UnavailableReason>,
+    ) -> String {
+        let mut lines = Vec::new();
+        let mut visited = FxHashSet::default();
+        self.format_tree_node(derivation_tree, "", "", &mut visited, &mut lines);
+        lines.join("\n")
+    }
+
+    fn format_tree_node(
+        &self,
+        derivation_tree: &DerivationTree<PubGrubPackage, Range<Version>, UnavailableReason>,
+        prefix: &str,
+        child_prefix: &str,
+        visited: &mut FxHashSet<usize>,
+        lines: &mut Vec<String>,
+    ) {
+        match derivation_tree {
+            DerivationTree::External(external) => {
+                lines.push(format!("{prefix}{}", self.format_external(external)));
+            }
+            DerivationTree::Derived(derived) => {
+                let conclusion = self.format_terms(&derived.terms);
+                if derived
+                    .shared_id
+                    .is_some_and(|shared_id| !visited.insert(shared_id))
+                {
+                    lines.push(format!("{prefix}{conclusion} (see above)"));
+                    return;
+                }
+                lines.push(format!("{prefix}{conclusion}"));
+                self.format_tree_node(
+                    &derived.cause1,
+                    &format!("{child_prefix}├── "),
+                    &format!("{child_prefix}│   "),
+                    visited,
+                    lines,
+                );
+                self.format_tree_node(
+                    &derived.cause2,
+                    &format!("{child_prefix}└── "),
+                    &format!("{child_prefix}    "),
+                    visited,
+                    lines,
+                );
+            }
+        }
+    }
 }
 
 #[derive(Derivative, Debug, Clone)]
@@ -0,0 +1,691 @@
+//! Support for the [PEP 751](https://peps.python.org/pep-0751/) `pylock.toml` lockfile format.
+//!
+//! Unlike `uv.lock`, `pylock.toml` is a standardized format intended for interoperability with
+//! other installers. A [`PylockToml`] can be produced from a [`crate::Lock`], and converted back
+//! into a set of pinned requirements for installation.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use url::Url;
+
+use distribution_filename::WheelFilename;
+use distribution_types::{
+    BuiltDist, Dist, File, FileLocation, IndexUrl, RegistryBuiltDist, RegistryBuiltWheel,
+    RegistrySourceDist, RemoteSource, Resolution, ResolvedDist, SourceDist,
+};
+use pep440_rs::Version;
+use pep508_rs::{MarkerEnvironment, MarkerTree, Pep508Error, VerbatimUrl, VersionOrUrl};
+use platform_tags::{TagCompatibility, Tags};
+use pypi_types::{HashAlgorithm, HashDigest, HashError, VerbatimParsedUrl};
+use requirements_txt::{
+    EditableRequirement, RequirementEntry, RequirementsTxtParserError, RequirementsTxtRequirement,
+};
+use uv_normalize::PackageName;
+
+/// The version of the `pylock.toml` format produced by uv.
+const LOCK_VERSION: &str = "1.0";
+
+#[derive(Debug, thiserror::Error)]
+pub enum PylockTomlError {
+    #[error("Unsupported `pylock.toml` version `{0}` (expected `{LOCK_VERSION}`)")]
+    UnsupportedVersion(String),
+    #[error("Package `{0}` has an invalid marker `{1}`")]
+    InvalidMarker(PackageName, String),
+    #[error("Package `{0}` must include a `version`, or one of `vcs`, `directory`, or `archive`")]
+    MissingVersion(PackageName),
+    #[error("Package `{0}` must include either a `url` or a `path` for its `{1}` source")]
+    MissingLocation(PackageName, &'static str),
+    #[error("Package `{0}` uses an unsupported version control system: `{1}`")]
+    UnsupportedVcs(PackageName, String),
+    #[error("Failed to convert path to URL: `{}`", _0.display())]
+    InvalidPath(PathBuf),
+    #[error("Package `{0}` has an invalid wheel filename: `{1}`")]
+    InvalidWheelFilename(PackageName, String),
+    #[error("Package `{0}` has no wheel compatible with the current platform, and no source distribution")]
+    NoCompatibleArtifact(PackageName),
+    #[error(transparent)]
+    Requirement(#[from] Box<Pep508Error<VerbatimParsedUrl>>),
+    #[error(transparent)]
+    Editable(#[from] RequirementsTxtParserError),
+    #[error(transparent)]
+    Hash(#[from] HashError),
+    #[error(transparent)]
+    Distribution(#[from] distribution_types::Error),
+}
+
+/// A `pylock.toml` file, as defined in PEP 751.
+///
+/// Only the subset of the format that's needed to install a set of packages is modeled here; any
+/// other keys (e.g., `environments` or `tool`) are ignored on read.
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct PylockToml {
+    lock_version: String,
+    created_by: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    packages: Vec<PylockTomlPackage>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct PylockTomlPackage {
+    pub(crate) name: PackageName,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) version: Option<Version>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) marker: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) index: Option<Url>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) vcs: Option<PylockTomlVcs>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) directory: Option<PylockTomlDirectory>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) archive: Option<PylockTomlArchive>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) sdist: Option<PylockTomlArtifact>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) wheels: Vec<PylockTomlArtifact>,
+}
+
+/// A package sourced from a version control system (e.g., a Git repository).
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct PylockTomlVcs {
+    pub(crate) r#type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) url: Option<Url>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) path: Option<PathBuf>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) requested_revision: Option<String>,
+    pub(crate) commit_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) subdirectory: Option<String>,
+}
+
+/// A package sourced from a local source tree.
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct PylockTomlDirectory {
+    pub(crate) path: PathBuf,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) editable: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) subdirectory: Option<String>,
+}
+
+/// A package sourced from a direct archive (e.g., `foo @ https://example.com/foo-1.0.tar.gz`).
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct PylockTomlArchive {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) url: Option<Url>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) path: Option<PathBuf>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) size: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) subdirectory: Option<String>,
+    #[serde(default)]
+    pub(crate) hashes: BTreeMap<String, String>,
+}
+
+/// A source distribution or wheel for a package sourced from an index.
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct PylockTomlArtifact {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) url: Option<Url>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) path: Option<PathBuf>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) size: Option<u64>,
+    #[serde(default)]
+    pub(crate) hashes: BTreeMap<String, String>,
+}
+
+impl PylockToml {
+    pub(crate) fn new(packages: Vec<PylockTomlPackage>) -> Self {
+        Self {
+            lock_version: LOCK_VERSION.to_string(),
+            created_by: "uv".to_string(),
+            packages,
+        }
+    }
+
+    /// Convert the `pylock.toml` into a set of pinned requirements, along with any editable
+    /// requirements and the indexes from which the pinned packages were locked.
+    ///
+    /// Relative paths are resolved against `install_path` (i.e., the directory containing the
+    /// `pylock.toml` file). Packages retain their markers, such that only those packages that apply
+    /// to the target environment are installed. PyPI is omitted from the returned indexes, since
+    /// it's used by default.
+    pub fn to_requirements(
+        &self,
+        install_path: &Path,
+    ) -> Result<
+        (
+            Vec<RequirementEntry>,
+            Vec<EditableRequirement>,
+            Vec<IndexUrl>,
+        ),
+        PylockTomlError,
+    > {
+        self.check_version()?;
+
+        let mut requirements = Vec::with_capacity(self.packages.len());
+        let mut editables = Vec::new();
+        let mut indexes = Vec::new();
+        for package in &self.packages {
+            package.parse_marker()?;
+            let marker = package
+                .marker
+                .as_deref()
+                .map(|marker| format!(" ; {marker}"))
+                .unwrap_or_default();
+
+            // Editable source trees are installed as editables; everything else is installed as a
+            // pinned requirement.
+            if let Some(directory) = package.editable() {
+                let path = subdirectory(install_path.join(&directory.path), directory);
+                let editable = EditableRequirement::parse(
+                    &format!("{}{marker}", path.display()),
+                    None,
+                    install_path,
+                )?;
+                editables.push(editable);
+                continue;
+            }
+
+            if let Some(index) = &package.index {
+                let index = IndexUrl::from(VerbatimUrl::from_url(index.clone()));
+                if !matches!(index, IndexUrl::Pypi(_)) && !indexes.contains(&index) {
+                    indexes.push(index);
+                }
+            }
+
+            let (requirement, hashes) = package.to_requirement(install_path)?;
+            let requirement =
+                RequirementsTxtRequirement::parse(&format!("{requirement}{marker}"), install_path)?;
+            requirements.push(RequirementEntry {
+                requirement,
+                hashes,
+            });
+        }
+
+        Ok((requirements, editables, indexes))
+    }
+
+    /// Convert the `pylock.toml` into a [`Resolution`] for the given environment, without
+    /// re-resolving.
+    ///
+    /// Only those packages whose markers apply to the given [`MarkerEnvironment`] are included, and
+    /// each is installed from its locked artifacts: for packages from an index, the most compatible
+    /// wheel for the given [`Tags`], or else the source distribution. Editable packages are omitted,
+    /// since they're installed from the editables returned by [`PylockToml::to_requirements`].
+    pub fn to_resolution(
+        &self,
+        install_path: &Path,
+        markers: &MarkerEnvironment,
+        tags: &Tags,
+    ) -> Result<Resolution, PylockTomlError> {
+        self.check_version()?;
+
+        let mut packages = BTreeMap::new();
+        for package in &self.packages {
+            if package
+                .parse_marker()?
+                .is_some_and(|marker| !marker.evaluate(markers, &[]))
+            {
+                continue;
+            }
+            if package.editable().is_some() {
+                continue;
+            }
+            let dist = package.to_dist(install_path, tags)?;
+            packages.insert(package.name.clone(), ResolvedDist::Installable(dist));
+        }
+
+        Ok(Resolution::new(packages, vec![]))
+    }
+
+    /// Per PEP 751, installers must reject lockfiles with an unsupported major version.
+    fn check_version(&self) -> Result<(), PylockTomlError> {
+        if self.lock_version.split('.').next() == LOCK_VERSION.split('.').next() {
+            Ok(())
+        } else {
+            Err(PylockTomlError::UnsupportedVersion(
+                self.lock_version.clone(),
+            ))
+        }
+    }
+}
+
+impl PylockTomlPackage {
+    /// Parse the marker under which this package is required, if any.
+    fn parse_marker(&self) -> Result<Option<MarkerTree>, PylockTomlError> {
+        self.marker
+            .as_deref()
+            .map(|marker| {
+                MarkerTree::from_str(marker).map_err(|_| {
+                    PylockTomlError::InvalidMarker(self.name.clone(), marker.to_string())
+                })
+            })
+            .transpose()
+    }
+
+    /// Return the source tree for this package, if it should be installed in editable mode.
+    fn editable(&self) -> Option<&PylockTomlDirectory> {
+        self.directory
+            .as_ref()
+            .filter(|directory| directory.editable == Some(true))
+    }
+
+    /// Return the [`Dist`] that installs this package from its locked artifacts.
+    fn to_dist(&self, install_path: &Path, tags: &Tags) -> Result<Dist, PylockTomlError> {
+        let name = &self.name;
+
+        // Packages that aren't from an index are installed from their direct URL.
+        if self.vcs.is_some() || self.directory.is_some() || self.archive.is_some() {
+            let (requirement, _) = self.to_requirement(install_path)?;
+            let requirement =
+                pep508_rs::Requirement::<VerbatimParsedUrl>::parse(&requirement, install_path)
+                    .map_err(Box::new)?;
+            let Some(VersionOrUrl::Url(url)) = requirement.version_or_url else {
+                unreachable!("direct requirements always include a URL");
+            };
+            return Ok(Dist::from_url(name.clone(), url)?);
+        }
+
+        let Some(version) = &self.version else {
+            return Err(PylockTomlError::MissingVersion(name.clone()));
+        };
+        let index = IndexUrl::from(VerbatimUrl::from_url(self.index.clone().unwrap_or_else(
+            || Url::parse("https://pypi.org/simple").expect("PyPI URL is valid"),
+        )));
+
+        let sdist = self
+            .sdist
+            .as_ref()
+            .map(|sdist| {
+                let file = sdist.to_file(install_path, name, "sdist")?;
+                Ok::<_, PylockTomlError>(RegistrySourceDist {
+                    name: name.clone(),
+                    version: version.clone(),
+                    file: Box::new(file),
+                    index: index.clone(),
+                    wheels: vec![],
+                })
+            })
+            .transpose()?;
+        let wheels = self
+            .wheels
+            .iter()
+            .map(|wheel| {
+                let file = wheel.to_file(install_path, name, "wheel")?;
+                let filename = WheelFilename::from_str(&file.filename).map_err(|_| {
+                    PylockTomlError::InvalidWheelFilename(name.clone(), file.filename.clone())
+                })?;
+                Ok(RegistryBuiltWheel {
+                    filename,
+                    file: Box::new(file),
+                    index: index.clone(),
+                })
+            })
+            .collect::<Result<Vec<_>, PylockTomlError>>()?;
+
+        // Prefer the most compatible wheel, falling back to the source distribution.
+        let best_wheel_index = wheels
+            .iter()
+            .enumerate()
+            .filter_map(|(index, wheel)| match wheel.filename.compatibility(tags) {
+                TagCompatibility::Compatible(priority) => Some((priority, index)),
+                TagCompatibility::Incompatible(_) => None,
+            })
+            .max_by_key(|(priority, _)| *priority)
+            .map(|(_, index)| index);
+        match (best_wheel_index, sdist) {
+            (Some(best_wheel_index), sdist) => {
+                Ok(Dist::Built(BuiltDist::Registry(RegistryBuiltDist {
+                    wheels,
+                    best_wheel_index,
+                    sdist,
+                })))
+            }
+            (None, Some(sdist)) => Ok(Dist::Source(SourceDist::Registry(RegistrySourceDist {
+                wheels,
+                ..sdist
+            }))),
+            (None, None) => Err(PylockTomlError::NoCompatibleArtifact(name.clone())),
+        }
+    }
+
+    /// Return the PEP 508 requirement (without markers) that pins this package to its locked
+    /// source, along with the hashes of its artifacts.
+    fn to_requirement(
+        &self,
+        install_path: &Path,
+    ) -> Result<(String, Vec<String>), PylockTomlError> {
+        let name = &self.name;
+
+        if let Some(vcs) = &self.vcs {
+            if vcs.r#type != "git" {
+                return Err(PylockTomlError::UnsupportedVcs(
+                    name.clone(),
+                    vcs.r#type.clone(),
+                ));
+            }
+            let mut url = location(
+                vcs.url.as_ref(),
+                vcs.path.as_deref(),
+                install_path,
+                name,
+                "vcs",
+            )?;
+            url.set_path(&format!("{}@{}", url.path(), vcs.commit_id));
+            if let Some(subdirectory) = &vcs.subdirectory {
+                url.set_fragment(Some(&format!("subdirectory={subdirectory}")));
+            }
+            return Ok((format!("{name} @ git+{url}"), vec![]));
+        }
+
+        if let Some(directory) = &self.directory {
+            let path = subdirectory(install_path.join(&directory.path), directory);
+            let url = Url::from_directory_path(&path)
+                .map_err(|()| PylockTomlError::InvalidPath(path.clone()))?;
+            return Ok((format!("{name} @ {url}"), vec![]));
+        }
+
+        if let Some(archive) = &self.archive {
+            let mut url = location(
+                archive.url.as_ref(),
+                archive.path.as_deref(),
+                install_path,
+                name,
+                "archive",
+            )?;
+            if let Some(subdirectory) = &archive.subdirectory {
+                url.set_fragment(Some(&format!("subdirectory={subdirectory}")));
+            }
+            return Ok((format!("{name} @ {url}"), hashes(&archive.hashes).collect()));
+        }
+
+        // Otherwise, the package comes from an index, and any of its artifacts are acceptable.
+        let Some(version) = &self.version else {
+            return Err(PylockTomlError::MissingVersion(name.clone()));
+        };
+        let hashes = self
+            .sdist
+            .iter()
+            .chain(&self.wheels)
+            .flat_map(|artifact| hashes(&artifact.hashes))
+            .collect();
+        Ok((format!("{name}=={version}"), hashes))
+    }
+}
+
+impl PylockTomlArtifact {
+    /// Convert the artifact into a [`File`], resolving any relative `path` against `install_path`.
+    fn to_file(
+        &self,
+        install_path: &Path,
+        name: &PackageName,
+        kind: &'static str,
+    ) -> Result<File, PylockTomlError> {
+        let url = location(
+            self.url.as_ref(),
+            self.path.as_deref(),
+            install_path,
+            name,
+            kind,
+        )?;
+        let filename = match &self.name {
+            Some(filename) => filename.clone(),
+            None => url.filename()?.to_string(),
+        };
+        let hashes = hashes(&self.hashes)
+            .map(|hash| HashDigest::from_str(&hash))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(File {
+            dist_info_metadata: false,
+            filename,
+            hashes,
+            requires_python: None,
+            size: self.size,
+            upload_time_utc_ms: None,
+            url: FileLocation::AbsoluteUrl(url.to_string()),
+            yanked: None,
+        })
+    }
+}
+
+/// Resolve the `url` or (relative) `path` for a package source into a URL.
+fn location(
+    url: Option<&Url>,
+    path: Option<&Path>,
+    install_path: &Path,
+    name: &PackageName,
+    kind: &'static str,
+) -> Result<Url, PylockTomlError> {
+    if let Some(url) = url {
+        return Ok(url.clone());
+    }
+    let Some(path) = path else {
+        return Err(PylockTomlError::MissingLocation(name.clone(), kind));
+    };
+    let path = install_path.join(path);
+    Url::from_file_path(&path).map_err(|()| PylockTomlError::InvalidPath(path))
+}
+
+/// Apply the `subdirectory`, if any, to the path of a source tree.
+fn subdirectory(path: PathBuf, directory: &PylockTomlDirectory) -> PathBuf {
+    match &directory.subdirectory {
+        Some(subdirectory) => path.join(subdirectory),
+        None => path,
+    }
+}
+
+/// Render the supported hashes in a `pylock.toml` hash table as `{algorithm}:{digest}` strings.
+fn hashes(hashes: &BTreeMap<String, String>) -> impl Iterator<Item = String> + '_ {
+    hashes
+        .iter()
+        .filter(|(algorithm, _)| HashAlgorithm::from_str(algorithm).is_ok())
+        .map(|(algorithm, digest)| format!("{algorithm}:{digest}"))
+}
+
+/// Convert a set of [`HashDigest`]s into a `pylock.toml` hash table.
+pub(crate) fn hash_table<'a>(
+    digests: impl IntoIterator<Item = &'a HashDigest>,
+) -> BTreeMap<String, String> {
+    digests
+        .into_iter()
+        .map(|digest| (digest.algorithm.to_string(), digest.digest.to_string()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_requirements() {
+        let data = r#"
+lock-version = "1.0"
+created-by = "uv"
+
+[[packages]]
+name = "anyio"
+version = "4.3.0"
+index = "https://pypi.org/simple"
+
+[[packages.wheels]]
+name = "anyio-4.3.0-py3-none-any.whl"
+url = "https://files.pythonhosted.org/packages/14/fd/2f20c40b45e4fb4324834aea24bd4afdf1143390242c0b33774da0e2e34f/anyio-4.3.0-py3-none-any.whl"
+hashes = { sha256 = "048e05d0f6caeed70d731f3db756d35dcc1f35747c8c403364a8332c630441b8" }
+
+[[packages]]
+name = "exceptiongroup"
+version = "1.2.0"
+marker = "python_version < '3.11'"
+index = "https://example.com/simple"
+
+[[packages]]
+name = "iniconfig"
+version = "2.0.0"
+
+[packages.vcs]
+type = "git"
+url = "https://github.com/pytest-dev/iniconfig"
+commit-id = "93f5930e668c0d1ddf4597e38dd0dea4e2665e7a"
+"#;
+        let pylock: PylockToml = toml::from_str(data).unwrap();
+        let (requirements, editables, indexes) = pylock.to_requirements(Path::new("/")).unwrap();
+        assert!(editables.is_empty());
+        assert_eq!(
+            indexes.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            vec!["https://example.com/simple"]
+        );
+
+        let requirements = requirements
+            .iter()
+            .map(|entry| {
+                let RequirementsTxtRequirement::Named(requirement) = &entry.requirement else {
+                    panic!("expected a named requirement");
+                };
+                (requirement.to_string(), entry.hashes.clone())
+            })
+            .collect::<Vec<_>>();
+        insta::assert_debug_snapshot!(requirements, @r###"
+        [
+            (
+                "anyio==4.3.0",
+                [
+                    "sha256:048e05d0f6caeed70d731f3db756d35dcc1f35747c8c403364a8332c630441b8",
+                ],
+            ),
+            (
+                "exceptiongroup==1.2.0 ; python_version < '3.11'",
+                [],
+            ),
+            (
+                "iniconfig @ git+https://github.com/pytest-dev/iniconfig@93f5930e668c0d1ddf4597e38dd0dea4e2665e7a",
+                [],
+            ),
+        ]
+        "###);
+    }
+
+    #[test]
+    fn to_resolution() {
+        let data = r#"
+lock-version = "1.0"
+created-by = "uv"
+
+[[packages]]
+name = "anyio"
+version = "4.3.0"
+
+[[packages.wheels]]
+url = "https://files.pythonhosted.org/packages/14/fd/2f20c40b45e4fb4324834aea24bd4afdf1143390242c0b33774da0e2e34f/anyio-4.3.0-py3-none-any.whl"
+hashes = { sha256 = "048e05d0f6caeed70d731f3db756d35dcc1f35747c8c403364a8332c630441b8" }
+
+[[packages]]
+name = "exceptiongroup"
+version = "1.2.0"
+marker = "python_version < '3.11'"
+
+[[packages.wheels]]
+url = "https://files.pythonhosted.org/packages/b8/9a/5028fd52db10e600f1c4674441b968cf2ea4959085bfb5b99fb1250e5f68/exceptiongroup-1.2.0-py3-none-any.whl"
+hashes = { sha256 = "4bfd3996ac73b41e9b9628b04e079f193850720ea5945fc96a08633c66912f14" }
+
+[[packages]]
+name = "markupsafe"
+version = "2.1.5"
+index = "https://example.com/simple"
+
+[packages.sdist]
+url = "https://example.com/files/MarkupSafe-2.1.5.tar.gz"
+hashes = { sha256 = "d283d37a890ba4c1ae73ffadf8046435c76e7bc2247bbb63c00bd1a709c6544b" }
+
+[[packages.wheels]]
+url = "https://example.com/files/MarkupSafe-2.1.5-cp312-cp312-win_amd64.whl"
+hashes = { sha256 = "823b65d8706e32ad2df51ed89496147a42a2a6e01c13cfb6ffb8b1e92bc910bb" }
+"#;
+        let pylock: PylockToml = toml::from_str(data).unwrap();
+        let tags = Tags::from_platform(
+            &platform_tags::Platform::new(
+                platform_tags::Os::Manylinux {
+                    major: 2,
+                    minor: 28,
+                },
+                platform_tags::Arch::X86_64,
+            ),
+            (3, 12),
+        )
+        .unwrap();
+        let markers = crate::resolution::tests::marker_environment("linux", "Linux");
+        let resolution = pylock
+            .to_resolution(Path::new("/"), &markers, &tags)
+            .unwrap();
+
+        // `exceptiongroup` isn't required on Python 3.12.
+        assert_eq!(
+            resolution
+                .packages()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec!["anyio", "markupsafe"]
+        );
+
+        // `anyio` is installed from its locked wheel, which retains its hash.
+        let anyio = PackageName::from_str("anyio").unwrap();
+        let Some(Dist::Built(BuiltDist::Registry(dist))) = resolution.get_remote(&anyio) else {
+            panic!("expected a registry wheel");
+        };
+        let wheel = dist.best_wheel();
+        assert_eq!(
+            wheel.file.url.to_string(),
+            "https://files.pythonhosted.org/packages/14/fd/2f20c40b45e4fb4324834aea24bd4afdf1143390242c0b33774da0e2e34f/anyio-4.3.0-py3-none-any.whl"
+        );
+        assert_eq!(
+            wheel.file.hashes,
+            vec![HashDigest::from_str(
+                "sha256:048e05d0f6caeed70d731f3db756d35dcc1f35747c8c403364a8332c630441b8"
+            )
+            .unwrap()]
+        );
+        assert_eq!(wheel.index.to_string(), "https://pypi.org/simple");
+
+        // `markupsafe` has no compatible wheel, so it's built from its source distribution.
+        let markupsafe = PackageName::from_str("markupsafe").unwrap();
+        let Some(Dist::Source(SourceDist::Registry(dist))) = resolution.get_remote(&markupsafe)
+        else {
+            panic!("expected a registry source distribution");
+        };
+        assert_eq!(dist.file.filename, "MarkupSafe-2.1.5.tar.gz");
+        assert_eq!(
+            dist.file.url.to_string(),
+            "https://example.com/files/MarkupSafe-2.1.5.tar.gz"
+        );
+        assert_eq!(dist.index.to_string(), "https://example.com/simple");
+    }
+
+    #[test]
+    fn unsupported_version() {
+        let data = r#"
+lock-version = "2.0"
+created-by = "uv"
+"#;
+        let pylock: PylockToml = toml::from_str(data).unwrap();
+        let err = pylock.to_requirements(Path::new("/")).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Unsupported `pylock.toml` version `2.0` (expected `1.0`)"
+        );
+    }
+}
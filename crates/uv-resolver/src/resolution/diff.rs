@@ -0,0 +1,372 @@
+use std::cmp::Ordering;
+
+use cache_key::CanonicalUrl;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use distribution_types::{
+    DistributionMetadata, InstalledDirectUrlDist, InstalledDist, Name, ResolvedDist,
+    VersionOrUrlRef,
+};
+use pep440_rs::Version;
+use uv_normalize::PackageName;
+use uv_types::InstalledPackagesProvider;
+
+use crate::{Preference, RequirementsTxtOptions, ResolutionGraph};
+
+/// The changes required to bring an environment in line with a [`ResolutionGraph`], as computed
+/// by [`ResolutionGraph::diff`].
+#[derive(Debug, Default, Clone)]
+pub struct ResolutionDiff {
+    /// Distributions that aren't installed at all.
+    pub install: Vec<ResolvedDist>,
+    /// Installed distributions that would be replaced by a newer version.
+    pub upgrade: Vec<(InstalledDist, ResolvedDist)>,
+    /// Installed distributions that would be replaced by an older version.
+    pub downgrade: Vec<(InstalledDist, ResolvedDist)>,
+    /// Installed distributions that would be replaced by the same version from a different source
+    /// (e.g., a different URL).
+    pub reinstall: Vec<(InstalledDist, ResolvedDist)>,
+    /// Installed distributions that already match the resolution.
+    pub unchanged: Vec<InstalledDist>,
+    /// Installed distributions that aren't part of the resolution.
+    pub remove: Vec<InstalledDist>,
+}
+
+impl ResolutionDiff {
+    /// Returns `true` if the environment already matches the resolution.
+    pub fn is_empty(&self) -> bool {
+        self.install.is_empty()
+            && self.upgrade.is_empty()
+            && self.downgrade.is_empty()
+            && self.reinstall.is_empty()
+            && self.remove.is_empty()
+    }
+}
+
+/// The changes between the pinned versions in an existing output file and a [`ResolutionGraph`],
+/// as computed by [`ResolutionGraph::pin_diff`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PinDiff {
+    /// Packages that weren't previously pinned.
+    pub added: Vec<(PackageName, Version)>,
+    /// Packages that were previously pinned, along with the previous and resolved versions.
+    pub changed: Vec<(PackageName, Version, Version)>,
+    /// Packages that were previously pinned, but are no longer part of the resolution.
+    pub removed: Vec<(PackageName, Version)>,
+}
+
+impl PinDiff {
+    /// Returns `true` if the resolution matches the previous pins.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.changed.is_empty() && self.removed.is_empty()
+    }
+}
+
+impl ResolutionGraph {
+    /// Compute the changes between the given pins (e.g., as read from an existing output file) and
+    /// the resolution, sorted by package name.
+    ///
+    /// Only registry distributions are compared, since distributions resolved from a URL or local
+    /// path aren't pinned to a version. If a package is pinned more than once (e.g., under
+    /// different markers), the first pin is used.
+    pub fn pin_diff(&self, pins: &[Preference]) -> PinDiff {
+        let mut previous = FxHashMap::default();
+        for pin in pins {
+            previous.entry(pin.name()).or_insert(pin.version());
+        }
+
+        let mut diff = PinDiff::default();
+        for dist in self.petgraph.node_weights() {
+            if !matches!(dist.dist.version_or_url(), VersionOrUrlRef::Version(_)) {
+                previous.remove(dist.name());
+                continue;
+            }
+            match previous.remove(dist.name()) {
+                None => diff
+                    .added
+                    .push((dist.name().clone(), dist.metadata.version.clone())),
+                Some(version) if *version == dist.metadata.version => {}
+                Some(version) => diff.changed.push((
+                    dist.name().clone(),
+                    version.clone(),
+                    dist.metadata.version.clone(),
+                )),
+            }
+        }
+        diff.removed.extend(
+            previous
+                .into_iter()
+                .map(|(name, version)| (name.clone(), version.clone())),
+        );
+
+        diff.added.sort_unstable();
+        diff.changed.sort_unstable();
+        diff.removed.sort_unstable();
+        diff
+    }
+
+    /// Compute the changes required to bring the given set of installed packages in line with the
+    /// resolution, without modifying the environment (e.g., to power a `--dry-run`).
+    ///
+    /// Registry distributions are compared by version. Distributions resolved from a URL or local
+    /// path are compared by their (canonicalized) URL, such that a change in source is reported as
+    /// a reinstall even if the version is unchanged.
+    ///
+    /// Any installed packages that aren't part of the resolution are marked for removal, as in
+    /// `pip sync`.
+    pub fn diff(&self, installed: &impl InstalledPackagesProvider) -> ResolutionDiff {
+        let mut diff = ResolutionDiff::default();
+
+        for dist in self.petgraph.node_weights() {
+            // If the resolver selected the installed distribution, it's unchanged by definition.
+            let resolved = match &dist.dist {
+                ResolvedDist::Installed(installed) => {
+                    diff.unchanged.push(installed.clone());
+                    continue;
+                }
+                ResolvedDist::Installable(resolved) => resolved,
+            };
+
+            let mut packages = installed.get_packages(dist.name()).into_iter();
+            let Some(existing) = packages.next() else {
+                diff.install.push(dist.dist.clone());
+                continue;
+            };
+
+            // If a package is installed multiple times, the duplicates are always removed.
+            diff.remove.extend(packages.cloned());
+
+            let same_source = match resolved.version_or_url() {
+                VersionOrUrlRef::Url(url) => match existing {
+                    InstalledDist::Url(InstalledDirectUrlDist {
+                        url: installed_url, ..
+                    }) => CanonicalUrl::new(url.raw()) == CanonicalUrl::new(installed_url),
+                    _ => false,
+                },
+                VersionOrUrlRef::Version(_) => !matches!(existing, InstalledDist::Url(_)),
+            };
+
+            let entry = (existing.clone(), dist.dist.clone());
+            match existing.version().cmp(&dist.metadata.version) {
+                Ordering::Less => diff.upgrade.push(entry),
+                Ordering::Greater => diff.downgrade.push(entry),
+                Ordering::Equal if same_source => diff.unchanged.push(existing.clone()),
+                Ordering::Equal => diff.reinstall.push(entry),
+            }
+        }
+
+        // Remove any packages that aren't part of the resolution.
+        let names = self
+            .petgraph
+            .node_weights()
+            .map(Name::name)
+            .collect::<FxHashSet<_>>();
+        diff.remove.extend(
+            installed
+                .iter()
+                .filter(|dist| !names.contains(dist.name()))
+                .cloned(),
+        );
+
+        diff
+    }
+
+    /// Render only the packages that need to be installed, upgraded, downgraded, or reinstalled
+    /// to bring the given set of installed packages in line with the resolution, in the
+    /// `requirements.txt` format.
+    ///
+    /// Packages that are already satisfied, as determined by [`ResolutionGraph::diff`], are
+    /// omitted, as are any removals, which can't be expressed as requirements.
+    pub fn requirements_txt_delta(
+        &self,
+        installed: &impl InstalledPackagesProvider,
+        options: &RequirementsTxtOptions,
+    ) -> String {
+        let unchanged = self
+            .diff(installed)
+            .unchanged
+            .iter()
+            .map(|dist| dist.name().clone())
+            .collect::<Vec<_>>();
+        self.requirements_txt_excluding(options, &unchanged)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::str::FromStr;
+
+    use distribution_types::{InstalledDist, InstalledRegistryDist, Name};
+    use pep440_rs::Version;
+    use url::Url;
+    use uv_normalize::PackageName;
+    use uv_types::InstalledPackagesProvider;
+
+    use crate::resolution::tests::{path_dist, registry_dist, resolution_graph};
+    use crate::{PinDiff, Preference, RequirementsTxtOptions};
+
+    #[derive(Clone)]
+    struct Installed(Vec<InstalledDist>);
+
+    impl InstalledPackagesProvider for Installed {
+        fn iter(&self) -> impl Iterator<Item = &InstalledDist> {
+            self.0.iter()
+        }
+
+        fn get_packages(&self, name: &PackageName) -> Vec<&InstalledDist> {
+            self.0.iter().filter(|dist| dist.name() == name).collect()
+        }
+
+        fn get_editables(&self, _url: &Url) -> Vec<&InstalledDist> {
+            Vec::new()
+        }
+    }
+
+    fn installed(name: &str, version: &str) -> InstalledDist {
+        InstalledDist::Registry(InstalledRegistryDist {
+            name: PackageName::from_str(name).unwrap(),
+            version: Version::from_str(version).unwrap(),
+            path: PathBuf::from(format!("/site-packages/{name}-{version}.dist-info")),
+        })
+    }
+
+    #[test]
+    fn diff() {
+        let graph = resolution_graph(
+            vec![
+                registry_dist("anyio", "4.3.0", &["idna>=2.8", "sniffio>=1.1"]),
+                registry_dist("idna", "3.7", &[]),
+                registry_dist("sniffio", "1.3.1", &[]),
+                path_dist("./flask-3.0.3-py3-none-any.whl", &[]),
+                registry_dist("click", "8.1.7", &[]),
+            ],
+            &[(0, 1), (0, 2)],
+            &[0, 3, 4],
+        );
+        let installed = Installed(vec![
+            installed("anyio", "4.3.0"),
+            installed("idna", "3.6"),
+            installed("sniffio", "1.3.2"),
+            installed("flask", "3.0.3"),
+            installed("requests", "2.31.0"),
+        ]);
+
+        let diff = graph.diff(&installed);
+        let names = |dists: &[(InstalledDist, _)]| {
+            dists
+                .iter()
+                .map(|(dist, _)| dist.name().to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            diff.install
+                .iter()
+                .map(|dist| dist.name().to_string())
+                .collect::<Vec<_>>(),
+            vec!["click"]
+        );
+        assert_eq!(names(&diff.upgrade), vec!["idna"]);
+        assert_eq!(names(&diff.downgrade), vec!["sniffio"]);
+        // The installed `flask` came from a registry, but the resolution points to a local wheel.
+        assert_eq!(names(&diff.reinstall), vec!["flask"]);
+        assert_eq!(
+            diff.unchanged
+                .iter()
+                .map(|dist| dist.name().to_string())
+                .collect::<Vec<_>>(),
+            vec!["anyio"]
+        );
+        assert_eq!(
+            diff.remove
+                .iter()
+                .map(|dist| dist.name().to_string())
+                .collect::<Vec<_>>(),
+            vec!["requests"]
+        );
+        assert!(!diff.is_empty());
+    }
+
+    #[test]
+    fn requirements_txt_delta() {
+        let graph = resolution_graph(
+            vec![
+                registry_dist("anyio", "4.3.0", &["idna>=2.8", "sniffio>=1.1"]),
+                registry_dist("idna", "3.7", &[]),
+                registry_dist("sniffio", "1.3.1", &[]),
+                registry_dist("click", "8.1.7", &[]),
+            ],
+            &[(0, 1), (0, 2)],
+            &[0, 3],
+        );
+
+        // `anyio` and `sniffio` are already satisfied; `idna` needs an upgrade, and `click` isn't
+        // installed at all.
+        let installed = Installed(vec![
+            installed("anyio", "4.3.0"),
+            installed("idna", "3.6"),
+            installed("sniffio", "1.3.1"),
+            installed("requests", "2.31.0"),
+        ]);
+        assert_eq!(
+            graph.requirements_txt_delta(&installed, &RequirementsTxtOptions::default()),
+            "click==8.1.7\nidna==3.7\n"
+        );
+
+        // If everything is satisfied, nothing should be emitted.
+        let installed = Installed(vec![
+            installed("anyio", "4.3.0"),
+            installed("idna", "3.7"),
+            installed("sniffio", "1.3.1"),
+            installed("click", "8.1.7"),
+        ]);
+        assert_eq!(
+            graph.requirements_txt_delta(&installed, &RequirementsTxtOptions::default()),
+            ""
+        );
+    }
+
+    #[test]
+    fn pin_diff() {
+        let graph = resolution_graph(
+            vec![
+                registry_dist("anyio", "4.3.0", &["idna>=2.8", "sniffio>=1.1"]),
+                registry_dist("idna", "3.7", &[]),
+                registry_dist("sniffio", "1.3.1", &[]),
+                path_dist("./flask-3.0.3-py3-none-any.whl", &[]),
+            ],
+            &[(0, 1), (0, 2)],
+            &[0, 3],
+        );
+        let pin = |name: &str, version: &str| {
+            Preference::simple(
+                PackageName::from_str(name).unwrap(),
+                Version::from_str(version).unwrap(),
+            )
+        };
+        let pins = vec![
+            pin("anyio", "4.3.0"),
+            pin("idna", "3.6"),
+            pin("flask", "3.0.2"),
+            pin("requests", "2.31.0"),
+        ];
+
+        // Packages resolved from a path aren't compared, since they aren't pinned by version.
+        let version = |version: &str| Version::from_str(version).unwrap();
+        assert_eq!(
+            graph.pin_diff(&pins),
+            PinDiff {
+                added: vec![(PackageName::from_str("sniffio").unwrap(), version("1.3.1"))],
+                changed: vec![(
+                    PackageName::from_str("idna").unwrap(),
+                    version("3.6"),
+                    version("3.7")
+                )],
+                removed: vec![(
+                    PackageName::from_str("requests").unwrap(),
+                    version("2.31.0")
+                )],
+            }
+        );
+    }
+}
@@ -1,80 +1,395 @@
-use std::borrow::Cow;
 use std::collections::BTreeSet;
+use std::sync::Arc;
+use std::time::Duration;
 
+use itertools::Itertools;
 use owo_colors::OwoColorize;
+use petgraph::graph::NodeIndex;
 use petgraph::visit::EdgeRef;
 use petgraph::Direction;
+use rustc_hash::{FxHashMap, FxHashSet};
+use url::Url;
 
-use distribution_types::{IndexUrl, LocalEditable, Name, SourceAnnotations, Verbatim};
+use distribution_types::{
+    DistributionMetadata, IndexLocations, IndexUrl, LocalEditable, Name, SourceAnnotations,
+    Verbatim, VersionOrUrlRef,
+};
+use pep508_rs::VerbatimUrl;
 use pypi_types::HashDigest;
-use uv_normalize::PackageName;
+use uv_normalize::{ExtraName, PackageName};
 
+use crate::error::MissingMarkersError;
+use crate::resolution::tree::DependencyTree;
 use crate::resolution::AnnotatedDist;
 use crate::ResolutionGraph;
 
 /// A [`std::fmt::Display`] implementation for the resolution graph.
 #[derive(Debug)]
-#[allow(clippy::struct_excessive_bools)]
 pub struct DisplayResolutionGraph<'a> {
     /// The underlying graph.
     resolution: &'a ResolutionGraph,
-    /// The packages to exclude from the output.
-    no_emit_packages: &'a [PackageName],
-    /// Whether to include hashes in the output.
-    show_hashes: bool,
-    /// Whether to include extras in the output (e.g., `black[colorama]`).
-    include_extras: bool,
-    /// Whether to include annotations in the output, to indicate which dependency or dependencies
-    /// requested each package.
-    include_annotations: bool,
-    /// Whether to include indexes in the output, to indicate which index was used for each package.
-    include_index_annotation: bool,
-    /// The style of annotation comments, used to indicate the dependencies that requested each
-    /// package.
-    annotation_style: AnnotationStyle,
-    /// External sources for each package: requirements, constraints, and overrides.
-    sources: SourceAnnotations,
+    /// The options that control the output.
+    options: RequirementsTxtOptions<'a>,
 }
 
 impl<'a> From<&'a ResolutionGraph> for DisplayResolutionGraph<'a> {
     fn from(resolution: &'a ResolutionGraph) -> Self {
         Self::new(
             resolution,
-            &[],
-            false,
-            false,
-            true,
-            false,
-            AnnotationStyle::default(),
-            SourceAnnotations::default(),
+            RequirementsTxtOptions {
+                include_annotations: true,
+                ..RequirementsTxtOptions::default()
+            },
         )
     }
 }
 
 impl<'a> DisplayResolutionGraph<'a> {
     /// Create a new [`DisplayResolutionGraph`] for the given graph.
-    #[allow(clippy::fn_params_excessive_bools, clippy::too_many_arguments)]
     pub fn new(
         underlying: &'a ResolutionGraph,
-        no_emit_packages: &'a [PackageName],
-        show_hashes: bool,
-        include_extras: bool,
-        include_annotations: bool,
-        include_index_annotation: bool,
-        annotation_style: AnnotationStyle,
-        sources: SourceAnnotations,
+        options: RequirementsTxtOptions<'a>,
     ) -> DisplayResolutionGraph<'a> {
         Self {
             resolution: underlying,
-            no_emit_packages,
-            show_hashes,
-            include_extras,
-            include_annotations,
-            include_index_annotation,
-            annotation_style,
-            sources,
+            options,
+        }
+    }
+
+    /// Render a compact, single-line summary of the resolution (e.g., `Resolved 214 packages (198
+    /// wheels, 16 sdists) from 2 indexes in 1.30s`), as an alternative to the full listing.
+    ///
+    /// The graph doesn't track how long the resolution took, so the duration is only included if
+    /// provided.
+    pub fn summary_line(&self, elapsed: Option<Duration>) -> String {
+        let stats = self.resolution.stats();
+        let indexes = self
+            .resolution
+            .petgraph
+            .node_weights()
+            .filter_map(|dist| dist.dist.index())
+            .collect::<FxHashSet<_>>()
+            .len();
+        let mut line = format!(
+            "Resolved {} {} ({} {}, {} {}) from {} {}",
+            stats.packages,
+            if stats.packages == 1 {
+                "package"
+            } else {
+                "packages"
+            },
+            stats.wheels,
+            if stats.wheels == 1 { "wheel" } else { "wheels" },
+            stats.source_dists,
+            if stats.source_dists == 1 {
+                "sdist"
+            } else {
+                "sdists"
+            },
+            indexes,
+            if indexes == 1 { "index" } else { "indexes" },
+        );
+        if let Some(elapsed) = elapsed {
+            line.push_str(&format!(" in {}", uv_warnings::elapsed(elapsed)));
+        }
+        line
+    }
+
+    /// Return the pip directives (e.g., `--index-url`) for the index locations to include in the
+    /// output, such that the output can be installed without additional configuration.
+    ///
+    /// Duplicate locations are omitted, as are the index URLs when only the default PyPI index is
+    /// in use (unless explicitly requested).
+    fn index_directives(&self) -> Vec<String> {
+        let Some(index_locations) = self.options.index_locations else {
+            return Vec::new();
+        };
+
+        let mut directives = Vec::new();
+        let mut seen = BTreeSet::new();
+
+        if self.options.include_index_url {
+            let index = index_locations.index();
+            let extra_index = index_locations.extra_index().collect::<Vec<_>>();
+            let default_only = extra_index.is_empty() && matches!(index, Some(IndexUrl::Pypi(_)));
+            if self.options.include_default_index || !default_only {
+                if let Some(index) = index {
+                    seen.insert(index.verbatim().to_string());
+                    directives.push(format!("--index-url {}", index.verbatim()));
+                }
+                for extra_index in extra_index {
+                    if seen.insert(extra_index.verbatim().to_string()) {
+                        directives.push(format!("--extra-index-url {}", extra_index.verbatim()));
+                    }
+                }
+            }
+        }
+
+        if self.options.include_find_links {
+            for flat_index in index_locations.flat_index() {
+                if seen.insert(flat_index.to_string()) {
+                    directives.push(format!("--find-links {flat_index}"));
+                }
+            }
+        }
+
+        directives
+    }
+
+    /// Write the resolution as a dependency tree, rooted at the requested packages.
+    fn fmt_tree(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let petgraph = &self.resolution.petgraph;
+        let no_emit_packages = self.options.no_emit_packages;
+
+        let children = |index: NodeIndex| {
+            let mut children = petgraph
+                .neighbors_directed(index, Direction::Outgoing)
+                .filter(|child| !no_emit_packages.contains(petgraph[*child].name()))
+                .collect::<Vec<_>>();
+            children.sort_unstable_by_key(|child| petgraph[*child].name());
+            children.dedup();
+            children
+        };
+        let label = |index: NodeIndex, root: bool| {
+            let dist = &petgraph[index];
+            let mut label = format!("{}=={}", dist.name(), dist.metadata.version);
+            if !root && !dist.extras.is_empty() {
+                let extras = dist.extras.iter().sorted_unstable().dedup().join(", ");
+                label.push_str(&format!(" [extras: {extras}]"));
+            }
+            label
+        };
+
+        // Start from the requested packages, or from any packages without dependents if the roots
+        // are unknown.
+        let tree = if self.resolution.roots.is_empty() {
+            let mut nodes = petgraph
+                .node_indices()
+                .filter(|index| !no_emit_packages.contains(petgraph[*index].name()))
+                .collect::<Vec<_>>();
+            nodes.sort_unstable_by_key(|index| petgraph[*index].name());
+            DependencyTree::from_nodes(&nodes, children, label)
+        } else {
+            let mut roots = self
+                .resolution
+                .roots
+                .keys()
+                .copied()
+                .filter(|index| !no_emit_packages.contains(petgraph[*index].name()))
+                .collect::<Vec<_>>();
+            roots.sort_unstable_by_key(|index| petgraph[*index].name());
+            DependencyTree::new(roots, children, label)
+        };
+        write!(f, "{tree}")
+    }
+}
+
+/// Options for rendering a [`ResolutionGraph`] in the `requirements.txt` format, via
+/// [`ResolutionGraph::requirements_txt`] or [`DisplayResolutionGraph`].
+///
+/// By default, each package is written as a bare requirement, without extras, hashes,
+/// annotations, or index directives.
+#[derive(Debug, Clone)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct RequirementsTxtOptions<'a> {
+    /// The packages to exclude from the output.
+    pub no_emit_packages: &'a [PackageName],
+    /// Whether to include extras (e.g., `black[colorama]`).
+    pub include_extras: bool,
+    /// Whether to include hashes.
+    pub include_hashes: bool,
+    /// The style in which to render hashes, if included.
+    pub hash_style: HashStyle,
+    /// Whether to include annotations to indicate the dependencies that requested each package.
+    pub include_annotations: bool,
+    /// The style of annotation comments, if included.
+    pub annotation_style: AnnotationStyle,
+    /// Whether to include annotations to indicate which index was used for each package.
+    pub include_index_annotation: bool,
+    /// Whether to include annotations to indicate packages that were resolved to a yanked release
+    /// (e.g., `# yanked: security issue`).
+    pub include_yanked_annotation: bool,
+    /// External sources for each package (e.g., requirements files), included in the annotations.
+    pub sources: SourceAnnotations,
+    /// The index locations to write as a preamble (e.g., `--index-url`), if any.
+    pub index_locations: Option<&'a IndexLocations>,
+    /// Whether to include the `--index-url` and `--extra-index-url` locations in the preamble.
+    pub include_index_url: bool,
+    /// Whether to include the `--index-url` location in the preamble even if only the default
+    /// index (PyPI) is used, which is otherwise omitted.
+    pub include_default_index: bool,
+    /// Whether to include the `--find-links` locations in the preamble.
+    pub include_find_links: bool,
+    /// The extras to omit from the requirement for each package, if extras are included.
+    pub excluded_extras: FxHashMap<PackageName, Vec<ExtraName>>,
+    /// The inputs that produced the resolution (e.g., the lines of a `requirements.in`), if any.
+    ///
+    /// If provided, the output is prefixed with a `# This file was autogenerated by uv/<version>`
+    /// header, along with a stable hash of the inputs, such that drift between the inputs and
+    /// the exported file can be detected.
+    pub autogenerated_header: Option<&'a [String]>,
+    /// Whether to pin packages from a registry to the URL of the resolved artifact, rather than to
+    /// a version.
+    pub pin_artifact_urls: bool,
+    /// The URL to record for each artifact, if pinning to artifact URLs.
+    pub artifact_url_policy: ArtifactUrlPolicy,
+    /// Overrides for how URLs with a given scheme are written (e.g., `git+ssh://`).
+    pub url_renderers: UrlRenderers,
+    /// Whether to annotate packages that are only required under some markers with those
+    /// markers, as in a universal resolution.
+    pub include_markers: bool,
+    /// Whether to include only the top-level (i.e., directly requested) packages.
+    pub top_level_only: bool,
+    /// Whether to render the resolution as a dependency tree, rather than a flat list of
+    /// requirements.
+    pub tree: bool,
+    /// Whether to render each package as a bare `{name}=={version}` line, without extras, hashes,
+    /// annotations, or URLs.
+    pub name_version_only: bool,
+}
+
+impl Default for RequirementsTxtOptions<'_> {
+    fn default() -> Self {
+        Self {
+            no_emit_packages: &[],
+            include_extras: false,
+            include_hashes: false,
+            hash_style: HashStyle::default(),
+            include_annotations: false,
+            annotation_style: AnnotationStyle::default(),
+            include_index_annotation: false,
+            include_yanked_annotation: false,
+            sources: SourceAnnotations::default(),
+            index_locations: None,
+            include_index_url: true,
+            include_default_index: false,
+            include_find_links: true,
+            excluded_extras: FxHashMap::default(),
+            autogenerated_header: None,
+            pin_artifact_urls: false,
+            artifact_url_policy: ArtifactUrlPolicy::default(),
+            url_renderers: UrlRenderers::default(),
+            include_markers: false,
+            top_level_only: false,
+            tree: false,
+            name_version_only: false,
+        }
+    }
+}
+
+/// A [`ResolutionGraph`] exported as a pair of files for pip, via
+/// [`ResolutionGraph::requirements_and_constraints`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequirementsAndConstraints {
+    /// The root requirements, in the `requirements.txt` format.
+    pub requirements: String,
+    /// The pins for the transitive dependencies, in the `constraints.txt` format.
+    pub constraints: String,
+}
+
+impl ResolutionGraph {
+    /// Render the resolution in the `requirements.txt` format.
+    ///
+    /// For streaming output, or finer control over the output, use [`DisplayResolutionGraph`]
+    /// directly.
+    pub fn requirements_txt(&self, options: &RequirementsTxtOptions) -> String {
+        self.requirements_txt_excluding(options, &[])
+    }
+
+    /// Render a universal resolution in the `requirements.txt` format, failing if any package
+    /// would be written without the markers under which it's required.
+    ///
+    /// See [`ResolutionGraph::check_markers`]. Never fails if markers are included in the output
+    /// (see [`RequirementsTxtOptions::include_markers`]).
+    pub fn requirements_txt_strict(
+        &self,
+        options: &RequirementsTxtOptions,
+    ) -> Result<String, MissingMarkersError> {
+        if !options.include_markers {
+            self.check_markers()?;
+        }
+        Ok(self.requirements_txt(options))
+    }
+
+    /// Render the resolution as a pair of files for pip: the root requirements, to be installed
+    /// with `pip install -r`, and the pins for their transitive dependencies, to be passed with
+    /// `pip install -c`.
+    ///
+    /// Transitive dependencies that can't be expressed as pip constraints (i.e., editables and
+    /// direct URLs) are written to the requirements instead. The constraints are always written
+    /// without extras, and without index directives.
+    pub fn requirements_and_constraints(
+        &self,
+        options: &RequirementsTxtOptions,
+    ) -> RequirementsAndConstraints {
+        // Identify the roots. If the roots weren't recorded, treat every package without any
+        // dependents as a root.
+        let roots = if self.roots.is_empty() {
+            self.petgraph
+                .externals(Direction::Incoming)
+                .collect::<FxHashSet<_>>()
+        } else {
+            self.roots.keys().copied().collect::<FxHashSet<_>>()
+        };
+
+        // Partition the packages by name, such that a package is written to the requirements if
+        // any of its nodes must be.
+        let requirements = self
+            .petgraph
+            .node_indices()
+            .filter(|index| {
+                let dist = &self.petgraph[*index];
+                roots.contains(index)
+                    || self.editables.get(dist.name()).is_some()
+                    || matches!(dist.dist.version_or_url(), VersionOrUrlRef::Url(_))
+            })
+            .map(|index| self.petgraph[index].name().clone())
+            .collect::<FxHashSet<_>>();
+        let constraints = self
+            .petgraph
+            .node_indices()
+            .map(|index| self.petgraph[index].name())
+            .filter(|name| !requirements.contains(*name))
+            .cloned()
+            .collect::<FxHashSet<_>>();
+
+        let requirements = requirements.into_iter().collect::<Vec<_>>();
+        let constraints = constraints.into_iter().collect::<Vec<_>>();
+        RequirementsAndConstraints {
+            requirements: self.requirements_txt_excluding(options, &constraints),
+            constraints: DisplayResolutionGraph::new(
+                self,
+                RequirementsTxtOptions {
+                    no_emit_packages: &requirements,
+                    include_extras: false,
+                    index_locations: None,
+                    excluded_extras: FxHashMap::default(),
+                    autogenerated_header: None,
+                    pin_artifact_urls: false,
+                    top_level_only: false,
+                    tree: false,
+                    ..options.clone()
+                },
+            )
+            .to_string(),
         }
     }
+
+    /// Render the resolution in the `requirements.txt` format, omitting the given packages.
+    pub(crate) fn requirements_txt_excluding(
+        &self,
+        options: &RequirementsTxtOptions,
+        no_emit_packages: &[PackageName],
+    ) -> String {
+        DisplayResolutionGraph::new(
+            self,
+            RequirementsTxtOptions {
+                no_emit_packages,
+                ..options.clone()
+            },
+        )
+        .to_string()
+    }
 }
 
 #[derive(Debug)]
@@ -85,23 +400,7 @@ enum Node<'a> {
     Distribution(&'a AnnotatedDist),
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
-enum NodeKey<'a> {
-    /// A node linked to an editable distribution, sorted by verbatim representation.
-    Editable(Cow<'a, str>),
-    /// A node linked to a non-editable distribution, sorted by package name.
-    Distribution(&'a PackageName),
-}
-
 impl<'a> Node<'a> {
-    /// Return a comparable key for the node.
-    fn key(&self) -> NodeKey<'a> {
-        match self {
-            Node::Editable(editable) => NodeKey::Editable(editable.verbatim()),
-            Node::Distribution(annotated) => NodeKey::Distribution(annotated.name()),
-        }
-    }
-
     /// Return the [`IndexUrl`] of the distribution, if any.
     fn index(&self) -> Option<&IndexUrl> {
         match self {
@@ -122,15 +421,43 @@ impl<'a> Node<'a> {
 /// Write the graph in the `{name}=={version}` format of requirements.txt that pip uses.
 impl std::fmt::Display for DisplayResolutionGraph<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        // Collect all packages.
-        let mut nodes = self
+        if let Some(inputs) = self.options.autogenerated_header {
+            writeln!(
+                f,
+                "# This file was autogenerated by uv/{}",
+                uv_version::version()
+            )?;
+            writeln!(f, "# inputs-hash: {}", cache_key::digest(&inputs))?;
+        }
+
+        if self.options.tree {
+            return self.fmt_tree(f);
+        }
+
+        // Write the index locations, separated from the requirements by a blank line.
+        let directives = if self.options.name_version_only {
+            Vec::new()
+        } else {
+            self.index_directives()
+        };
+        if !directives.is_empty() {
+            for directive in directives {
+                writeln!(f, "{directive}")?;
+            }
+            writeln!(f)?;
+        }
+
+        // Collect all packages, sorted by name, but with editable packages first.
+        let nodes = self
             .resolution
-            .petgraph
-            .node_indices()
+            .display_order()
             .filter_map(|index| {
                 let dist = &self.resolution.petgraph[index];
                 let name = dist.name();
-                if self.no_emit_packages.contains(name) {
+                if self.options.no_emit_packages.contains(name) {
+                    return None;
+                }
+                if self.options.top_level_only && !self.resolution.roots.contains_key(&index) {
                     return None;
                 }
 
@@ -143,26 +470,97 @@ impl std::fmt::Display for DisplayResolutionGraph<'_> {
             })
             .collect::<Vec<_>>();
 
-        // Sort the nodes by name, but with editable packages first.
-        nodes.sort_unstable_by_key(|(index, node)| (node.key(), *index));
+        // In a universal resolution, determine the markers under which each package is required.
+        let markers = if self.options.include_markers {
+            self.resolution.conditional_markers()
+        } else {
+            FxHashMap::default()
+        };
 
         // Print out the dependency graph.
         for (index, node) in nodes {
+            // In the minimal format, write only the name and version. Packages that weren't
+            // resolved from an index (e.g., editables and direct URLs) can't be represented, so
+            // they're flagged in a comment instead.
+            if self.options.name_version_only {
+                let dist = &self.resolution.petgraph[index];
+                match (&node, dist.dist.version_or_url()) {
+                    (Node::Distribution(_), VersionOrUrlRef::Version(_)) => {
+                        writeln!(f, "{}=={}", dist.name(), dist.metadata.version)?;
+                    }
+                    _ => {
+                        writeln!(
+                            f,
+                            "# {}=={} (omitted: not resolved from an index)",
+                            dist.name(),
+                            dist.metadata.version
+                        )?;
+                    }
+                }
+                continue;
+            }
+
             // Display the node itself.
             let mut line = match node {
                 Node::Editable(editable) => format!("-e {}", editable.verbatim()),
                 Node::Distribution(dist) => {
-                    dist.to_requirements_txt(self.include_extras).to_string()
+                    let excluded_extras = self
+                        .options
+                        .excluded_extras
+                        .get(dist.name())
+                        .map_or(&[][..], Vec::as_slice);
+                    match dist
+                        .artifact_url()
+                        .filter(|_| self.options.pin_artifact_urls)
+                        .map(|url| self.options.artifact_url_policy.resolve(url))
+                    {
+                        // Ex) `flask @ https://files.pythonhosted.org/.../flask-3.0.3-py3-none-any.whl`
+                        Some(url) if self.options.include_extras => {
+                            let extras = dist
+                                .extras
+                                .iter()
+                                .filter(|extra| !excluded_extras.contains(extra))
+                                .sorted_unstable()
+                                .dedup()
+                                .join(", ");
+                            if extras.is_empty() {
+                                format!("{} @ {url}", dist.name())
+                            } else {
+                                format!("{}[{extras}] @ {url}", dist.name())
+                            }
+                        }
+                        Some(url) => format!("{} @ {url}", dist.name()),
+                        None => dist
+                            .to_requirements_txt(
+                                self.options.include_extras,
+                                excluded_extras,
+                                &self.options.url_renderers,
+                            )
+                            .to_string(),
+                    }
                 }
             };
 
+            // Display the markers, if the package is only required under some markers. Editables
+            // can't be annotated with markers.
+            if let (Node::Distribution(_), Some(marker)) = (&node, markers.get(&index)) {
+                line.push_str(&format!(" ; {marker}"));
+            }
+
             // Display the distribution hashes, if any.
             let mut has_hashes = false;
-            if self.show_hashes {
+            if self.options.include_hashes {
                 for hash in node.hashes() {
-                    has_hashes = true;
-                    line.push_str(" \\\n");
-                    line.push_str("    --hash=");
+                    match self.options.hash_style {
+                        HashStyle::Line => {
+                            line.push_str(" --hash=");
+                        }
+                        HashStyle::Split => {
+                            has_hashes = true;
+                            line.push_str(" \\\n");
+                            line.push_str("    --hash=");
+                        }
+                    }
                     line.push_str(&hash.to_string());
                 }
             }
@@ -172,7 +570,7 @@ impl std::fmt::Display for DisplayResolutionGraph<'_> {
 
             // If enabled, include annotations to indicate the dependencies that requested each
             // package (e.g., `# via mypy`).
-            if self.include_annotations {
+            if self.options.include_annotations {
                 // Display all dependencies.
                 let mut edges = self
                     .resolution
@@ -185,13 +583,17 @@ impl std::fmt::Display for DisplayResolutionGraph<'_> {
                 // Include all external sources (e.g., requirements files).
                 let default = BTreeSet::default();
                 let source = match node {
-                    Node::Editable(editable) => {
-                        self.sources.get_editable(&editable.url).unwrap_or(&default)
+                    Node::Editable(editable) => self
+                        .options
+                        .sources
+                        .get_editable(&editable.url)
+                        .unwrap_or(&default),
+                    Node::Distribution(dist) => {
+                        self.options.sources.get(dist.name()).unwrap_or(&default)
                     }
-                    Node::Distribution(dist) => self.sources.get(dist.name()).unwrap_or(&default),
                 };
 
-                match self.annotation_style {
+                match self.options.annotation_style {
                     AnnotationStyle::Line => match edges.as_slice() {
                         [] if source.is_empty() => {}
                         [] if source.len() == 1 => {
@@ -260,12 +662,26 @@ impl std::fmt::Display for DisplayResolutionGraph<'_> {
 
             // If enabled, include indexes to indicate which index was used for each package (e.g.,
             // `# from https://pypi.org/simple`).
-            if self.include_index_annotation {
+            if self.options.include_index_annotation {
                 if let Some(index) = node.index() {
                     let url = index.redacted();
                     writeln!(f, "{}", format!("    # from {url}").green())?;
                 }
             }
+
+            // If enabled, flag packages that were resolved to a yanked release (e.g.,
+            // `# yanked: security issue`).
+            if self.options.include_yanked_annotation {
+                if let Node::Distribution(dist) = node {
+                    if dist.is_yanked() {
+                        let comment = match dist.yanked_reason() {
+                            Some(reason) => format!("    # yanked: {reason}"),
+                            None => "    # yanked".to_string(),
+                        };
+                        writeln!(f, "{}", comment.yellow())?;
+                    }
+                }
+            }
         }
 
         Ok(())
@@ -285,3 +701,632 @@ pub enum AnnotationStyle {
     #[default]
     Split,
 }
+
+/// The URL to record for each artifact, when pinning packages to artifact URLs.
+#[derive(Debug, Default, Clone)]
+pub enum ArtifactUrlPolicy {
+    /// Record the URL advertised by the index, which is typically stable.
+    #[default]
+    Original,
+    /// Record the URL from which each artifact was ultimately downloaded (e.g., a signed object
+    /// storage URL, after following redirects), keyed by the URL advertised by the index.
+    ///
+    /// Artifacts without a known final URL fall back to the URL advertised by the index.
+    Final(FxHashMap<Url, Url>),
+}
+
+impl ArtifactUrlPolicy {
+    /// Returns the URL to record for the artifact advertised at the given URL.
+    fn resolve(&self, url: Url) -> Url {
+        match self {
+            Self::Original => url,
+            Self::Final(final_urls) => final_urls.get(&url).cloned().unwrap_or(url),
+        }
+    }
+}
+
+/// A function that renders a URL for inclusion in a requirement (e.g., as `flask @ <url>`).
+type RenderUrl = dyn Fn(&VerbatimUrl) -> String + Send + Sync;
+
+/// Overrides for how URLs are written in `requirements.txt` output, keyed by URL scheme (e.g.,
+/// `git+ssh`), for consumers that expect a different spelling than the one given by the user.
+///
+/// URLs with a scheme that isn't overridden are written verbatim. Relative paths, which are
+/// written as unnamed requirements, are never overridden.
+#[derive(Default, Clone)]
+pub struct UrlRenderers(FxHashMap<String, Arc<RenderUrl>>);
+
+impl UrlRenderers {
+    /// Render URLs with the given scheme (e.g., `git+ssh`) using the given function.
+    #[must_use]
+    pub fn with_scheme(
+        mut self,
+        scheme: impl Into<String>,
+        render: impl Fn(&VerbatimUrl) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.0.insert(scheme.into(), Arc::new(render));
+        self
+    }
+
+    /// Render the given URL, if its scheme is overridden.
+    pub(crate) fn render(&self, url: &VerbatimUrl) -> Option<String> {
+        let render = self.0.get(url.scheme())?;
+        Some(render(url))
+    }
+}
+
+impl std::fmt::Debug for UrlRenderers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.0.keys()).finish()
+    }
+}
+
+/// Indicate the style in which to render hashes.
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub enum HashStyle {
+    /// Render the hashes on the same line as the requirement.
+    Line,
+    /// Render each hash on its own line, using `\` line continuations (as supported by pip).
+    #[default]
+    Split,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use std::time::Duration;
+
+    use distribution_types::{
+        BuiltDist, Dist, FlatIndexLocation, IndexLocations, IndexUrl, ResolvedDist, Verbatim,
+    };
+    use pypi_types::Yanked;
+    use rustc_hash::FxHashMap;
+    use url::Url;
+
+    use crate::resolution::tests::{git_dist, path_dist, registry_dist, resolution_graph};
+    use crate::{
+        ArtifactUrlPolicy, DisplayResolutionGraph, HashStyle, RequirementsTxtOptions, UrlRenderers,
+    };
+
+    fn display(index_locations: &IndexLocations) -> String {
+        let graph = resolution_graph(vec![registry_dist("anyio", "4.3.0", &[])], &[], &[0]);
+        DisplayResolutionGraph::new(
+            &graph,
+            RequirementsTxtOptions {
+                index_locations: Some(index_locations),
+                ..RequirementsTxtOptions::default()
+            },
+        )
+        .to_string()
+    }
+
+    #[test]
+    fn index_directives() {
+        let index_locations = IndexLocations::new(
+            Some(IndexUrl::from_str("https://test.pypi.org/simple").unwrap()),
+            vec![
+                IndexUrl::from_str("https://pypi.org/simple").unwrap(),
+                IndexUrl::from_str("https://test.pypi.org/simple").unwrap(),
+                IndexUrl::from_str("https://pypi.org/simple").unwrap(),
+            ],
+            vec![
+                FlatIndexLocation::from_str("https://download.pytorch.org/whl/torch_stable.html")
+                    .unwrap(),
+            ],
+            false,
+        );
+        assert_eq!(
+            display(&index_locations),
+            "--index-url https://test.pypi.org/simple\n\
+             --extra-index-url https://pypi.org/simple\n\
+             --find-links https://download.pytorch.org/whl/torch_stable.html\n\
+             \n\
+             anyio==4.3.0\n"
+        );
+    }
+
+    #[test]
+    fn index_directives_default_index() {
+        let index_locations = IndexLocations::default();
+        assert_eq!(display(&index_locations), "anyio==4.3.0\n");
+
+        // Unless explicitly requested, e.g., via `--emit-index-url`.
+        let graph = resolution_graph(vec![registry_dist("anyio", "4.3.0", &[])], &[], &[0]);
+        let display = DisplayResolutionGraph::new(
+            &graph,
+            RequirementsTxtOptions {
+                index_locations: Some(&index_locations),
+                include_default_index: true,
+                ..RequirementsTxtOptions::default()
+            },
+        );
+        assert_eq!(
+            display.to_string(),
+            "--index-url https://pypi.org/simple\n\nanyio==4.3.0\n"
+        );
+    }
+
+    #[test]
+    fn top_level_only() {
+        let graph = resolution_graph(
+            vec![
+                registry_dist("anyio", "4.3.0", &["idna>=2.8", "sniffio>=1.1"]),
+                registry_dist("idna", "3.6", &[]),
+                registry_dist("sniffio", "1.3.1", &[]),
+                registry_dist("flask", "3.0.3", &[]),
+            ],
+            &[(0, 1), (0, 2)],
+            &[0, 3],
+        );
+        let display = DisplayResolutionGraph::new(
+            &graph,
+            RequirementsTxtOptions {
+                top_level_only: true,
+                ..RequirementsTxtOptions::default()
+            },
+        );
+        assert_eq!(display.to_string(), "anyio==4.3.0\nflask==3.0.3\n");
+    }
+
+    #[test]
+    fn hash_style() {
+        let mut anyio = registry_dist("anyio", "4.3.0", &["sniffio>=1.1"]);
+        anyio.hashes = vec![
+            "sha256:048e05d0f6caeed70d731f3db756d35dcc1f35747c8c403364a8332c630441b8"
+                .parse()
+                .unwrap(),
+            "sha256:f75253795a87df48568485fd18cdd2a3fa5c4f7c5be8e5e36637733fce06fed6"
+                .parse()
+                .unwrap(),
+        ];
+        let graph = resolution_graph(
+            vec![anyio, registry_dist("sniffio", "1.3.1", &[])],
+            &[(0, 1)],
+            &[0],
+        );
+
+        let mut display = DisplayResolutionGraph::from(&graph);
+        display.options.include_hashes = true;
+        display.options.include_annotations = false;
+
+        // By default, each hash is written on its own line.
+        insta::assert_snapshot!(display.to_string(), @r###"
+        anyio==4.3.0 \
+            --hash=sha256:048e05d0f6caeed70d731f3db756d35dcc1f35747c8c403364a8332c630441b8 \
+            --hash=sha256:f75253795a87df48568485fd18cdd2a3fa5c4f7c5be8e5e36637733fce06fed6
+        sniffio==1.3.1
+        "###);
+
+        display.options.hash_style = HashStyle::Line;
+        insta::assert_snapshot!(display.to_string(), @r###"
+        anyio==4.3.0 --hash=sha256:048e05d0f6caeed70d731f3db756d35dcc1f35747c8c403364a8332c630441b8 --hash=sha256:f75253795a87df48568485fd18cdd2a3fa5c4f7c5be8e5e36637733fce06fed6
+        sniffio==1.3.1
+        "###);
+    }
+
+    #[test]
+    fn tree() {
+        let mut sniffio = registry_dist("sniffio", "1.3.1", &[]);
+        sniffio.extras = vec!["test".parse().unwrap()];
+        let graph = resolution_graph(
+            vec![
+                registry_dist("anyio", "4.3.0", &["idna>=2.8", "sniffio>=1.1"]),
+                registry_dist("idna", "3.7", &[]),
+                sniffio,
+                registry_dist("trio", "0.25.0", &["anyio", "idna", "sniffio"]),
+            ],
+            &[(0, 1), (0, 2), (3, 0), (3, 1), (3, 2), (2, 3)],
+            &[0, 3],
+        );
+
+        let mut display = DisplayResolutionGraph::from(&graph);
+        display.options.tree = true;
+        assert_eq!(
+            display.to_string(),
+            "anyio==4.3.0\n\
+             ├── idna==3.7\n\
+             └── sniffio==1.3.1 [extras: test]\n\
+             \x20   └── trio==0.25.0\n\
+             \x20       ├── anyio==4.3.0 (*)\n\
+             \x20       ├── idna==3.7\n\
+             \x20       └── sniffio==1.3.1 [extras: test] (*)\n\
+             trio==0.25.0 (*)\n"
+        );
+    }
+
+    #[test]
+    fn name_version_only() {
+        let mut anyio = registry_dist("anyio", "4.3.0", &["sniffio>=1.1"]);
+        anyio.extras = vec!["trio".parse().unwrap()];
+        let graph = resolution_graph(
+            vec![
+                anyio,
+                registry_dist("sniffio", "1.3.1", &[]),
+                path_dist("./flask-3.0.3-py3-none-any.whl", &[]),
+            ],
+            &[(0, 1)],
+            &[0, 2],
+        );
+
+        let mut display = DisplayResolutionGraph::from(&graph);
+        display.options.include_extras = true;
+        display.options.name_version_only = true;
+        assert_eq!(
+            display.to_string(),
+            "anyio==4.3.0\n\
+             # flask==3.0.3 (omitted: not resolved from an index)\n\
+             sniffio==1.3.1\n"
+        );
+    }
+
+    #[test]
+    fn pin_artifact_urls() {
+        let mut anyio = registry_dist("anyio", "4.3.0", &["sniffio>=1.1"]);
+        anyio.hashes = vec![
+            "sha256:048e05d0f6caeed70d731f3db756d35dcc1f35747c8c403364a8332c630441b8"
+                .parse()
+                .unwrap(),
+        ];
+        let graph = resolution_graph(
+            vec![
+                anyio,
+                registry_dist("sniffio", "1.3.1", &[]),
+                path_dist("./flask-3.0.3-py3-none-any.whl", &[]),
+            ],
+            &[(0, 1)],
+            &[0, 2],
+        );
+
+        // Registry packages should be pinned to the URL of the resolved wheel, with their hashes;
+        // packages without a registry URL should be unaffected.
+        let mut display = DisplayResolutionGraph::from(&graph);
+        display.options.include_annotations = false;
+        display.options.include_hashes = true;
+        display.options.hash_style = HashStyle::Line;
+        display.options.pin_artifact_urls = true;
+        assert_eq!(
+            display.to_string(),
+            "anyio @ https://files.pythonhosted.org/packages/anyio-4.3.0-py3-none-any.whl \
+             --hash=sha256:048e05d0f6caeed70d731f3db756d35dcc1f35747c8c403364a8332c630441b8\n\
+             ./flask-3.0.3-py3-none-any.whl\n\
+             sniffio @ https://files.pythonhosted.org/packages/sniffio-1.3.1-py3-none-any.whl\n"
+        );
+    }
+
+    #[test]
+    fn yanked_annotation() {
+        let mut anyio = registry_dist("anyio", "4.3.0", &["sniffio>=1.1"]);
+        let ResolvedDist::Installable(Dist::Built(BuiltDist::Registry(wheels))) = &mut anyio.dist
+        else {
+            unreachable!()
+        };
+        wheels.wheels[0].file.yanked = Some(Yanked::Reason("broken metadata".to_string()));
+        assert!(anyio.is_yanked());
+        assert_eq!(anyio.yanked_reason(), Some("broken metadata"));
+
+        let graph = resolution_graph(
+            vec![anyio, registry_dist("sniffio", "1.3.1", &[])],
+            &[(0, 1)],
+            &[0],
+        );
+
+        // Yanked releases should only be annotated when enabled.
+        let mut display = DisplayResolutionGraph::from(&graph);
+        display.options.include_annotations = false;
+        assert_eq!(display.to_string(), "anyio==4.3.0\nsniffio==1.3.1\n");
+
+        display.options.include_yanked_annotation = true;
+        let output = display.to_string();
+        let lines = output.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "anyio==4.3.0");
+        assert!(lines[1].contains("    # yanked: broken metadata"));
+        assert_eq!(lines[2], "sniffio==1.3.1");
+    }
+
+    #[test]
+    fn summary_line() {
+        let graph = resolution_graph(
+            vec![
+                registry_dist("anyio", "4.3.0", &["sniffio>=1.1"]),
+                registry_dist("sniffio", "1.3.1", &[]),
+                path_dist("./flask-3.0.3-py3-none-any.whl", &[]),
+            ],
+            &[(0, 1)],
+            &[0, 2],
+        );
+
+        let display = DisplayResolutionGraph::from(&graph);
+        assert_eq!(
+            display.summary_line(None),
+            "Resolved 3 packages (3 wheels, 0 sdists) from 1 index"
+        );
+        assert_eq!(
+            display.summary_line(Some(Duration::from_millis(1300))),
+            "Resolved 3 packages (3 wheels, 0 sdists) from 1 index in 1.30s"
+        );
+    }
+
+    #[test]
+    fn requirements_txt() {
+        let mut anyio = registry_dist("anyio", "4.3.0", &["sniffio>=1.1"]);
+        anyio.extras = vec!["trio".parse().unwrap()];
+        anyio.hashes = vec![
+            "sha256:048e05d0f6caeed70d731f3db756d35dcc1f35747c8c403364a8332c630441b8"
+                .parse()
+                .unwrap(),
+        ];
+        let graph = resolution_graph(
+            vec![anyio, registry_dist("sniffio", "1.3.1", &[])],
+            &[(0, 1)],
+            &[0],
+        );
+
+        // By default, write bare requirements.
+        assert_eq!(
+            graph.requirements_txt(&RequirementsTxtOptions::default()),
+            "anyio==4.3.0\nsniffio==1.3.1\n"
+        );
+
+        // Include extras and hashes, along with the index preamble.
+        let index_locations = IndexLocations::new(
+            Some(IndexUrl::from_str("https://test.pypi.org/simple").unwrap()),
+            vec![],
+            vec![],
+            false,
+        );
+        assert_eq!(
+            graph.requirements_txt(&RequirementsTxtOptions {
+                include_extras: true,
+                include_hashes: true,
+                hash_style: HashStyle::Line,
+                index_locations: Some(&index_locations),
+                ..RequirementsTxtOptions::default()
+            }),
+            "--index-url https://test.pypi.org/simple\n\
+             \n\
+             anyio[trio]==4.3.0 --hash=sha256:048e05d0f6caeed70d731f3db756d35dcc1f35747c8c403364a8332c630441b8\n\
+             sniffio==1.3.1\n"
+        );
+    }
+
+    #[test]
+    fn excluded_extras() {
+        let mut anyio = registry_dist("anyio", "4.3.0", &["sniffio>=1.1", "trio>=0.23"]);
+        anyio.extras = vec!["trio".parse().unwrap(), "test".parse().unwrap()];
+        let graph = resolution_graph(
+            vec![
+                anyio,
+                registry_dist("sniffio", "1.3.1", &[]),
+                registry_dist("trio", "0.25.0", &[]),
+            ],
+            &[(0, 1), (0, 2)],
+            &[0],
+        );
+
+        // Strip the `trio` extra from `anyio`, but keep its dependencies.
+        let mut excluded_extras = FxHashMap::default();
+        excluded_extras.insert("anyio".parse().unwrap(), vec!["trio".parse().unwrap()]);
+        assert_eq!(
+            graph.requirements_txt(&RequirementsTxtOptions {
+                include_extras: true,
+                excluded_extras,
+                ..RequirementsTxtOptions::default()
+            }),
+            "anyio[test]==4.3.0\nsniffio==1.3.1\ntrio==0.25.0\n"
+        );
+    }
+
+    #[test]
+    fn requirements_and_constraints() {
+        let mut anyio = registry_dist("anyio", "4.3.0", &["idna>=2.8", "sniffio>=1.1"]);
+        anyio.extras = vec!["trio".parse().unwrap()];
+        let mut sniffio = registry_dist("sniffio", "1.3.1", &[]);
+        sniffio.extras = vec!["test".parse().unwrap()];
+        let graph = resolution_graph(
+            vec![
+                anyio,
+                registry_dist("idna", "3.7", &[]),
+                sniffio,
+                path_dist("./flask-3.0.3-py3-none-any.whl", &[]),
+            ],
+            &[(0, 1), (0, 2), (0, 3)],
+            &[0],
+        );
+        let exported = graph.requirements_and_constraints(&RequirementsTxtOptions {
+            include_extras: true,
+            ..RequirementsTxtOptions::default()
+        });
+
+        // Transitive dependencies should be written as constraints, without extras, unless they
+        // can't be expressed as constraints (e.g., direct URLs).
+        assert_eq!(
+            exported.requirements,
+            "anyio[trio]==4.3.0\n./flask-3.0.3-py3-none-any.whl\n"
+        );
+        assert_eq!(exported.constraints, "idna==3.7\nsniffio==1.3.1\n");
+    }
+
+    #[test]
+    fn autogenerated_header() {
+        let graph = resolution_graph(vec![registry_dist("anyio", "4.3.0", &[])], &[], &[0]);
+        let inputs = vec!["anyio>=4".to_string()];
+        let header = graph.requirements_txt(&RequirementsTxtOptions {
+            autogenerated_header: Some(&inputs),
+            ..RequirementsTxtOptions::default()
+        });
+        assert_eq!(
+            header,
+            format!(
+                "# This file was autogenerated by uv/{}\n\
+                 # inputs-hash: {}\n\
+                 anyio==4.3.0\n",
+                uv_version::version(),
+                cache_key::digest(&inputs.as_slice())
+            )
+        );
+
+        // The hash should be stable, and change with the inputs.
+        assert_eq!(
+            graph.requirements_txt(&RequirementsTxtOptions {
+                autogenerated_header: Some(&inputs),
+                ..RequirementsTxtOptions::default()
+            }),
+            header
+        );
+        let inputs = vec!["anyio>=4.1".to_string()];
+        assert_ne!(
+            graph.requirements_txt(&RequirementsTxtOptions {
+                autogenerated_header: Some(&inputs),
+                ..RequirementsTxtOptions::default()
+            }),
+            header
+        );
+    }
+
+    #[test]
+    fn requirements_txt_strict() {
+        let graph = resolution_graph(
+            vec![
+                registry_dist("anyio", "4.3.0", &["sniffio>=1.1"]),
+                registry_dist("sniffio", "1.3.1", &[]),
+            ],
+            &[(0, 1)],
+            &[0],
+        );
+        assert_eq!(
+            graph
+                .requirements_txt_strict(&RequirementsTxtOptions::default())
+                .unwrap(),
+            "anyio==4.3.0\nsniffio==1.3.1\n"
+        );
+
+        let graph = resolution_graph(
+            vec![
+                registry_dist(
+                    "click",
+                    "8.1.7",
+                    &["colorama; platform_system == 'Windows'"],
+                ),
+                registry_dist("colorama", "0.4.6", &[]),
+            ],
+            &[(0, 1)],
+            &[0],
+        );
+        let err = graph
+            .requirements_txt_strict(&RequirementsTxtOptions::default())
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "The resolution contains packages that are only required under some markers, but would be written without them:\n\
+             - colorama==0.4.6 is only required if `platform_system == 'Windows'`"
+        );
+    }
+
+    #[test]
+    fn include_markers() {
+        let graph = resolution_graph(
+            vec![
+                registry_dist(
+                    "click",
+                    "8.1.7",
+                    &["colorama; platform_system == 'Windows'"],
+                ),
+                registry_dist("colorama", "0.4.6", &[]),
+            ],
+            &[(0, 1)],
+            &[0],
+        );
+        let options = RequirementsTxtOptions {
+            include_markers: true,
+            ..RequirementsTxtOptions::default()
+        };
+        let expected = "click==8.1.7\ncolorama==0.4.6 ; platform_system == 'Windows'\n";
+        assert_eq!(graph.requirements_txt(&options), expected);
+        assert_eq!(graph.requirements_txt_strict(&options).unwrap(), expected);
+    }
+
+    #[test]
+    fn artifact_url_policy() {
+        let graph = resolution_graph(
+            vec![
+                registry_dist("anyio", "4.3.0", &["sniffio>=1.1"]),
+                registry_dist("sniffio", "1.3.1", &[]),
+            ],
+            &[(0, 1)],
+            &[0],
+        );
+
+        // By default, record the URL advertised by the index.
+        let options = RequirementsTxtOptions {
+            pin_artifact_urls: true,
+            ..RequirementsTxtOptions::default()
+        };
+        assert_eq!(
+            graph.requirements_txt(&options),
+            "anyio @ https://files.pythonhosted.org/packages/anyio-4.3.0-py3-none-any.whl\n\
+             sniffio @ https://files.pythonhosted.org/packages/sniffio-1.3.1-py3-none-any.whl\n"
+        );
+
+        // Otherwise, record the final URL, where known.
+        let mut final_urls = FxHashMap::default();
+        final_urls.insert(
+            Url::parse("https://files.pythonhosted.org/packages/anyio-4.3.0-py3-none-any.whl")
+                .unwrap(),
+            Url::parse("https://storage.example.com/anyio-4.3.0-py3-none-any.whl?signature=abc")
+                .unwrap(),
+        );
+        let options = RequirementsTxtOptions {
+            pin_artifact_urls: true,
+            artifact_url_policy: ArtifactUrlPolicy::Final(final_urls),
+            ..RequirementsTxtOptions::default()
+        };
+        assert_eq!(
+            graph.requirements_txt(&options),
+            "anyio @ https://storage.example.com/anyio-4.3.0-py3-none-any.whl?signature=abc\n\
+             sniffio @ https://files.pythonhosted.org/packages/sniffio-1.3.1-py3-none-any.whl\n"
+        );
+    }
+
+    #[test]
+    fn url_renderers() {
+        let https = resolution_graph(
+            vec![git_dist("git+https://github.com/pallets/flask.git@3.0.3")],
+            &[],
+            &[0],
+        );
+        let ssh = resolution_graph(
+            vec![git_dist("git+ssh://git@github.com/pallets/flask.git@3.0.3")],
+            &[],
+            &[0],
+        );
+
+        // By default, Git URLs are written verbatim.
+        let options = RequirementsTxtOptions::default();
+        assert_eq!(
+            https.requirements_txt(&options),
+            "flask @ git+https://github.com/pallets/flask.git@3.0.3\n"
+        );
+        assert_eq!(
+            ssh.requirements_txt(&options),
+            "flask @ git+ssh://git@github.com/pallets/flask.git@3.0.3\n"
+        );
+
+        // Overriding a scheme only affects URLs with that scheme.
+        let options = RequirementsTxtOptions {
+            url_renderers: UrlRenderers::default().with_scheme("git+ssh", |url| {
+                url.verbatim()
+                    .replacen("git+ssh://git@github.com/", "git+https://github.com/", 1)
+            }),
+            ..RequirementsTxtOptions::default()
+        };
+        assert_eq!(
+            https.requirements_txt(&options),
+            "flask @ git+https://github.com/pallets/flask.git@3.0.3\n"
+        );
+        assert_eq!(
+            ssh.requirements_txt(&options),
+            "flask @ git+https://github.com/pallets/flask.git@3.0.3\n"
+        );
+    }
+}
@@ -0,0 +1,169 @@
+use std::io::Write;
+
+use itertools::Itertools;
+use petgraph::Direction;
+
+use distribution_types::Name;
+use uv_normalize::PackageName;
+
+use crate::resolution::graph::edge_marker;
+use crate::ResolutionGraph;
+
+impl ResolutionGraph {
+    /// Write the resolution to the given writer as a Graphviz DOT graph, with one node per pinned
+    /// package and one edge per dependency, sorted by package name.
+    ///
+    /// Dependencies that only apply under some markers are labeled with those markers. The given
+    /// packages are omitted, along with any edges to or from them.
+    pub fn write_dot(
+        &self,
+        mut writer: impl Write,
+        no_emit_packages: &[PackageName],
+    ) -> std::io::Result<()> {
+        writeln!(writer, "digraph resolution {{")?;
+        let indices = self
+            .sorted_indices()
+            .filter(|index| !no_emit_packages.contains(self.petgraph[*index].name()))
+            .collect::<Vec<_>>();
+        for index in &indices {
+            let dist = &self.petgraph[*index];
+            writeln!(
+                writer,
+                "    {} [label={}];",
+                quote(dist.name()),
+                quote(format!("{}=={}", dist.name(), dist.metadata.version))
+            )?;
+        }
+        for index in indices {
+            let source = &self.petgraph[index];
+            for target in self
+                .petgraph
+                .neighbors_directed(index, Direction::Outgoing)
+                .map(|target| &self.petgraph[target])
+                .filter(|target| !no_emit_packages.contains(target.name()))
+                .sorted_unstable_by(|a, b| a.name().cmp(b.name()))
+                .dedup_by(|a, b| a.name() == b.name())
+            {
+                match edge_marker(source, target.name()) {
+                    Some(marker) => writeln!(
+                        writer,
+                        "    {} -> {} [label={}];",
+                        quote(source.name()),
+                        quote(target.name()),
+                        quote(marker)
+                    )?,
+                    None => writeln!(
+                        writer,
+                        "    {} -> {};",
+                        quote(source.name()),
+                        quote(target.name())
+                    )?,
+                }
+            }
+        }
+        writeln!(writer, "}}")?;
+        Ok(())
+    }
+}
+
+/// Format the given value as a quoted DOT identifier.
+fn quote(value: impl std::fmt::Display) -> String {
+    format!(
+        "\"{}\"",
+        value.to_string().replace('\\', "\\\\").replace('"', "\\\"")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use uv_normalize::PackageName;
+
+    use crate::resolution::tests::{registry_dist, resolution_graph};
+
+    #[test]
+    fn dot() {
+        let graph = resolution_graph(
+            vec![
+                registry_dist("sniffio", "1.3.1", &[]),
+                registry_dist("anyio", "4.3.0", &["idna>=2.8", "sniffio>=1.1"]),
+                registry_dist("idna", "3.7", &[]),
+            ],
+            &[(1, 2), (1, 0)],
+            &[1],
+        );
+
+        let mut dot = Vec::new();
+        graph.write_dot(&mut dot, &[]).unwrap();
+        assert_eq!(
+            String::from_utf8(dot).unwrap(),
+            concat!(
+                "digraph resolution {\n",
+                "    \"anyio\" [label=\"anyio==4.3.0\"];\n",
+                "    \"idna\" [label=\"idna==3.7\"];\n",
+                "    \"sniffio\" [label=\"sniffio==1.3.1\"];\n",
+                "    \"anyio\" -> \"idna\";\n",
+                "    \"anyio\" -> \"sniffio\";\n",
+                "}\n",
+            )
+        );
+    }
+
+    #[test]
+    fn dot_markers() {
+        let graph = resolution_graph(
+            vec![
+                registry_dist(
+                    "click",
+                    "8.1.7",
+                    &["colorama; platform_system == 'Windows'"],
+                ),
+                registry_dist("colorama", "0.4.6", &[]),
+            ],
+            &[(0, 1)],
+            &[0],
+        );
+
+        let mut dot = Vec::new();
+        graph.write_dot(&mut dot, &[]).unwrap();
+        assert_eq!(
+            String::from_utf8(dot).unwrap(),
+            concat!(
+                "digraph resolution {\n",
+                "    \"click\" [label=\"click==8.1.7\"];\n",
+                "    \"colorama\" [label=\"colorama==0.4.6\"];\n",
+                "    \"click\" -> \"colorama\" [label=\"platform_system == 'Windows'\"];\n",
+                "}\n",
+            )
+        );
+    }
+
+    #[test]
+    fn dot_no_emit_packages() {
+        let graph = resolution_graph(
+            vec![
+                registry_dist("sniffio", "1.3.1", &[]),
+                registry_dist("anyio", "4.3.0", &["idna>=2.8", "sniffio>=1.1"]),
+                registry_dist("idna", "3.7", &[]),
+            ],
+            &[(1, 2), (1, 0)],
+            &[1],
+        );
+
+        let mut dot = Vec::new();
+        graph
+            .write_dot(&mut dot, &[PackageName::from_str("sniffio").unwrap()])
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(dot).unwrap(),
+            concat!(
+                "digraph resolution {\n",
+                "    \"anyio\" [label=\"anyio==4.3.0\"];\n",
+                "    \"idna\" [label=\"idna==3.7\"];\n",
+                "    \"anyio\" -> \"idna\";\n",
+                "}\n",
+            )
+        );
+    }
+}
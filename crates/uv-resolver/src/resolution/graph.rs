@@ -1,47 +1,121 @@
+use std::borrow::Cow;
+use std::collections::hash_map::Entry;
+use std::collections::VecDeque;
 use std::hash::BuildHasherDefault;
 use std::sync::Arc;
 
+use itertools::Itertools;
+use petgraph::graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+use petgraph::Direction;
 use pubgrub::range::Range;
 use pubgrub::solver::{Kind, State};
 use pubgrub::type_aliases::SelectedDependencies;
 use rustc_hash::{FxHashMap, FxHashSet};
 
 use distribution_types::{
-    Dist, DistributionMetadata, Name, Requirement, ResolutionDiagnostic, ResolvedDist, VersionId,
-    VersionOrUrlRef,
+    Diagnostic, Dist, DistributionMetadata, IndexUrl, Name, Requirement, ResolutionDiagnostic,
+    ResolvedDist, Verbatim, VersionId, VersionOrUrlRef,
 };
-use pep440_rs::{Version, VersionSpecifier};
-use pep508_rs::MarkerEnvironment;
-use pypi_types::{ParsedUrlError, Yanked};
-use uv_normalize::PackageName;
+use pep440_rs::{Operator, Version, VersionSpecifier, VersionSpecifiers};
+use pep508_rs::{ExtraOperator, MarkerEnvironment, MarkerExpression, MarkerTree};
+use pypi_types::{HashAlgorithm, HashDigest, ParsedUrlError, Yanked};
+use uv_normalize::{ExtraName, PackageName};
 
 use crate::dependency_provider::UvDependencyProvider;
 use crate::editables::Editables;
+use crate::error::{
+    ConflictError, DuplicatePackageError, MissingMarkersError, RequiresPythonError,
+};
 use crate::pins::FilePins;
 use crate::preferences::Preferences;
-use crate::pubgrub::{PubGrubDistribution, PubGrubPackageInner};
+use crate::pubgrub::{PubGrubDistribution, PubGrubPackageInner, PubGrubSpecifier};
 use crate::redirect::url_to_precise;
-use crate::resolution::AnnotatedDist;
+use crate::resolution::requires_python::validate_requires_python;
+use crate::resolution::{AnnotatedDist, SourceKind};
 use crate::resolver::FxOnceMap;
 use crate::{
     lock, InMemoryIndex, Lock, LockError, Manifest, MetadataResponse, ResolveError,
     VersionsResponse,
 };
 
+/// A comparable key for a node in the graph, used to sort nodes for display.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum NodeKey<'a> {
+    /// A node linked to an editable distribution, sorted by verbatim representation.
+    Editable(Cow<'a, str>),
+    /// A node linked to a non-editable distribution, sorted by package name.
+    Distribution(&'a PackageName),
+}
+
 /// A complete resolution graph in which every node represents a pinned package and every edge
 /// represents a dependency between two pinned packages.
 #[derive(Debug)]
 pub struct ResolutionGraph {
     /// The underlying graph.
     pub(crate) petgraph: petgraph::graph::Graph<AnnotatedDist, Range<Version>, petgraph::Directed>,
+    /// The direct dependencies of the root package, along with the markers under which each
+    /// applies (`None` if the dependency is unconditional).
+    pub(crate) roots: FxHashMap<NodeIndex, Option<MarkerTree>>,
     /// The set of editable requirements in this resolution.
     pub(crate) editables: Editables,
     /// Any diagnostics that were encountered while building the graph.
     pub(crate) diagnostics: Vec<ResolutionDiagnostic>,
 }
 
+/// The total download size of a [`ResolutionGraph`].
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct ResolutionSize {
+    /// The combined size, in bytes, of all distributions with a known size.
+    pub known: u64,
+    /// The number of distributions with an unknown size.
+    pub unknown: usize,
+}
+
+/// Summary statistics for a [`ResolutionGraph`].
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct ResolutionStats {
+    /// The total number of packages in the resolution.
+    pub packages: usize,
+    /// The number of packages resolved to a wheel.
+    pub wheels: usize,
+    /// The number of packages resolved to a source distribution.
+    pub source_dists: usize,
+    /// The number of packages resolved from the default index (PyPI).
+    pub default_index: usize,
+    /// The number of packages resolved from any other index.
+    pub other_index: usize,
+    /// The number of packages with at least one hash.
+    pub hashed: usize,
+    /// The number of packages without any hashes.
+    pub unhashed: usize,
+}
+
+impl std::fmt::Display for ResolutionStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} package{} ({} wheel{}, {} source distribution{}; {} from PyPI, {} from other indexes; {} with hashes, {} without)",
+            self.packages,
+            if self.packages == 1 { "" } else { "s" },
+            self.wheels,
+            if self.wheels == 1 { "" } else { "s" },
+            self.source_dists,
+            if self.source_dists == 1 { "" } else { "s" },
+            self.default_index,
+            self.other_index,
+            self.hashed,
+            self.unhashed,
+        )
+    }
+}
+
 impl ResolutionGraph {
     /// Create a new graph from the resolved PubGrub state.
+    ///
+    /// If a [`MarkerEnvironment`] is provided for `requires_python`, every distribution's
+    /// `Requires-Python` is validated against the environment's Python version, and any
+    /// incompatible distributions are reported as an error.
     #[allow(clippy::too_many_arguments)]
     pub(crate) fn from_state(
         selection: &SelectedDependencies<UvDependencyProvider>,
@@ -51,6 +125,8 @@ impl ResolutionGraph {
         state: &State<UvDependencyProvider>,
         preferences: &Preferences,
         editables: Editables,
+        requirements: &[Requirement],
+        requires_python: Option<&MarkerEnvironment>,
     ) -> anyhow::Result<Self, ResolveError> {
         // Collect and validate the extras.
         let mut extras = FxHashMap::default();
@@ -328,7 +404,12 @@ impl ResolutionGraph {
             };
         }
 
+        // Every package should be represented by a single node; otherwise, the edges (and any
+        // lockfile derived from the graph) would silently refer to only one of them.
+        check_duplicates(&petgraph)?;
+
         // Add every edge to the graph.
+        let mut roots = FxHashMap::default();
         for (package, version) in selection {
             for id in &state.incompatibilities[package] {
                 if let Kind::FromDependencyOf(
@@ -346,6 +427,20 @@ impl ResolutionGraph {
                         continue;
                     }
 
+                    // Track the direct dependencies of the root package.
+                    if let PubGrubPackageInner::Root(_) = &**self_package {
+                        if let PubGrubPackageInner::Package { name, .. }
+                        | PubGrubPackageInner::Extra { name, .. } = &**dependency_package
+                        {
+                            if let Some(index) = inverse.get(name) {
+                                roots
+                                    .entry(*index)
+                                    .or_insert_with(|| root_marker(name, requirements));
+                            }
+                        }
+                        continue;
+                    }
+
                     let PubGrubPackageInner::Package {
                         name: self_name, ..
                     } = &**self_package
@@ -378,8 +473,18 @@ impl ResolutionGraph {
             }
         }
 
+        // If requested, validate that every distribution is compatible with the target Python
+        // version.
+        if let Some(markers) = requires_python {
+            validate_requires_python(
+                petgraph.node_weights(),
+                &markers.python_full_version().version,
+            )?;
+        }
+
         Ok(Self {
             petgraph,
+            roots,
             editables,
             diagnostics,
         })
@@ -402,6 +507,154 @@ impl ResolutionGraph {
             .any(|index| self.petgraph[index].name() == name)
     }
 
+    /// Validate that each extra requested by the given requirements is declared by the
+    /// corresponding package in the resolution (e.g., to catch `[tests]` in place of `[test]`).
+    ///
+    /// Returns a [`ResolutionDiagnostic::MissingExtra`] for each undeclared extra, to be reported
+    /// as a warning. Requirements for packages that aren't part of the resolution are ignored.
+    pub fn validate_extras(&self, requirements: &[Requirement]) -> Vec<ResolutionDiagnostic> {
+        let mut diagnostics = Vec::new();
+        for requirement in requirements {
+            let Some(dist) = self
+                .petgraph
+                .node_weights()
+                .find(|dist| dist.name() == &requirement.name)
+            else {
+                continue;
+            };
+            for extra in &requirement.extras {
+                if !dist.provides_extra().contains(extra) {
+                    diagnostics.push(ResolutionDiagnostic::MissingExtra {
+                        dist: dist.dist.clone(),
+                        extra: extra.clone(),
+                    });
+                }
+            }
+        }
+        diagnostics
+    }
+
+    /// Return the total download size of the distributions in this resolution.
+    ///
+    /// Sizes are only known if reported by the index; distributions of unknown size are counted
+    /// separately, rather than treated as empty.
+    pub fn total_size(&self) -> ResolutionSize {
+        self.petgraph
+            .node_weights()
+            .fold(ResolutionSize::default(), |mut total, dist| {
+                match dist.size() {
+                    Some(size) => total.known += size,
+                    None => total.unknown += 1,
+                }
+                total
+            })
+    }
+
+    /// Return the distributions in this resolution that don't have any hashes (e.g., to backfill
+    /// hashes before enabling `--require-hashes`).
+    pub fn unhashed(&self) -> Vec<&AnnotatedDist> {
+        self.petgraph
+            .node_weights()
+            .filter(|dist| dist.hashes.is_empty())
+            .collect()
+    }
+
+    /// Return this resolution with the given hashes added to each package (e.g., hashes computed
+    /// locally, for algorithms that the index doesn't provide).
+    #[must_use]
+    pub fn with_hashes(
+        mut self,
+        hashes: &FxHashMap<PackageName, Vec<HashDigest>>,
+    ) -> ResolutionGraph {
+        for dist in self.petgraph.node_weights_mut() {
+            let Some(digests) = hashes.get(dist.name()) else {
+                continue;
+            };
+            for digest in digests {
+                if !dist.hashes.contains(digest) {
+                    dist.hashes.push(digest.clone());
+                }
+            }
+            dist.hashes.sort_unstable();
+        }
+        self
+    }
+
+    /// Return this resolution with only the hashes computed using the given algorithms (e.g., to
+    /// emit SHA-512 digests for indexes that provide several).
+    ///
+    /// Distributions that don't have a hash for any of the given algorithms are left without
+    /// hashes.
+    #[must_use]
+    pub fn with_hash_algorithms(mut self, algorithms: &[HashAlgorithm]) -> ResolutionGraph {
+        for dist in self.petgraph.node_weights_mut() {
+            dist.hashes
+                .retain(|hash| algorithms.contains(&hash.algorithm));
+        }
+        self
+    }
+
+    /// Return summary statistics for the resolution (e.g., the number of wheels and source
+    /// distributions).
+    pub fn stats(&self) -> ResolutionStats {
+        self.petgraph
+            .node_weights()
+            .fold(ResolutionStats::default(), |mut stats, dist| {
+                stats.packages += 1;
+                match &dist.dist {
+                    ResolvedDist::Installable(Dist::Built(_)) => stats.wheels += 1,
+                    ResolvedDist::Installable(Dist::Source(_)) => stats.source_dists += 1,
+                    ResolvedDist::Installed(_) => {}
+                }
+                match dist.dist.index() {
+                    Some(IndexUrl::Pypi(_)) => stats.default_index += 1,
+                    Some(IndexUrl::Url(_) | IndexUrl::Path(_)) => stats.other_index += 1,
+                    None => {}
+                }
+                if dist.hashes.is_empty() {
+                    stats.unhashed += 1;
+                } else {
+                    stats.hashed += 1;
+                }
+                stats
+            })
+    }
+
+    /// Iterate over the distributions in this resolution, without cloning them.
+    ///
+    /// Distributions are yielded in the same order as they're written by
+    /// [`crate::DisplayResolutionGraph`]: sorted by package name, but with editable packages
+    /// first.
+    pub fn dists(&self) -> impl Iterator<Item = &AnnotatedDist> {
+        self.display_order().map(|index| &self.petgraph[index])
+    }
+
+    /// Return the node indices in the graph, sorted by package name, but with editable packages
+    /// first (sorted by their verbatim representation).
+    pub(crate) fn display_order(&self) -> impl Iterator<Item = NodeIndex> + '_ {
+        self.petgraph
+            .node_indices()
+            .map(|index| {
+                let name = self.petgraph[index].name();
+                let key = if let Some(editable) = self.editables.get(name) {
+                    NodeKey::Editable(editable.built.verbatim())
+                } else {
+                    NodeKey::Distribution(name)
+                };
+                (key, index)
+            })
+            .sorted_unstable()
+            .map(|(_, index)| index)
+    }
+
+    /// Iterate over the packages in this resolution, along with the [`SourceKind`] of each
+    /// resolved distribution.
+    pub fn source_kinds(&self) -> impl Iterator<Item = (&PackageName, SourceKind)> {
+        self.petgraph
+            .node_weights()
+            .map(|dist| (dist.name(), dist.source_kind()))
+    }
+
     /// Iterate over the [`ResolvedDist`] entities in this resolution.
     pub fn into_distributions(self) -> impl Iterator<Item = ResolvedDist> {
         self.petgraph
@@ -416,6 +669,485 @@ impl ResolutionGraph {
         &self.diagnostics
     }
 
+    /// Return the subset of this resolution that applies to the given [`MarkerEnvironment`].
+    ///
+    /// Dependencies whose markers don't apply to the environment are removed, along with any
+    /// distributions that are no longer reachable from the root requirements.
+    #[must_use]
+    pub fn for_environment(&self, markers: &MarkerEnvironment) -> ResolutionGraph {
+        // Identify the roots that apply to the environment. If the roots weren't recorded, treat
+        // every package without any dependents as a root.
+        let roots = if self.roots.is_empty() {
+            self.petgraph
+                .externals(Direction::Incoming)
+                .collect::<Vec<_>>()
+        } else {
+            self.roots
+                .iter()
+                .filter(|(_, marker)| {
+                    marker
+                        .as_ref()
+                        .map_or(true, |marker| marker.evaluate(markers, &[]))
+                })
+                .map(|(index, _)| *index)
+                .collect::<Vec<_>>()
+        };
+
+        // Walk the graph from the roots, following only those edges that apply to the
+        // environment.
+        let mut petgraph = petgraph::graph::Graph::with_capacity(
+            self.petgraph.node_count(),
+            self.petgraph.edge_count(),
+        );
+        let mut inverse = FxHashMap::default();
+        let mut queue = VecDeque::new();
+        for root in roots {
+            if !inverse.contains_key(&root) {
+                inverse.insert(root, petgraph.add_node(self.petgraph[root].clone()));
+                queue.push_back(root);
+            }
+        }
+        while let Some(index) = queue.pop_front() {
+            let source = &self.petgraph[index];
+            for edge in self.petgraph.edges_directed(index, Direction::Outgoing) {
+                let target = &self.petgraph[edge.target()];
+                if !edge_applies(source, target.name(), markers) {
+                    continue;
+                }
+                let target_index = *inverse.entry(edge.target()).or_insert_with(|| {
+                    queue.push_back(edge.target());
+                    petgraph.add_node(target.clone())
+                });
+                petgraph.update_edge(inverse[&index], target_index, edge.weight().clone());
+            }
+        }
+
+        let roots = self
+            .roots
+            .iter()
+            .filter_map(|(index, marker)| Some((*inverse.get(index)?, marker.clone())))
+            .collect();
+
+        let diagnostics = self
+            .diagnostics
+            .iter()
+            .filter(|diagnostic| {
+                petgraph
+                    .node_weights()
+                    .any(|dist| diagnostic.includes(dist.name()))
+            })
+            .cloned()
+            .collect();
+
+        ResolutionGraph {
+            petgraph,
+            roots,
+            editables: self.editables.clone(),
+            diagnostics,
+        }
+    }
+
+    /// Verify that every package in this (universal) resolution is required unconditionally, such
+    /// that it can be exported without markers.
+    ///
+    /// A package is required unconditionally if it's reachable from the root requirements along a
+    /// path on which every requirement is unconditional (or conditional only on extras that were
+    /// enabled). Packages that are only reachable through requirements with markers (e.g.,
+    /// `colorama; sys_platform == 'win32'`) would be installed on every platform if exported
+    /// without those markers, and are returned as an error, along with the markers under which
+    /// they're required.
+    ///
+    /// Resolutions for a specific environment have already omitted the dependencies that don't
+    /// apply, so this check is only meaningful for universal resolutions.
+    pub fn check_markers(&self) -> Result<(), MissingMarkersError> {
+        let mut markers = self.conditional_markers();
+        let packages = self
+            .display_order()
+            .filter_map(|index| {
+                let marker = markers.remove(&index)?;
+                Some((self.petgraph[index].dist.clone(), marker))
+            })
+            .collect::<Vec<_>>();
+
+        if packages.is_empty() {
+            Ok(())
+        } else {
+            Err(MissingMarkersError { packages })
+        }
+    }
+
+    /// Return the markers under which each package in this (universal) resolution is required,
+    /// for every package that isn't required unconditionally.
+    ///
+    /// See [`ResolutionGraph::check_markers`].
+    pub(crate) fn conditional_markers(&self) -> FxHashMap<NodeIndex, MarkerTree> {
+        // Identify the unconditional roots. If the roots weren't recorded, treat every package
+        // without any dependents as an unconditional root.
+        let roots = if self.roots.is_empty() {
+            self.petgraph
+                .externals(Direction::Incoming)
+                .collect::<Vec<_>>()
+        } else {
+            self.roots
+                .iter()
+                .filter(|(_, marker)| marker.is_none())
+                .map(|(index, _)| *index)
+                .collect::<Vec<_>>()
+        };
+
+        // Walk the graph from the unconditional roots, following only unconditional edges.
+        let mut unconditional = FxHashSet::default();
+        let mut queue = roots
+            .into_iter()
+            .filter(|index| unconditional.insert(*index))
+            .collect::<VecDeque<_>>();
+        while let Some(index) = queue.pop_front() {
+            let source = &self.petgraph[index];
+            for edge in self.petgraph.edges_directed(index, Direction::Outgoing) {
+                let target = &self.petgraph[edge.target()];
+                if edge_marker(source, target.name()).is_none()
+                    && unconditional.insert(edge.target())
+                {
+                    queue.push_back(edge.target());
+                }
+            }
+        }
+
+        // Any remaining package is only required under some markers. Seed those markers from the
+        // conditional roots, and the conditional edges out of the unconditional packages...
+        let mut markers: FxHashMap<NodeIndex, Vec<MarkerTree>> = FxHashMap::default();
+        for (index, marker) in &self.roots {
+            if let Some(marker) = marker.as_ref().filter(|_| !unconditional.contains(index)) {
+                markers.entry(*index).or_default().push(marker.clone());
+            }
+        }
+        for index in self
+            .display_order()
+            .filter(|index| unconditional.contains(index))
+        {
+            let source = &self.petgraph[index];
+            for edge in self.petgraph.edges_directed(index, Direction::Outgoing) {
+                if unconditional.contains(&edge.target()) {
+                    continue;
+                }
+                if let Some(marker) = edge_marker(source, self.petgraph[edge.target()].name()) {
+                    markers.entry(edge.target()).or_default().push(marker);
+                }
+            }
+        }
+
+        // ...then propagate them to the transitive dependencies. Each package tracks the markers
+        // of the paths along which it's required (as conjunctions), and is revisited whenever a
+        // new path reaches it, until no package gains any further paths.
+        let mut paths: FxHashMap<NodeIndex, Vec<Vec<MarkerTree>>> = markers
+            .into_iter()
+            .map(|(index, markers)| {
+                let mut paths = Vec::new();
+                for marker in markers {
+                    insert_path(&mut paths, conjuncts(&marker));
+                }
+                (index, paths)
+            })
+            .collect();
+        let mut queue = self
+            .display_order()
+            .filter(|index| paths.contains_key(index))
+            .collect::<VecDeque<_>>();
+        while let Some(index) = queue.pop_front() {
+            let source = &self.petgraph[index];
+            let sources = paths[&index].clone();
+            for edge in self.petgraph.edges_directed(index, Direction::Outgoing) {
+                if unconditional.contains(&edge.target()) {
+                    continue;
+                }
+                let marker = edge_marker(source, self.petgraph[edge.target()].name());
+                let targets = paths.entry(edge.target()).or_default();
+                let mut changed = false;
+                for path in &sources {
+                    let mut path = path.clone();
+                    if let Some(marker) = &marker {
+                        for conjunct in conjuncts(marker) {
+                            if !path.contains(&conjunct) {
+                                path.push(conjunct);
+                            }
+                        }
+                    }
+                    changed |= insert_path(targets, path);
+                }
+                if changed && !queue.contains(&edge.target()) {
+                    queue.push_back(edge.target());
+                }
+            }
+        }
+
+        self.display_order()
+            .filter(|index| !unconditional.contains(index))
+            .filter_map(|index| {
+                let paths = paths.remove(&index)?;
+                Some((
+                    index,
+                    or_markers(paths.into_iter().map(and_markers).collect()),
+                ))
+            })
+            .collect()
+    }
+
+    /// Return the Python versions supported by every package in the resolution, i.e., the
+    /// intersection of their `Requires-Python` specifiers.
+    ///
+    /// Packages that don't declare a `Requires-Python` support any Python version. If the
+    /// specifiers have no version in common, the conflicting packages are returned as an error.
+    pub fn supported_python(&self) -> Result<VersionSpecifiers, RequiresPythonError> {
+        let requires_python = self
+            .dists()
+            .filter_map(|dist| Some((dist, dist.requires_python()?)))
+            .collect::<Vec<_>>();
+
+        // Intersect the ranges to detect conflicts.
+        let ranges = requires_python
+            .iter()
+            .map(|(_, specifiers)| specifiers_to_range(specifiers))
+            .collect::<Vec<_>>();
+        let supported = ranges
+            .iter()
+            .fold(Range::full(), |acc, range| acc.intersection(range));
+        if supported.is_empty() {
+            // Report the packages that are pairwise incompatible, or, if the conflict only
+            // arises from three or more packages, every package with a `Requires-Python`.
+            let mut conflicting = FxHashSet::default();
+            for (i, first) in ranges.iter().enumerate() {
+                for (j, second) in ranges.iter().enumerate().skip(i + 1) {
+                    if first.intersection(second).is_empty() {
+                        conflicting.insert(i);
+                        conflicting.insert(j);
+                    }
+                }
+            }
+            let dists = requires_python
+                .into_iter()
+                .enumerate()
+                .filter(|(i, _)| conflicting.is_empty() || conflicting.contains(i))
+                .map(|(_, (dist, specifiers))| (dist.dist.clone(), specifiers.clone()))
+                .collect();
+            return Err(RequiresPythonError::NoCommonVersion { dists });
+        }
+
+        // Otherwise, combine the specifiers, retaining only the tightest lower and upper bounds.
+        let mut lower: Option<&VersionSpecifier> = None;
+        let mut upper: Option<&VersionSpecifier> = None;
+        let mut others: Vec<&VersionSpecifier> = Vec::new();
+        for specifier in requires_python
+            .iter()
+            .flat_map(|(_, specifiers)| specifiers.iter())
+        {
+            match specifier.operator() {
+                Operator::GreaterThan | Operator::GreaterThanEqual => {
+                    if lower.map_or(true, |lower| {
+                        specifier.version() > lower.version()
+                            || (specifier.version() == lower.version()
+                                && *specifier.operator() == Operator::GreaterThan)
+                    }) {
+                        lower = Some(specifier);
+                    }
+                }
+                Operator::LessThan | Operator::LessThanEqual => {
+                    if upper.map_or(true, |upper| {
+                        specifier.version() < upper.version()
+                            || (specifier.version() == upper.version()
+                                && *specifier.operator() == Operator::LessThan)
+                    }) {
+                        upper = Some(specifier);
+                    }
+                }
+                _ => {
+                    if !others.contains(&specifier) {
+                        others.push(specifier);
+                    }
+                }
+            }
+        }
+        Ok(lower
+            .into_iter()
+            .chain(others)
+            .chain(upper)
+            .cloned()
+            .collect())
+    }
+
+    /// Return a copy of this resolution without the given packages (e.g., to exclude development
+    /// tools before exporting).
+    ///
+    /// Only the given packages are removed; their dependencies are retained, even if they're no
+    /// longer reachable from the root requirements. See
+    /// [`ResolutionGraph::without_packages_and_unreachable`] to remove those as well.
+    #[must_use]
+    pub fn without_packages(&self, packages: &FxHashSet<PackageName>) -> ResolutionGraph {
+        let retain = self
+            .petgraph
+            .node_indices()
+            .filter(|index| !packages.contains(self.petgraph[*index].name()))
+            .collect();
+        self.retain_nodes(&retain)
+    }
+
+    /// Return a copy of this resolution without the given packages, along with any packages that
+    /// are no longer reachable from the remaining root requirements (i.e., dependencies that were
+    /// only required by the removed packages).
+    #[must_use]
+    pub fn without_packages_and_unreachable(
+        &self,
+        packages: &FxHashSet<PackageName>,
+    ) -> ResolutionGraph {
+        // Identify the remaining roots. If the roots weren't recorded, treat every package without
+        // any dependents as a root.
+        let roots = if self.roots.is_empty() {
+            self.petgraph
+                .externals(Direction::Incoming)
+                .collect::<Vec<_>>()
+        } else {
+            self.roots.keys().copied().collect::<Vec<_>>()
+        };
+
+        // Walk the graph from the remaining roots, without traversing the removed packages.
+        let mut retain = FxHashSet::default();
+        let mut queue = roots
+            .into_iter()
+            .filter(|index| !packages.contains(self.petgraph[*index].name()))
+            .collect::<VecDeque<_>>();
+        while let Some(index) = queue.pop_front() {
+            if !retain.insert(index) {
+                continue;
+            }
+            for dependency in self.petgraph.neighbors_directed(index, Direction::Outgoing) {
+                if !packages.contains(self.petgraph[dependency].name()) {
+                    queue.push_back(dependency);
+                }
+            }
+        }
+
+        self.retain_nodes(&retain)
+    }
+
+    /// Return a copy of this resolution that includes only the given nodes, along with the edges
+    /// between them.
+    fn retain_nodes(&self, retain: &FxHashSet<NodeIndex>) -> ResolutionGraph {
+        let mut petgraph = petgraph::graph::Graph::with_capacity(retain.len(), 0);
+        let mut inverse = FxHashMap::default();
+        for index in self.petgraph.node_indices() {
+            if retain.contains(&index) {
+                inverse.insert(index, petgraph.add_node(self.petgraph[index].clone()));
+            }
+        }
+        for edge in self.petgraph.edge_references() {
+            if let (Some(source), Some(target)) =
+                (inverse.get(&edge.source()), inverse.get(&edge.target()))
+            {
+                petgraph.add_edge(*source, *target, edge.weight().clone());
+            }
+        }
+
+        let roots = self
+            .roots
+            .iter()
+            .filter_map(|(index, marker)| Some((*inverse.get(index)?, marker.clone())))
+            .collect();
+
+        let diagnostics = self
+            .diagnostics
+            .iter()
+            .filter(|diagnostic| {
+                petgraph
+                    .node_weights()
+                    .any(|dist| diagnostic.includes(dist.name()))
+            })
+            .cloned()
+            .collect();
+
+        ResolutionGraph {
+            petgraph,
+            roots,
+            editables: self.editables.clone(),
+            diagnostics,
+        }
+    }
+
+    /// Merge another [`ResolutionGraph`] into this one, e.g., to combine the resolutions of
+    /// several independent groups of requirements.
+    ///
+    /// Packages that appear in both resolutions must be pinned to the same version (or URL), in
+    /// which case their extras and hashes are combined. Otherwise, the conflicting packages are
+    /// reported in a [`ConflictError`].
+    pub fn merge(mut self, other: ResolutionGraph) -> Result<ResolutionGraph, ConflictError> {
+        let mut inverse = self
+            .petgraph
+            .node_indices()
+            .map(|index| (self.petgraph[index].name().clone(), index))
+            .collect::<FxHashMap<_, _>>();
+
+        // Add (or combine) the packages from the other resolution, tracking the index of each
+        // package in the merged graph.
+        let (nodes, edges) = other.petgraph.into_nodes_edges();
+        let mut indices = Vec::with_capacity(nodes.len());
+        let mut conflicts = Vec::new();
+        for node in nodes {
+            let dist = node.weight;
+            if let Some(&index) = inverse.get(dist.name()) {
+                let existing = &mut self.petgraph[index];
+                if existing.dist.version_id() == dist.dist.version_id() {
+                    existing.extras.extend(dist.extras);
+                    existing.extras.sort_unstable();
+                    existing.extras.dedup();
+                    existing.hashes.extend(dist.hashes);
+                    existing.hashes.sort_unstable();
+                    existing.hashes.dedup();
+                } else {
+                    conflicts.push((existing.dist.clone(), dist.dist));
+                }
+                indices.push(index);
+            } else {
+                let name = dist.name().clone();
+                let index = self.petgraph.add_node(dist);
+                inverse.insert(name, index);
+                indices.push(index);
+            }
+        }
+
+        if !conflicts.is_empty() {
+            return Err(ConflictError { conflicts });
+        }
+
+        for edge in edges {
+            let source = indices[edge.source().index()];
+            let target = indices[edge.target().index()];
+            if !self.petgraph.contains_edge(source, target) {
+                self.petgraph.add_edge(source, target, edge.weight);
+            }
+        }
+
+        // A package that is a root in both resolutions applies whenever either marker applies.
+        for (index, marker) in other.roots {
+            match self.roots.entry(indices[index.index()]) {
+                Entry::Occupied(mut entry) => {
+                    let combined = match (entry.get_mut().take(), marker) {
+                        (Some(existing), Some(marker)) => {
+                            Some(MarkerTree::Or(vec![existing, marker]))
+                        }
+                        _ => None,
+                    };
+                    entry.insert(combined);
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(marker);
+                }
+            }
+        }
+
+        self.editables.extend(other.editables);
+        self.diagnostics.extend(other.diagnostics);
+
+        Ok(self)
+    }
+
     /// Return the marker tree specific to this resolution.
     ///
     /// This accepts a manifest, in-memory-index and marker environment. All
@@ -442,9 +1174,7 @@ impl ResolutionGraph {
         index: &InMemoryIndex,
         marker_env: &MarkerEnvironment,
     ) -> anyhow::Result<pep508_rs::MarkerTree, Box<ParsedUrlError>> {
-        use pep508_rs::{
-            MarkerExpression, MarkerOperator, MarkerTree, MarkerValueString, MarkerValueVersion,
-        };
+        use pep508_rs::{MarkerExpression, MarkerOperator, MarkerValueString, MarkerValueVersion};
 
         /// A subset of the possible marker values.
         ///
@@ -563,13 +1293,18 @@ impl ResolutionGraph {
     }
 
     pub fn lock(&self) -> anyhow::Result<Lock, LockError> {
+        // Record the markers under which each conditionally-required package applies, along with
+        // the markers on each dependency edge, so that the lock can be installed on any platform.
+        let markers = self.conditional_markers();
         let mut locked_dists = vec![];
         for node_index in self.petgraph.node_indices() {
             let dist = &self.petgraph[node_index];
-            let mut locked_dist = lock::Distribution::from_annotated_dist(dist)?;
+            let mut locked_dist =
+                lock::Distribution::from_annotated_dist(dist, markers.get(&node_index))?;
             for edge in self.petgraph.neighbors(node_index) {
                 let dependency_dist = &self.petgraph[edge];
-                locked_dist.add_dependency(dependency_dist);
+                let marker = edge_marker(dist, dependency_dist.name());
+                locked_dist.add_dependency(dependency_dist, marker.as_ref());
             }
             locked_dists.push(locked_dist);
         }
@@ -578,6 +1313,212 @@ impl ResolutionGraph {
     }
 }
 
+/// Validate that no two nodes in the graph share the same (normalized) package name.
+fn check_duplicates(
+    petgraph: &petgraph::graph::Graph<AnnotatedDist, Range<Version>, petgraph::Directed>,
+) -> Result<(), DuplicatePackageError> {
+    let mut seen = FxHashMap::default();
+    let mut duplicates = Vec::new();
+    for dist in petgraph.node_weights() {
+        match seen.entry(dist.name()) {
+            Entry::Occupied(entry) => {
+                duplicates.push((entry.get().dist.clone(), dist.dist.clone()));
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(dist);
+            }
+        }
+    }
+    if duplicates.is_empty() {
+        Ok(())
+    } else {
+        Err(DuplicatePackageError { duplicates })
+    }
+}
+
+/// Convert the given specifiers to the range of versions they allow, ignoring any specifiers that
+/// can't be represented as a range.
+fn specifiers_to_range(specifiers: &VersionSpecifiers) -> Range<Version> {
+    specifiers
+        .iter()
+        .filter_map(|specifier| PubGrubSpecifier::try_from(specifier).ok())
+        .fold(Range::full(), |acc, specifier| {
+            acc.intersection(&Range::from(specifier))
+        })
+}
+
+/// Return the marker under which a direct dependency applies, or `None` if the dependency is
+/// unconditional.
+fn root_marker(name: &PackageName, requirements: &[Requirement]) -> Option<MarkerTree> {
+    let mut markers = Vec::new();
+    for requirement in requirements
+        .iter()
+        .filter(|requirement| requirement.name == *name)
+    {
+        markers.push(requirement.marker.clone()?);
+    }
+    if markers.len() > 1 {
+        Some(MarkerTree::Or(markers))
+    } else {
+        markers.pop()
+    }
+}
+
+/// Returns `true` if the dependency of `source` on `target` applies in the given
+/// [`MarkerEnvironment`], based on the requirements declared in the source's metadata.
+fn edge_applies(source: &AnnotatedDist, target: &PackageName, markers: &MarkerEnvironment) -> bool {
+    let mut requirements = source
+        .metadata
+        .requires_dist
+        .iter()
+        .filter(|requirement| requirement.name == *target)
+        .peekable();
+
+    // If the dependency isn't backed by a declared requirement (e.g., it was introduced by an
+    // override), retain it.
+    if requirements.peek().is_none() {
+        return true;
+    }
+
+    requirements.any(|requirement| requirement.evaluate_markers(markers, &source.extras))
+}
+
+/// Returns the marker under which `source` requires `target`, based on the requirements declared in
+/// the source's metadata, or `None` if the requirement is unconditional (including if it's only
+/// conditional on extras that are enabled for the source).
+///
+/// Requirements that only apply for extras that aren't enabled for the source are ignored.
+pub(super) fn edge_marker(source: &AnnotatedDist, target: &PackageName) -> Option<MarkerTree> {
+    let mut markers = Vec::new();
+    for requirement in source
+        .metadata
+        .requires_dist
+        .iter()
+        .filter(|requirement| requirement.name == *target)
+    {
+        // If any requirement is unconditional, so is the dependency.
+        match evaluate_extras(requirement.marker.clone()?, &source.extras) {
+            ExtraMarker::True => return None,
+            ExtraMarker::False => {}
+            ExtraMarker::Marker(marker) => markers.push(marker),
+        }
+    }
+
+    // If the dependency isn't backed by an applicable requirement (e.g., it was introduced by an
+    // override), treat it as unconditional.
+    if markers.is_empty() {
+        None
+    } else {
+        Some(or_markers(markers))
+    }
+}
+
+/// The result of evaluating the `extra` expressions in a marker, given the enabled extras.
+enum ExtraMarker {
+    /// The marker always applies.
+    True,
+    /// The marker can't apply (e.g., `extra == 'dev'` when `dev` isn't enabled).
+    False,
+    /// The marker applies under the remaining (non-`extra`) marker.
+    Marker(MarkerTree),
+}
+
+/// Evaluate the `extra` expressions in a marker, given the enabled extras.
+fn evaluate_extras(marker: MarkerTree, extras: &[ExtraName]) -> ExtraMarker {
+    match marker {
+        MarkerTree::Expression(MarkerExpression::Extra { operator, name }) => {
+            let enabled = extras.contains(&name);
+            let applies = match operator {
+                ExtraOperator::Equal => enabled,
+                ExtraOperator::NotEqual => !enabled,
+            };
+            if applies {
+                ExtraMarker::True
+            } else {
+                ExtraMarker::False
+            }
+        }
+        MarkerTree::Expression(expression) => {
+            ExtraMarker::Marker(MarkerTree::Expression(expression))
+        }
+        MarkerTree::And(markers) => {
+            let mut remaining = Vec::new();
+            for marker in markers {
+                match evaluate_extras(marker, extras) {
+                    ExtraMarker::True => {}
+                    // If any marker can't apply, neither can the conjunction.
+                    ExtraMarker::False => return ExtraMarker::False,
+                    ExtraMarker::Marker(marker) => remaining.push(marker),
+                }
+            }
+            if remaining.is_empty() {
+                ExtraMarker::True
+            } else {
+                ExtraMarker::Marker(and_markers(remaining))
+            }
+        }
+        MarkerTree::Or(markers) => {
+            let mut remaining = Vec::new();
+            for marker in markers {
+                match evaluate_extras(marker, extras) {
+                    // If any marker always applies, so does the disjunction.
+                    ExtraMarker::True => return ExtraMarker::True,
+                    ExtraMarker::False => {}
+                    ExtraMarker::Marker(marker) => remaining.push(marker),
+                }
+            }
+            if remaining.is_empty() {
+                ExtraMarker::False
+            } else {
+                ExtraMarker::Marker(or_markers(remaining))
+            }
+        }
+    }
+}
+
+/// Split the given marker into the markers that must all apply for it to apply.
+fn conjuncts(marker: &MarkerTree) -> Vec<MarkerTree> {
+    match marker {
+        MarkerTree::And(markers) => markers.clone(),
+        marker => vec![marker.clone()],
+    }
+}
+
+/// Add a path, given as the (conjoined) markers along it, to the paths under which a package is
+/// required, returning `true` if the path is new.
+///
+/// A path is redundant if some existing path requires a subset of its markers; likewise, any
+/// existing paths made redundant by the new path are removed.
+fn insert_path(paths: &mut Vec<Vec<MarkerTree>>, path: Vec<MarkerTree>) -> bool {
+    let is_subset = |subset: &[MarkerTree], superset: &[MarkerTree]| {
+        subset.iter().all(|marker| superset.contains(marker))
+    };
+    if paths.iter().any(|existing| is_subset(existing, &path)) {
+        return false;
+    }
+    paths.retain(|existing| !is_subset(&path, existing));
+    paths.push(path);
+    true
+}
+
+/// Combine the given (non-empty) markers into a single marker that applies if all of them apply.
+fn and_markers(mut markers: Vec<MarkerTree>) -> MarkerTree {
+    if markers.len() == 1 {
+        markers.remove(0)
+    } else {
+        MarkerTree::And(markers)
+    }
+}
+
+/// Combine the given (non-empty) markers into a single marker that applies if any of them apply.
+fn or_markers(mut markers: Vec<MarkerTree>) -> MarkerTree {
+    if markers.len() == 1 {
+        markers.remove(0)
+    } else {
+        MarkerTree::Or(markers)
+    }
+}
+
 impl From<ResolutionGraph> for distribution_types::Resolution {
     fn from(graph: ResolutionGraph) -> Self {
         Self::new(
@@ -595,3 +1536,654 @@ impl From<ResolutionGraph> for distribution_types::Resolution {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use distribution_types::{BuiltDist, Diagnostic, Dist, Requirement, ResolvedDist};
+    use pep440_rs::VersionSpecifiers;
+    use pep508_rs::MarkerEnvironment;
+    use platform_tags::{Arch, Os, Platform, Tags};
+    use pypi_types::{HashAlgorithm, HashDigest};
+    use rustc_hash::{FxHashMap, FxHashSet};
+    use uv_normalize::{ExtraName, PackageName};
+
+    use crate::resolution::tests::{
+        marker_environment, path_dist, registry_dist, resolution_graph,
+    };
+    use crate::{AnnotatedDist, ResolutionSize, ResolutionStats};
+
+    #[test]
+    fn for_environment_removes_platform_specific_dependency() {
+        let graph = resolution_graph(
+            vec![
+                registry_dist(
+                    "click",
+                    "8.1.7",
+                    &["colorama; platform_system == 'Windows'"],
+                ),
+                registry_dist("colorama", "0.4.6", &[]),
+            ],
+            &[(0, 1)],
+            &[0],
+        );
+
+        let linux = graph.for_environment(&marker_environment("linux", "Linux"));
+        assert_eq!(linux.len(), 1);
+        assert!(linux.contains(&PackageName::from_str("click").unwrap()));
+        assert!(!linux.contains(&PackageName::from_str("colorama").unwrap()));
+
+        let windows = graph.for_environment(&marker_environment("win32", "Windows"));
+        assert_eq!(windows.len(), 2);
+        assert!(windows.contains(&PackageName::from_str("colorama").unwrap()));
+    }
+
+    #[test]
+    fn for_environment_removes_unreachable_dependencies() {
+        let graph = resolution_graph(
+            vec![
+                registry_dist("app", "1.0.0", &["pywin32; sys_platform == 'win32'"]),
+                registry_dist("pywin32", "306", &["pywin32-ctypes"]),
+                registry_dist("pywin32-ctypes", "0.2.2", &[]),
+            ],
+            &[(0, 1), (1, 2)],
+            &[0],
+        );
+
+        let linux = graph.for_environment(&marker_environment("linux", "Linux"));
+        assert_eq!(linux.len(), 1);
+        assert!(!linux.contains(&PackageName::from_str("pywin32-ctypes").unwrap()));
+    }
+
+    #[test]
+    fn check_markers_unconditional() {
+        // Unconditional requirements, and requirements conditional only on enabled extras, can be
+        // exported without markers.
+        let mut black = registry_dist("black", "24.4.2", &["colorama; extra == 'colorama'"]);
+        black.extras = vec![ExtraName::from_str("colorama").unwrap()];
+        let graph = resolution_graph(
+            vec![
+                registry_dist("flask", "3.0.3", &["click>=8.1.3"]),
+                registry_dist(
+                    "click",
+                    "8.1.7",
+                    &["colorama; platform_system == 'Windows'"],
+                ),
+                black,
+                registry_dist("colorama", "0.4.6", &[]),
+            ],
+            &[(0, 1), (1, 3), (2, 3)],
+            &[0, 2],
+        );
+        assert!(graph.check_markers().is_ok());
+    }
+
+    #[test]
+    fn check_markers_missing() {
+        let graph = resolution_graph(
+            vec![
+                registry_dist(
+                    "app",
+                    "1.0.0",
+                    &["pywin32; sys_platform == 'win32'", "click"],
+                ),
+                registry_dist("click", "8.1.7", &[]),
+                registry_dist("pywin32", "306", &["pywin32-ctypes"]),
+                registry_dist("pywin32-ctypes", "0.2.2", &[]),
+            ],
+            &[(0, 1), (0, 2), (2, 3)],
+            &[0],
+        );
+
+        // Both `pywin32` and its (unconditional) dependency are only required on Windows.
+        let err = graph.check_markers().unwrap_err();
+        let packages = err
+            .packages()
+            .iter()
+            .map(|(dist, marker)| (dist.to_string(), marker.to_string()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            packages,
+            vec![
+                (
+                    "pywin32==306".to_string(),
+                    "sys_platform == 'win32'".to_string()
+                ),
+                (
+                    "pywin32-ctypes==0.2.2".to_string(),
+                    "sys_platform == 'win32'".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn check_markers_disabled_extras() {
+        // Requirements gated on extras that aren't enabled don't contribute to the markers.
+        let graph = resolution_graph(
+            vec![
+                registry_dist(
+                    "black",
+                    "24.4.2",
+                    &[
+                        "colorama; sys_platform == 'win32' or extra == 'colorama'",
+                        "colorama; platform_system == 'Windows' and extra == 'jupyter'",
+                    ],
+                ),
+                registry_dist("colorama", "0.4.6", &[]),
+            ],
+            &[(0, 1)],
+            &[0],
+        );
+
+        let err = graph.check_markers().unwrap_err();
+        let packages = err
+            .packages()
+            .iter()
+            .map(|(dist, marker)| (dist.to_string(), marker.to_string()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            packages,
+            vec![(
+                "colorama==0.4.6".to_string(),
+                "sys_platform == 'win32'".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn check_markers_diamond() {
+        // `c` is required on Windows via `a`, and on Linux via `b` and `d`.
+        let graph = resolution_graph(
+            vec![
+                registry_dist(
+                    "app",
+                    "1.0.0",
+                    &["a; sys_platform == 'win32'", "b; sys_platform == 'linux'"],
+                ),
+                registry_dist("a", "1.0.0", &["c"]),
+                registry_dist("b", "1.0.0", &["d"]),
+                registry_dist("c", "1.0.0", &[]),
+                registry_dist("d", "1.0.0", &["c"]),
+            ],
+            &[(0, 1), (0, 2), (1, 3), (2, 4), (4, 3)],
+            &[0],
+        );
+
+        let err = graph.check_markers().unwrap_err();
+        let packages = err
+            .packages()
+            .iter()
+            .map(|(dist, marker)| (dist.to_string(), marker.to_string()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            packages,
+            vec![
+                (
+                    "a==1.0.0".to_string(),
+                    "sys_platform == 'win32'".to_string()
+                ),
+                (
+                    "b==1.0.0".to_string(),
+                    "sys_platform == 'linux'".to_string()
+                ),
+                (
+                    "c==1.0.0".to_string(),
+                    "sys_platform == 'win32' or sys_platform == 'linux'".to_string()
+                ),
+                (
+                    "d==1.0.0".to_string(),
+                    "sys_platform == 'linux'".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn lock_markers_diamond() {
+        // `c` is required on Windows via `a`, and on Linux via `b` and `d`.
+        let mut dists = vec![
+            registry_dist(
+                "app",
+                "1.0.0",
+                &["a; sys_platform == 'win32'", "b; sys_platform == 'linux'"],
+            ),
+            registry_dist("a", "1.0.0", &["c"]),
+            registry_dist("b", "1.0.0", &["d"]),
+            registry_dist("c", "1.0.0", &[]),
+            registry_dist("d", "1.0.0", &["c"]),
+        ];
+        for dist in &mut dists {
+            let ResolvedDist::Installable(Dist::Built(BuiltDist::Registry(wheels))) =
+                &mut dist.dist
+            else {
+                unreachable!()
+            };
+            wheels.wheels[0].file.hashes = vec![HashDigest {
+                algorithm: HashAlgorithm::Sha256,
+                digest: "0".repeat(64).into_boxed_str(),
+            }];
+        }
+        let graph = resolution_graph(dists, &[(0, 1), (0, 2), (1, 3), (2, 4), (4, 3)], &[0]);
+        let lock = graph.lock().unwrap();
+
+        let tags = Tags::from_platform(
+            &Platform::new(
+                Os::Manylinux {
+                    major: 2,
+                    minor: 28,
+                },
+                Arch::X86_64,
+            ),
+            (3, 12),
+        )
+        .unwrap();
+        let root = PackageName::from_str("app").unwrap();
+        let packages = |markers: &MarkerEnvironment| {
+            lock.to_resolution(markers, &tags, &root)
+                .packages()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+        };
+
+        // `c` is installed on both platforms.
+        assert_eq!(
+            packages(&marker_environment("linux", "Linux")),
+            vec!["app", "b", "c", "d"]
+        );
+        assert_eq!(
+            packages(&marker_environment("win32", "Windows")),
+            vec!["a", "app", "c"]
+        );
+    }
+
+    #[test]
+    fn merge() {
+        let runtime = resolution_graph(
+            vec![
+                registry_dist("flask", "3.0.3", &["click>=8.1.3"]),
+                registry_dist("click", "8.1.7", &[]),
+            ],
+            &[(0, 1)],
+            &[0],
+        );
+        let dev = resolution_graph(
+            vec![
+                registry_dist("black", "24.4.2", &["click>=8.0.0"]),
+                registry_dist("click", "8.1.7", &[]),
+            ],
+            &[(0, 1)],
+            &[0],
+        );
+
+        let merged = runtime.merge(dev).unwrap();
+        assert_eq!(merged.len(), 3);
+        assert_eq!(merged.petgraph.edge_count(), 2);
+        assert_eq!(merged.roots.len(), 2);
+        for name in ["flask", "black", "click"] {
+            assert!(merged.contains(&PackageName::from_str(name).unwrap()));
+        }
+    }
+
+    #[test]
+    fn merge_extras_and_hashes() {
+        let sha256 = |digest: &str| pypi_types::HashDigest {
+            algorithm: pypi_types::HashAlgorithm::Sha256,
+            digest: digest.into(),
+        };
+        let extra = |extra: &str| uv_normalize::ExtraName::from_str(extra).unwrap();
+
+        let mut runtime = registry_dist("flask", "3.0.3", &[]);
+        runtime.extras = vec![extra("dotenv"), extra("async")];
+        runtime.hashes = vec![sha256("f3"), sha256("b0")];
+        let mut dev = registry_dist("flask", "3.0.3", &[]);
+        dev.extras = vec![extra("async"), extra("dev")];
+        dev.hashes = vec![sha256("b0"), sha256("a1")];
+
+        // The combined extras and hashes should be sorted and free of duplicates.
+        let merged = resolution_graph(vec![runtime], &[], &[0])
+            .merge(resolution_graph(vec![dev], &[], &[0]))
+            .unwrap();
+        let flask = merged.petgraph.node_weights().next().unwrap();
+        assert_eq!(
+            flask.extras,
+            vec![extra("async"), extra("dev"), extra("dotenv")]
+        );
+        assert_eq!(flask.hashes, vec![sha256("a1"), sha256("b0"), sha256("f3")]);
+    }
+
+    #[test]
+    fn merge_conflict() {
+        let runtime = resolution_graph(
+            vec![
+                registry_dist("flask", "3.0.3", &["click>=8.1.3"]),
+                registry_dist("click", "8.1.7", &[]),
+            ],
+            &[(0, 1)],
+            &[0],
+        );
+        let dev = resolution_graph(
+            vec![
+                registry_dist("black", "22.3.0", &["click>=8.0.0"]),
+                registry_dist("click", "8.0.4", &[]),
+            ],
+            &[(0, 1)],
+            &[0],
+        );
+
+        let err = runtime.merge(dev).unwrap_err();
+        assert_eq!(err.conflicts().len(), 1);
+        assert_eq!(
+            err.to_string(),
+            "The resolutions contain conflicting versions:\n- click==8.1.7 conflicts with click==8.0.4"
+        );
+    }
+
+    #[test]
+    fn total_size() {
+        let mut flask = registry_dist("flask", "3.0.3", &["click>=8.1.3"]);
+        let mut click = registry_dist("click", "8.1.7", &[]);
+        for (dist, size) in [(&mut flask, 101_735), (&mut click, 97_941)] {
+            let ResolvedDist::Installable(Dist::Built(BuiltDist::Registry(wheels))) =
+                &mut dist.dist
+            else {
+                unreachable!()
+            };
+            wheels.wheels[0].file.size = Some(size);
+        }
+        let graph = resolution_graph(
+            vec![flask, click, registry_dist("itsdangerous", "2.2.0", &[])],
+            &[(0, 1), (0, 2)],
+            &[0],
+        );
+
+        assert_eq!(
+            graph.total_size(),
+            ResolutionSize {
+                known: 199_676,
+                unknown: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn stats() {
+        let mut flask = registry_dist("flask", "3.0.3", &["click>=8.1.3"]);
+        flask.hashes = vec![
+            "sha256:34e815dfaa43340d1d15a5c3a02b8476004037eb4840b34910c6e21a7c1ede2"
+                .parse()
+                .unwrap(),
+        ];
+        let graph = resolution_graph(
+            vec![
+                flask,
+                registry_dist("click", "8.1.7", &[]),
+                path_dist("./flask-3.0.3-py3-none-any.whl", &[]),
+            ],
+            &[(0, 1)],
+            &[0, 2],
+        );
+
+        let stats = graph.stats();
+        assert_eq!(
+            stats,
+            ResolutionStats {
+                packages: 3,
+                wheels: 3,
+                source_dists: 0,
+                default_index: 2,
+                other_index: 0,
+                hashed: 1,
+                unhashed: 2,
+            }
+        );
+        assert_eq!(
+            stats.to_string(),
+            "3 packages (3 wheels, 0 source distributions; 2 from PyPI, 0 from other indexes; 1 with hashes, 2 without)"
+        );
+    }
+
+    #[test]
+    fn validate_extras() {
+        let mut black = registry_dist("black", "24.3.0", &[]);
+        black.metadata.provides_extras = vec![ExtraName::from_str("colorama").unwrap()];
+        let graph = resolution_graph(vec![black], &[], &[0]);
+
+        let requirement = |requirement: &str| {
+            Requirement::from(pep508_rs::Requirement::from_str(requirement).unwrap())
+        };
+        let diagnostics = graph.validate_extras(&[
+            requirement("black[colorama,colourama]"),
+            requirement("flask[async]"),
+        ]);
+        assert_eq!(
+            diagnostics
+                .iter()
+                .map(Diagnostic::message)
+                .collect::<Vec<_>>(),
+            vec!["The package `black==24.3.0` does not have an extra named `colourama`."]
+        );
+    }
+
+    #[test]
+    fn unhashed() {
+        let mut flask = registry_dist("flask", "3.0.3", &["click>=8.1.3"]);
+        flask.hashes = vec![
+            "sha256:34e815dfaa43340d1d15a5c3a02b8476004037eb4840b34910c6e21a7c1ede2"
+                .parse()
+                .unwrap(),
+        ];
+        let mut black = registry_dist("black", "24.3.0", &[]);
+        black.extras = vec![ExtraName::from_str("colorama").unwrap()];
+        let graph = resolution_graph(
+            vec![flask, registry_dist("click", "8.1.7", &[]), black],
+            &[(0, 1)],
+            &[0, 2],
+        );
+
+        // Only the distributions without hashes are returned, along with their annotations.
+        let unhashed = graph.unhashed();
+        assert_eq!(
+            unhashed
+                .iter()
+                .map(|dist| dist.dist.to_string())
+                .collect::<Vec<_>>(),
+            vec!["click==8.1.7", "black==24.3.0"]
+        );
+        assert!(unhashed.iter().all(|dist| dist.hashes.is_empty()));
+        assert_eq!(
+            unhashed[1]
+                .extras
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec!["colorama"]
+        );
+    }
+
+    #[test]
+    fn with_hash_algorithms() {
+        let mut flask = registry_dist("flask", "3.0.3", &[]);
+        flask.hashes = vec![
+            "sha256:34e815dfaa43340d1d15a5c3a02b8476004037eb4840b34910c6e21a7c1ede2"
+                .parse()
+                .unwrap(),
+            "sha512:5bd0a72d1a1cf6d4c5ac6b3ba10c9f2c4b8cc3b33cd35c40ae9b2ba37fe31c4b"
+                .parse()
+                .unwrap(),
+        ];
+        let graph =
+            resolution_graph(vec![flask], &[], &[0]).with_hash_algorithms(&[HashAlgorithm::Sha512]);
+
+        assert_eq!(
+            graph
+                .dists()
+                .flat_map(|dist| dist.hashes.iter().map(ToString::to_string))
+                .collect::<Vec<_>>(),
+            vec!["sha512:5bd0a72d1a1cf6d4c5ac6b3ba10c9f2c4b8cc3b33cd35c40ae9b2ba37fe31c4b"]
+        );
+    }
+
+    #[test]
+    fn with_hashes() {
+        let mut flask = registry_dist("flask", "3.0.3", &[]);
+        flask.hashes = vec![
+            "sha256:34e815dfaa43340d1d15a5c3a02b8476004037eb4840b34910c6e21a7c1ede2"
+                .parse()
+                .unwrap(),
+        ];
+        let hashes = FxHashMap::from_iter([(
+            PackageName::from_str("flask").unwrap(),
+            vec![
+                "sha256:34e815dfaa43340d1d15a5c3a02b8476004037eb4840b34910c6e21a7c1ede2"
+                    .parse()
+                    .unwrap(),
+                "sha512:5bd0a72d1a1cf6d4c5ac6b3ba10c9f2c4b8cc3b33cd35c40ae9b2ba37fe31c4b"
+                    .parse()
+                    .unwrap(),
+            ],
+        )]);
+        let graph = resolution_graph(vec![flask], &[], &[0]).with_hashes(&hashes);
+
+        // Hashes that are already present shouldn't be duplicated.
+        assert_eq!(
+            graph
+                .dists()
+                .flat_map(|dist| dist.hashes.iter().map(ToString::to_string))
+                .collect::<Vec<_>>(),
+            vec![
+                "sha256:34e815dfaa43340d1d15a5c3a02b8476004037eb4840b34910c6e21a7c1ede2",
+                "sha512:5bd0a72d1a1cf6d4c5ac6b3ba10c9f2c4b8cc3b33cd35c40ae9b2ba37fe31c4b"
+            ]
+        );
+    }
+
+    #[test]
+    fn without_packages() {
+        let graph = resolution_graph(
+            vec![
+                registry_dist("flask", "3.0.3", &["click>=8.1.3"]),
+                registry_dist("click", "8.1.7", &[]),
+                registry_dist("black", "24.4.2", &["click>=8.0.0", "pathspec>=0.9.0"]),
+                registry_dist("pathspec", "0.12.1", &[]),
+            ],
+            &[(0, 1), (2, 1), (2, 3)],
+            &[0, 2],
+        );
+        let dev = FxHashSet::from_iter([PackageName::from_str("black").unwrap()]);
+
+        // Removing `black` alone retains its dependencies.
+        let pruned = graph.without_packages(&dev);
+        assert_eq!(pruned.len(), 3);
+        assert!(!pruned.contains(&PackageName::from_str("black").unwrap()));
+        assert!(pruned.contains(&PackageName::from_str("pathspec").unwrap()));
+        assert_eq!(pruned.roots.len(), 1);
+
+        // Removing unreachable packages also drops `pathspec`, which only `black` depends on, but
+        // retains `click`, which `flask` depends on too.
+        let pruned = graph.without_packages_and_unreachable(&dev);
+        assert_eq!(pruned.len(), 2);
+        assert!(pruned.contains(&PackageName::from_str("flask").unwrap()));
+        assert!(pruned.contains(&PackageName::from_str("click").unwrap()));
+        assert!(!pruned.contains(&PackageName::from_str("pathspec").unwrap()));
+        assert_eq!(pruned.petgraph.edge_count(), 1);
+    }
+
+    #[test]
+    fn dists() {
+        let graph = resolution_graph(
+            vec![
+                registry_dist("itsdangerous", "2.2.0", &[]),
+                registry_dist("flask", "3.0.3", &["click>=8.1.3", "itsdangerous>=2.1.2"]),
+                registry_dist("click", "8.1.7", &[]),
+            ],
+            &[(1, 2), (1, 0)],
+            &[1],
+        );
+
+        // Distributions should be yielded in the display order, regardless of insertion order.
+        assert_eq!(
+            graph.dists().map(ToString::to_string).collect::<Vec<_>>(),
+            vec!["click==8.1.7", "flask==3.0.3", "itsdangerous==2.2.0"]
+        );
+    }
+
+    #[test]
+    fn duplicate_packages() {
+        let graph = resolution_graph(
+            vec![
+                registry_dist("typing-extensions", "4.11.0", &[]),
+                registry_dist("anyio", "4.3.0", &[]),
+                registry_dist("Typing_Extensions", "4.10.0", &[]),
+            ],
+            &[],
+            &[],
+        );
+        let err = super::check_duplicates(&graph.petgraph).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "The resolution contains multiple distributions for the same package:\n\
+             - typing-extensions==4.11.0 and typing-extensions==4.10.0"
+        );
+
+        let graph = resolution_graph(
+            vec![
+                registry_dist("typing-extensions", "4.11.0", &[]),
+                registry_dist("anyio", "4.3.0", &[]),
+            ],
+            &[],
+            &[],
+        );
+        assert!(super::check_duplicates(&graph.petgraph).is_ok());
+    }
+
+    /// Create a registry distribution with the given `Requires-Python`.
+    fn requires_python_dist(name: &str, requires_python: &str) -> AnnotatedDist {
+        let mut dist = registry_dist(name, "1.0.0", &[]);
+        dist.metadata.requires_python = Some(VersionSpecifiers::from_str(requires_python).unwrap());
+        dist
+    }
+
+    #[test]
+    fn supported_python() {
+        let graph = resolution_graph(
+            vec![
+                requires_python_dist("anyio", ">=3.8"),
+                requires_python_dist("idna", ">=3.5"),
+                registry_dist("sniffio", "1.3.1", &[]),
+                requires_python_dist("trio", ">=3.8, !=3.9.0, <4"),
+            ],
+            &[],
+            &[],
+        );
+        assert_eq!(
+            graph.supported_python().unwrap().to_string(),
+            ">=3.8, !=3.9.0, <4"
+        );
+
+        // Without any `Requires-Python`, every version is supported.
+        let graph = resolution_graph(vec![registry_dist("sniffio", "1.3.1", &[])], &[], &[]);
+        assert!(graph.supported_python().unwrap().is_empty());
+    }
+
+    #[test]
+    fn supported_python_conflict() {
+        let graph = resolution_graph(
+            vec![
+                requires_python_dist("anyio", ">=3.8"),
+                requires_python_dist("futures", ">=2.6, <3"),
+                requires_python_dist("six", ">=2.6"),
+            ],
+            &[],
+            &[],
+        );
+        let err = graph.supported_python().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "The resolution contains packages that don't support any Python version in common:\n\
+             - anyio==1.0.0 requires Python >=3.8\n\
+             - futures==1.0.0 requires Python >=2.6, <3"
+        );
+    }
+}
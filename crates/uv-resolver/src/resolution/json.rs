@@ -0,0 +1,293 @@
+use std::borrow::Cow;
+use std::io::Write;
+
+use itertools::Itertools;
+use petgraph::graph::NodeIndex;
+use petgraph::Direction;
+use rustc_hash::FxHashMap;
+
+use distribution_types::{Name, VersionOrUrlRef};
+use pep508_rs::MarkerTree;
+use uv_normalize::PackageName;
+
+use crate::resolution::ArtifactType;
+use crate::ResolutionGraph;
+
+/// A pinned package, as written by the JSON and JSONL exports of a [`ResolutionGraph`].
+#[derive(Debug, serde::Serialize)]
+struct JsonDist {
+    name: String,
+    version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    index: Option<String>,
+    artifact: ArtifactType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    filename: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    extras: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    hashes: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    requires_python: Option<String>,
+    /// The markers under which the package is required, for packages in a universal resolution
+    /// that aren't required unconditionally.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    markers: Option<String>,
+    dependencies: Vec<String>,
+    /// The PEP 518 build requirements, for source distributions that declare any. Unlike
+    /// `dependencies`, these are needed to build the package, rather than at runtime.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    build_requires: Vec<String>,
+}
+
+impl ResolutionGraph {
+    /// Write the resolution to the given writer as a single JSON array, with one object per
+    /// pinned package, sorted by package name.
+    ///
+    /// Source distributions that declare PEP 518 build requirements include them under
+    /// `build_requires`, separately from their runtime `dependencies`. The given packages are
+    /// omitted, both as objects and from the `dependencies` of other packages.
+    pub fn write_json(
+        &self,
+        writer: impl Write,
+        no_emit_packages: &[PackageName],
+    ) -> std::io::Result<()> {
+        let markers = self.conditional_markers();
+        let dists = self
+            .sorted_indices()
+            .filter(|index| !no_emit_packages.contains(self.petgraph[*index].name()))
+            .map(|index| self.json_dist(index, &markers, no_emit_packages))
+            .collect::<Vec<_>>();
+        serde_json::to_writer(writer, &dists)?;
+        Ok(())
+    }
+
+    /// Write the resolution to the given writer as JSON Lines, with one object per pinned
+    /// package, sorted by package name.
+    ///
+    /// Unlike [`ResolutionGraph::write_json`], each package is serialized and written
+    /// individually, such that the output can be consumed as a stream.
+    pub fn write_jsonl(&self, mut writer: impl Write) -> std::io::Result<()> {
+        let markers = self.conditional_markers();
+        for index in self.sorted_indices() {
+            serde_json::to_writer(&mut writer, &self.json_dist(index, &markers, &[]))?;
+            writer.write_all(b"\n")?;
+        }
+        Ok(())
+    }
+
+    /// Return the node indices in the graph, sorted by package name and version.
+    pub(super) fn sorted_indices(&self) -> impl Iterator<Item = NodeIndex> + '_ {
+        self.petgraph.node_indices().sorted_by(|a, b| {
+            let a = &self.petgraph[*a];
+            let b = &self.petgraph[*b];
+            a.name()
+                .cmp(b.name())
+                .then_with(|| a.metadata.version.cmp(&b.metadata.version))
+        })
+    }
+
+    /// Convert the distribution at the given index to its serialized representation, given the
+    /// markers of each conditionally-required package (see
+    /// [`ResolutionGraph::conditional_markers`]), omitting the given packages from its
+    /// dependencies.
+    fn json_dist(
+        &self,
+        index: NodeIndex,
+        markers: &FxHashMap<NodeIndex, MarkerTree>,
+        no_emit_packages: &[PackageName],
+    ) -> JsonDist {
+        let dist = &self.petgraph[index];
+        JsonDist {
+            name: dist.name().to_string(),
+            version: dist.metadata.version.to_string(),
+            url: match dist.dist.version_or_url() {
+                VersionOrUrlRef::Version(_) => None,
+                VersionOrUrlRef::Url(url) => Some(url.to_string()),
+            },
+            index: dist.dist.index().map(ToString::to_string),
+            artifact: dist.artifact_type(),
+            filename: dist.artifact_filename().map(Cow::into_owned),
+            extras: dist
+                .extras
+                .iter()
+                .sorted_unstable()
+                .dedup()
+                .map(ToString::to_string)
+                .collect(),
+            hashes: dist.hashes.iter().map(ToString::to_string).collect(),
+            requires_python: dist
+                .metadata
+                .requires_python
+                .as_ref()
+                .map(ToString::to_string),
+            markers: markers.get(&index).map(ToString::to_string),
+            dependencies: self
+                .petgraph
+                .neighbors_directed(index, Direction::Outgoing)
+                .map(|dependency| self.petgraph[dependency].name())
+                .filter(|name| !no_emit_packages.contains(name))
+                .map(ToString::to_string)
+                .sorted_unstable()
+                .dedup()
+                .collect(),
+            build_requires: dist
+                .build_requires()
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use itertools::Itertools;
+
+    use distribution_types::{
+        BuiltDist, Dist, IndexUrl, RegistrySourceDist, ResolvedDist, SourceDist,
+    };
+    use pep440_rs::Version;
+    use uv_normalize::PackageName;
+
+    use crate::resolution::tests::{registry_dist, resolution_graph};
+
+    #[test]
+    fn jsonl() {
+        let graph = resolution_graph(
+            vec![
+                registry_dist("sniffio", "1.3.1", &[]),
+                registry_dist("anyio", "4.3.0", &["idna>=2.8", "sniffio>=1.1"]),
+                registry_dist("idna", "3.7", &[]),
+            ],
+            &[(1, 2), (1, 0)],
+            &[1],
+        );
+
+        let mut jsonl = Vec::new();
+        graph.write_jsonl(&mut jsonl).unwrap();
+        let jsonl = String::from_utf8(jsonl).unwrap();
+        assert_eq!(
+            jsonl,
+            concat!(
+                r#"{"name":"anyio","version":"4.3.0","index":"https://pypi.org/simple","artifact":"wheel","filename":"anyio-4.3.0-py3-none-any.whl","dependencies":["idna","sniffio"]}"#,
+                "\n",
+                r#"{"name":"idna","version":"3.7","index":"https://pypi.org/simple","artifact":"wheel","filename":"idna-3.7-py3-none-any.whl","dependencies":[]}"#,
+                "\n",
+                r#"{"name":"sniffio","version":"1.3.1","index":"https://pypi.org/simple","artifact":"wheel","filename":"sniffio-1.3.1-py3-none-any.whl","dependencies":[]}"#,
+                "\n",
+            )
+        );
+
+        // The full JSON export should contain the same objects.
+        let mut json = Vec::new();
+        graph.write_json(&mut json, &[]).unwrap();
+        let json = String::from_utf8(json).unwrap();
+        assert_eq!(json, format!("[{}]", jsonl.trim_end().lines().join(",")));
+    }
+
+    #[test]
+    fn markers() {
+        let graph = resolution_graph(
+            vec![
+                registry_dist(
+                    "click",
+                    "8.1.7",
+                    &["colorama; platform_system == 'Windows'"],
+                ),
+                registry_dist("colorama", "0.4.6", &[]),
+            ],
+            &[(0, 1)],
+            &[0],
+        );
+
+        // Packages that are only required under some markers should include them.
+        let mut jsonl = Vec::new();
+        graph.write_jsonl(&mut jsonl).unwrap();
+        assert_eq!(
+            String::from_utf8(jsonl).unwrap(),
+            concat!(
+                r#"{"name":"click","version":"8.1.7","index":"https://pypi.org/simple","artifact":"wheel","filename":"click-8.1.7-py3-none-any.whl","dependencies":["colorama"]}"#,
+                "\n",
+                r#"{"name":"colorama","version":"0.4.6","index":"https://pypi.org/simple","artifact":"wheel","filename":"colorama-0.4.6-py3-none-any.whl","markers":"platform_system == 'Windows'","dependencies":[]}"#,
+                "\n",
+            )
+        );
+    }
+
+    #[test]
+    fn artifact_sdist() {
+        // A registry package that provides both a wheel and a source distribution, but was
+        // resolved to the source distribution.
+        let mut flask = registry_dist("flask", "3.0.3", &[]);
+        let ResolvedDist::Installable(Dist::Built(BuiltDist::Registry(wheels))) = &flask.dist
+        else {
+            unreachable!()
+        };
+        let mut file = wheels.wheels[0].file.clone();
+        file.filename = "flask-3.0.3.tar.gz".to_string();
+        flask.dist =
+            ResolvedDist::Installable(Dist::Source(SourceDist::Registry(RegistrySourceDist {
+                name: PackageName::from_str("flask").unwrap(),
+                version: Version::from_str("3.0.3").unwrap(),
+                file,
+                index: IndexUrl::from_str("https://pypi.org/simple").unwrap(),
+                wheels: wheels.wheels.clone(),
+            })));
+        let graph = resolution_graph(vec![flask], &[], &[0]);
+
+        let mut jsonl = Vec::new();
+        graph.write_jsonl(&mut jsonl).unwrap();
+        assert_eq!(
+            String::from_utf8(jsonl).unwrap(),
+            concat!(
+                r#"{"name":"flask","version":"3.0.3","index":"https://pypi.org/simple","artifact":"sdist","filename":"flask-3.0.3.tar.gz","dependencies":[]}"#,
+                "\n",
+            )
+        );
+    }
+
+    #[test]
+    fn build_requires() {
+        // A source distribution with build requirements, which depends on a wheel.
+        let mut flask = registry_dist("flask", "3.0.3", &["click>=8.1.3"]);
+        let ResolvedDist::Installable(Dist::Built(BuiltDist::Registry(wheels))) = &flask.dist
+        else {
+            unreachable!()
+        };
+        let mut file = wheels.wheels[0].file.clone();
+        file.filename = "flask-3.0.3.tar.gz".to_string();
+        flask.dist =
+            ResolvedDist::Installable(Dist::Source(SourceDist::Registry(RegistrySourceDist {
+                name: PackageName::from_str("flask").unwrap(),
+                version: Version::from_str("3.0.3").unwrap(),
+                file,
+                index: IndexUrl::from_str("https://pypi.org/simple").unwrap(),
+                wheels: wheels.wheels.clone(),
+            })));
+        flask.metadata.build_requires = vec!["flit-core<4".parse().unwrap()];
+        let graph = resolution_graph(
+            vec![flask, registry_dist("click", "8.1.7", &[])],
+            &[(0, 1)],
+            &[0],
+        );
+
+        // The build requirements should be listed separately from the runtime dependencies, and
+        // omitted for wheels.
+        let mut jsonl = Vec::new();
+        graph.write_jsonl(&mut jsonl).unwrap();
+        assert_eq!(
+            String::from_utf8(jsonl).unwrap(),
+            concat!(
+                r#"{"name":"click","version":"8.1.7","index":"https://pypi.org/simple","artifact":"wheel","filename":"click-8.1.7-py3-none-any.whl","dependencies":[]}"#,
+                "\n",
+                r#"{"name":"flask","version":"3.0.3","index":"https://pypi.org/simple","artifact":"sdist","filename":"flask-3.0.3.tar.gz","dependencies":["click"],"build_requires":["flit-core<4"]}"#,
+                "\n",
+            )
+        );
+    }
+}
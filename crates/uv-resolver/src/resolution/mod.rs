@@ -1,91 +1,257 @@
 use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::fmt::Display;
 use std::path::Path;
 
 use itertools::Itertools;
 
-use distribution_types::{DistributionMetadata, Name, ResolvedDist, Verbatim, VersionOrUrlRef};
-use pep508_rs::{split_scheme, Scheme};
-use pypi_types::{HashDigest, Metadata23};
+use distribution_types::{
+    BuiltDist, Dist, DistributionMetadata, IndexUrl, Name, RemoteSource, ResolvedDist, SourceDist,
+    Verbatim, VersionOrUrlRef,
+};
+use pep440_rs::{VersionSpecifier, VersionSpecifiers};
+use pep508_rs::{split_scheme, strip_project_root, Scheme};
+use pypi_types::{HashAlgorithm, HashDigest, Metadata23, VerbatimParsedUrl, Yanked};
+use url::Url;
+use uv_git::GitSha;
 use uv_normalize::{ExtraName, PackageName};
 
-pub use crate::resolution::display::{AnnotationStyle, DisplayResolutionGraph};
-pub use crate::resolution::graph::ResolutionGraph;
+pub use crate::resolution::diff::{PinDiff, ResolutionDiff};
+pub use crate::resolution::display::{
+    AnnotationStyle, ArtifactUrlPolicy, DisplayResolutionGraph, HashStyle,
+    RequirementsAndConstraints, RequirementsTxtOptions, UrlRenderers,
+};
+pub use crate::resolution::graph::{ResolutionGraph, ResolutionSize, ResolutionStats};
+pub use crate::resolution::tree::DependencyTree;
 
+mod diff;
 mod display;
+mod dot;
 mod graph;
+mod json;
+mod requires_python;
+mod tree;
 
 /// A pinned package with its resolved distribution and metadata. The [`ResolvedDist`] refers to a
 /// specific distribution (e.g., a specific wheel), while the [`Metadata23`] refers to the metadata
 /// for the package-version pair.
 #[derive(Debug, Clone)]
-pub(crate) struct AnnotatedDist {
+pub struct AnnotatedDist {
     pub(crate) dist: ResolvedDist,
     pub(crate) extras: Vec<ExtraName>,
     pub(crate) hashes: Vec<HashDigest>,
     pub(crate) metadata: Metadata23,
 }
 
+/// The kind of source from which a distribution was resolved.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SourceKind {
+    /// A wheel from a registry.
+    RegistryWheel,
+    /// A source distribution from a registry.
+    RegistrySdist,
+    /// A wheel or source distribution from a direct URL.
+    DirectUrl,
+    /// A source distribution from a Git repository, along with the resolved commit, if known.
+    Git(Option<GitSha>),
+    /// A wheel, source distribution, or source tree on the local filesystem.
+    Path,
+    /// An already-installed distribution.
+    Installed,
+}
+
+/// The type of artifact to which a distribution was resolved.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ArtifactType {
+    /// A built distribution (wheel).
+    Wheel,
+    /// A source distribution archive.
+    Sdist,
+    /// A source tree, such as a local directory or Git repository.
+    SourceTree,
+    /// An already-installed distribution.
+    Installed,
+}
+
 impl AnnotatedDist {
+    /// Returns the [`ArtifactType`] of the distribution.
+    ///
+    /// If a registry package provides both wheels and a source distribution, this reflects the
+    /// artifact that was actually chosen.
+    pub fn artifact_type(&self) -> ArtifactType {
+        match &self.dist {
+            ResolvedDist::Installed(_) => ArtifactType::Installed,
+            ResolvedDist::Installable(Dist::Built(_)) => ArtifactType::Wheel,
+            ResolvedDist::Installable(Dist::Source(dist)) => match dist {
+                SourceDist::Registry(_) | SourceDist::DirectUrl(_) | SourceDist::Path(_) => {
+                    ArtifactType::Sdist
+                }
+                SourceDist::Git(_) | SourceDist::Directory(_) => ArtifactType::SourceTree,
+            },
+        }
+    }
+
+    /// Returns the filename of the chosen artifact (e.g., the best compatible wheel), if the
+    /// distribution was resolved to an archive.
+    pub fn artifact_filename(&self) -> Option<Cow<'_, str>> {
+        match &self.dist {
+            ResolvedDist::Installed(_) => None,
+            ResolvedDist::Installable(Dist::Built(dist)) => {
+                Some(Cow::Owned(dist.wheel_filename().to_string()))
+            }
+            ResolvedDist::Installable(Dist::Source(dist)) => match dist {
+                SourceDist::Registry(_) | SourceDist::DirectUrl(_) | SourceDist::Path(_) => {
+                    dist.filename().ok()
+                }
+                SourceDist::Git(_) | SourceDist::Directory(_) => None,
+            },
+        }
+    }
+
+    /// Returns `true` if the distribution was resolved to a yanked release.
+    pub fn is_yanked(&self) -> bool {
+        self.dist.yanked().is_some_and(Yanked::is_yanked)
+    }
+
+    /// Returns the reason the release was yanked, if it was yanked with a reason.
+    pub fn yanked_reason(&self) -> Option<&str> {
+        match self.dist.yanked()? {
+            Yanked::Reason(reason) => Some(reason),
+            Yanked::Bool(_) => None,
+        }
+    }
+
+    /// Returns the URL of the resolved artifact (e.g., the chosen wheel), if the distribution was
+    /// resolved from a registry that provided a valid URL.
+    pub fn artifact_url(&self) -> Option<Url> {
+        self.dist.file()?.url.to_url().ok()
+    }
+
+    /// Returns the PEP 518 build requirements of the distribution, if it was resolved to a source
+    /// distribution that declares any. These are distinct from the runtime requirements in its
+    /// metadata, and are always empty for wheels.
+    pub fn build_requires(&self) -> &[pep508_rs::Requirement<VerbatimParsedUrl>] {
+        &self.metadata.build_requires
+    }
+
+    /// Returns the URL of the index from which the distribution was resolved (e.g., for
+    /// provenance, when multiple indexes could serve the package).
+    ///
+    /// Returns `None` for distributions that weren't resolved from an index, like local paths
+    /// and direct URLs.
+    pub fn index_url(&self) -> Option<&Url> {
+        self.dist.index().map(IndexUrl::url)
+    }
+
+    /// Returns the [`SourceKind`] of the distribution.
+    pub fn source_kind(&self) -> SourceKind {
+        match &self.dist {
+            ResolvedDist::Installed(_) => SourceKind::Installed,
+            ResolvedDist::Installable(Dist::Built(dist)) => match dist {
+                BuiltDist::Registry(_) => SourceKind::RegistryWheel,
+                BuiltDist::DirectUrl(_) => SourceKind::DirectUrl,
+                BuiltDist::Path(_) => SourceKind::Path,
+            },
+            ResolvedDist::Installable(Dist::Source(dist)) => match dist {
+                SourceDist::Registry(_) => SourceKind::RegistrySdist,
+                SourceDist::DirectUrl(_) => SourceKind::DirectUrl,
+                SourceDist::Git(dist) => SourceKind::Git(dist.git.precise()),
+                SourceDist::Path(_) | SourceDist::Directory(_) => SourceKind::Path,
+            },
+        }
+    }
+
+    /// Returns the size of the distribution in bytes, if reported by the index.
+    ///
+    /// Returns `None` if the size is unknown, e.g., for direct URL and local distributions.
+    pub fn size(&self) -> Option<u64> {
+        match &self.dist {
+            ResolvedDist::Installable(dist) => dist.file()?.size,
+            ResolvedDist::Installed(_) => None,
+        }
+    }
+
+    /// Returns the extras declared by the distribution (i.e., its `Provides-Extra` metadata).
+    pub fn provides_extra(&self) -> &[ExtraName] {
+        &self.metadata.provides_extras
+    }
+
+    /// Returns the Python versions supported by the distribution, if declared (i.e., its
+    /// `Requires-Python` metadata).
+    pub fn requires_python(&self) -> Option<&VersionSpecifiers> {
+        self.metadata.requires_python.as_ref()
+    }
+
+    /// Returns the SPDX license expression of the distribution, if declared (i.e., its
+    /// `License-Expression` metadata).
+    pub fn license_expression(&self) -> Option<&str> {
+        self.metadata.license_expression.as_deref()
+    }
+
+    /// Returns the license files included in the distribution, if declared (i.e., its
+    /// `License-File` metadata).
+    pub fn license_files(&self) -> &[String] {
+        &self.metadata.license_files
+    }
+
+    /// Returns the hash of the distribution computed with the given [`HashAlgorithm`], if any.
+    pub fn hash(&self, algorithm: HashAlgorithm) -> Option<&HashDigest> {
+        self.hashes
+            .iter()
+            .find(|digest| digest.algorithm == algorithm)
+    }
+
+    /// Returns the hashes of the distribution, keyed by [`HashAlgorithm`].
+    ///
+    /// If multiple digests are present for the same algorithm, the first is retained.
+    pub fn hashes_by_algorithm(&self) -> BTreeMap<HashAlgorithm, &HashDigest> {
+        let mut hashes = BTreeMap::new();
+        for digest in &self.hashes {
+            hashes.entry(digest.algorithm).or_insert(digest);
+        }
+        hashes
+    }
+
     /// Convert the [`AnnotatedDist`] to a requirement that adheres to the `requirements.txt`
     /// format.
     ///
     /// This typically results in a PEP 508 representation of the requirement, but will write an
     /// unnamed requirement for relative paths, which can't be represented with PEP 508 (but are
     /// supported in `requirements.txt`).
-    pub(crate) fn to_requirements_txt(&self, include_extras: bool) -> Cow<str> {
-        // If the URL is not _definitively_ an absolute `file://` URL, write it as a relative path.
-        if self.dist.is_local() {
-            if let VersionOrUrlRef::Url(url) = self.dist.version_or_url() {
-                let given = url.verbatim();
-                match split_scheme(&given) {
-                    Some((scheme, path)) => {
-                        match Scheme::parse(scheme) {
-                            Some(Scheme::File) => {
-                                if path
-                                    .strip_prefix("//localhost")
-                                    .filter(|path| path.starts_with('/'))
-                                    .is_some()
-                                {
-                                    // Always absolute; nothing to do.
-                                } else if let Some(path) = path.strip_prefix("//") {
-                                    // Strip the prefix, to convert, e.g., `file://flask-3.0.3-py3-none-any.whl` to `flask-3.0.3-py3-none-any.whl`.
-                                    //
-                                    // However, we should allow any of the following:
-                                    // - `file:///flask-3.0.3-py3-none-any.whl`
-                                    // - `file://C:\Users\user\flask-3.0.3-py3-none-any.whl`
-                                    // - `file:///C:\Users\user\flask-3.0.3-py3-none-any.whl`
-                                    if !path.starts_with("${PROJECT_ROOT}")
-                                        && !Path::new(path).has_root()
-                                    {
-                                        return Cow::Owned(path.to_string());
-                                    }
-                                } else {
-                                    // Ex) `file:./flask-3.0.3-py3-none-any.whl`
-                                    return given;
-                                }
-                            }
-                            Some(_) => {}
-                            None => {
-                                // Ex) `flask @ C:\Users\user\flask-3.0.3-py3-none-any.whl`
-                                return given;
-                            }
-                        }
-                    }
-                    None => {
-                        // Ex) `flask @ flask-3.0.3-py3-none-any.whl`
-                        return given;
-                    }
-                }
+    ///
+    /// Any `excluded_extras` are omitted from the requirement, even if `include_extras` is set.
+    /// URLs with a scheme overridden in `url_renderers` are written with the override, rather than
+    /// verbatim.
+    pub(crate) fn to_requirements_txt(
+        &self,
+        include_extras: bool,
+        excluded_extras: &[ExtraName],
+        url_renderers: &UrlRenderers,
+    ) -> Cow<str> {
+        let extras = self.sorted_extras(include_extras, excluded_extras);
+
+        if let Some(path) = self.unnamed_path() {
+            return with_extras(path, &extras);
+        }
+
+        if let VersionOrUrlRef::Url(url) = self.version_or_url() {
+            if let Some(url) = url_renderers.render(url) {
+                return if extras.is_empty() {
+                    Cow::Owned(format!("{} @ {url}", self.name()))
+                } else {
+                    Cow::Owned(format!(
+                        "{}[{}] @ {url}",
+                        self.name(),
+                        extras.into_iter().join(", ")
+                    ))
+                };
             }
         }
 
-        if self.extras.is_empty() || !include_extras {
+        if extras.is_empty() {
             self.dist.verbatim()
         } else {
-            let mut extras = self.extras.clone();
-            extras.sort_unstable();
-            extras.dedup();
             Cow::Owned(format!(
                 "{}[{}]{}",
                 self.name(),
@@ -94,6 +260,109 @@ impl AnnotatedDist {
             ))
         }
     }
+
+    /// Convert the [`AnnotatedDist`] to a PEP 508 [`pep508_rs::Requirement`] (e.g., to add
+    /// markers or merge extras programmatically).
+    ///
+    /// Returns `None` for relative paths, which can't be represented with PEP 508, and are
+    /// written as unnamed requirements by [`AnnotatedDist::to_requirements_txt`] instead.
+    pub fn to_pep508(&self, include_extras: bool) -> Option<pep508_rs::Requirement> {
+        if self.unnamed_path().is_some() {
+            return None;
+        }
+
+        let version_or_url = match self.version_or_url() {
+            VersionOrUrlRef::Version(version) => pep508_rs::VersionOrUrl::VersionSpecifier(
+                VersionSpecifiers::from(VersionSpecifier::equals_version(version.clone())),
+            ),
+            VersionOrUrlRef::Url(url) => pep508_rs::VersionOrUrl::Url(url.clone()),
+        };
+        Some(pep508_rs::Requirement {
+            name: self.name().clone(),
+            extras: self.sorted_extras(include_extras, &[]),
+            version_or_url: Some(version_or_url),
+            marker: None,
+            origin: None,
+        })
+    }
+
+    /// Return the extras to include in a requirement for the [`AnnotatedDist`], sorted and
+    /// deduplicated, so that the output is stable.
+    fn sorted_extras(&self, include_extras: bool, excluded_extras: &[ExtraName]) -> Vec<ExtraName> {
+        if !include_extras {
+            return vec![];
+        }
+        let mut extras = self
+            .extras
+            .iter()
+            .filter(|extra| !excluded_extras.contains(extra))
+            .cloned()
+            .collect::<Vec<_>>();
+        extras.sort_unstable();
+        extras.dedup();
+        extras
+    }
+
+    /// If the [`AnnotatedDist`] is a local distribution given as a relative path (i.e., if the URL
+    /// is not _definitively_ an absolute `file://` URL), return that path.
+    fn unnamed_path(&self) -> Option<Cow<str>> {
+        if !self.dist.is_local() {
+            return None;
+        }
+        let VersionOrUrlRef::Url(url) = self.dist.version_or_url() else {
+            return None;
+        };
+        let given = url.verbatim();
+        match split_scheme(&given) {
+            Some((scheme, path)) => {
+                match Scheme::parse(scheme) {
+                    Some(Scheme::File) => {
+                        if path
+                            .strip_prefix("//localhost")
+                            .filter(|path| path.starts_with('/'))
+                            .is_some()
+                        {
+                            // Always absolute; nothing to do.
+                            None
+                        } else if let Some(path) = path.strip_prefix("//") {
+                            // Strip the prefix, to convert, e.g., `file://flask-3.0.3-py3-none-any.whl` to `flask-3.0.3-py3-none-any.whl`.
+                            //
+                            // However, we should allow any of the following:
+                            // - `file:///flask-3.0.3-py3-none-any.whl`
+                            // - `file://C:\Users\user\flask-3.0.3-py3-none-any.whl`
+                            // - `file:///C:\Users\user\flask-3.0.3-py3-none-any.whl`
+                            if strip_project_root(path).is_none() && !Path::new(path).has_root() {
+                                Some(Cow::Owned(path.to_string()))
+                            } else {
+                                None
+                            }
+                        } else {
+                            // Ex) `file:./flask-3.0.3-py3-none-any.whl`
+                            Some(given)
+                        }
+                    }
+                    Some(_) => None,
+                    None => {
+                        // Ex) `flask @ C:\Users\user\flask-3.0.3-py3-none-any.whl`
+                        Some(given)
+                    }
+                }
+            }
+            None => {
+                // Ex) `flask @ flask-3.0.3-py3-none-any.whl`
+                Some(given)
+            }
+        }
+    }
+}
+
+/// Append the given extras to an unnamed requirement (e.g., `./path/to/project[extra]`).
+fn with_extras<'a>(given: Cow<'a, str>, extras: &[ExtraName]) -> Cow<'a, str> {
+    if extras.is_empty() {
+        given
+    } else {
+        Cow::Owned(format!("{given}[{}]", extras.iter().join(", ")))
+    }
 }
 
 impl Name for AnnotatedDist {
@@ -113,3 +382,348 @@ impl Display for AnnotatedDist {
         Display::fmt(&self.dist, f)
     }
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::str::FromStr;
+
+    use pubgrub::range::Range;
+    use rustc_hash::FxHashMap;
+    use url::Url;
+
+    use distribution_filename::WheelFilename;
+    use distribution_types::{
+        BuiltDist, Dist, File, FileLocation, IndexUrl, PathBuiltDist, RegistryBuiltDist,
+        RegistryBuiltWheel,
+    };
+    use pep440_rs::Version;
+    use pep508_rs::{MarkerEnvironment, MarkerEnvironmentBuilder, VerbatimUrl};
+    use pypi_types::{HashAlgorithm, HashDigest, Metadata23, ParsedGitUrl};
+    use uv_normalize::{ExtraName, PackageName};
+
+    use crate::editables::Editables;
+    use crate::resolution::{AnnotatedDist, SourceKind, UrlRenderers};
+    use crate::ResolutionGraph;
+
+    /// Create an [`AnnotatedDist`] for a wheel from PyPI with the given `Requires-Dist` metadata.
+    pub(crate) fn registry_dist(
+        name: &str,
+        version: &str,
+        requires_dist: &[&str],
+    ) -> AnnotatedDist {
+        let filename = WheelFilename::from_str(&format!(
+            "{}-{version}-py3-none-any.whl",
+            name.replace('-', "_")
+        ))
+        .unwrap();
+        let file = File {
+            dist_info_metadata: false,
+            filename: filename.to_string(),
+            hashes: vec![],
+            requires_python: None,
+            size: None,
+            upload_time_utc_ms: None,
+            url: FileLocation::AbsoluteUrl(format!(
+                "https://files.pythonhosted.org/packages/{filename}"
+            )),
+            yanked: None,
+        };
+        let dist = Dist::Built(BuiltDist::Registry(RegistryBuiltDist {
+            wheels: vec![RegistryBuiltWheel {
+                filename,
+                file: Box::new(file),
+                index: IndexUrl::from_str("https://pypi.org/simple").unwrap(),
+            }],
+            best_wheel_index: 0,
+            sdist: None,
+        }));
+        let metadata = Metadata23 {
+            name: PackageName::from_str(name).unwrap(),
+            version: Version::from_str(version).unwrap(),
+            requires_dist: requires_dist
+                .iter()
+                .map(|requirement| pep508_rs::Requirement::from_str(requirement).unwrap())
+                .collect(),
+            requires_python: None,
+            provides_extras: vec![],
+            license_expression: None,
+            license_files: vec![],
+            build_requires: vec![],
+        };
+        AnnotatedDist {
+            dist: dist.into(),
+            extras: vec![],
+            hashes: vec![],
+            metadata,
+        }
+    }
+
+    /// Create an [`AnnotatedDist`] for a local wheel, as given by the user, with the given extras.
+    pub(crate) fn path_dist(given: &str, extras: &[&str]) -> AnnotatedDist {
+        let filename = WheelFilename::from_str("flask-3.0.3-py3-none-any.whl").unwrap();
+        let url = Url::parse("file:///home/user/flask-3.0.3-py3-none-any.whl").unwrap();
+        let dist = Dist::Built(BuiltDist::Path(PathBuiltDist {
+            filename,
+            path: url.to_file_path().unwrap(),
+            url: VerbatimUrl::from_url(url).with_given(given),
+        }));
+        AnnotatedDist {
+            dist: dist.into(),
+            extras: extras
+                .iter()
+                .map(|extra| ExtraName::from_str(extra).unwrap())
+                .collect(),
+            hashes: vec![],
+            metadata: flask_metadata(),
+        }
+    }
+
+    /// Create an [`AnnotatedDist`] for a source distribution in a Git repository, as given by the
+    /// user.
+    pub(crate) fn git_dist(given: &str) -> AnnotatedDist {
+        let url = Url::parse(given).unwrap();
+        let ParsedGitUrl {
+            url: git,
+            subdirectory,
+        } = ParsedGitUrl::try_from(url.clone()).unwrap();
+        let dist = Dist::from_git_url(
+            PackageName::from_str("flask").unwrap(),
+            VerbatimUrl::from_url(url).with_given(given),
+            git,
+            subdirectory,
+        )
+        .unwrap();
+        AnnotatedDist {
+            dist: dist.into(),
+            extras: vec![],
+            hashes: vec![],
+            metadata: flask_metadata(),
+        }
+    }
+
+    /// The metadata for `flask==3.0.3`, without any dependencies.
+    fn flask_metadata() -> Metadata23 {
+        Metadata23 {
+            name: PackageName::from_str("flask").unwrap(),
+            version: Version::from_str("3.0.3").unwrap(),
+            requires_dist: vec![],
+            requires_python: None,
+            provides_extras: vec![],
+            license_expression: None,
+            license_files: vec![],
+            build_requires: vec![],
+        }
+    }
+
+    /// Create a [`ResolutionGraph`] from the given distributions, with edges and roots given as
+    /// indices into `dists`.
+    pub(crate) fn resolution_graph(
+        dists: Vec<AnnotatedDist>,
+        edges: &[(usize, usize)],
+        roots: &[usize],
+    ) -> ResolutionGraph {
+        let mut petgraph = petgraph::graph::Graph::new();
+        let indices = dists
+            .into_iter()
+            .map(|dist| petgraph.add_node(dist))
+            .collect::<Vec<_>>();
+        for (source, target) in edges {
+            petgraph.add_edge(indices[*source], indices[*target], Range::full());
+        }
+        let roots = roots
+            .iter()
+            .map(|root| (indices[*root], None))
+            .collect::<FxHashMap<_, _>>();
+        ResolutionGraph {
+            petgraph,
+            roots,
+            editables: Editables::default(),
+            diagnostics: vec![],
+        }
+    }
+
+    /// Create a CPython 3.12 [`MarkerEnvironment`] for the given platform.
+    pub(crate) fn marker_environment(
+        sys_platform: &str,
+        platform_system: &str,
+    ) -> MarkerEnvironment {
+        MarkerEnvironment::try_from(MarkerEnvironmentBuilder {
+            implementation_name: "cpython",
+            implementation_version: "3.12.1",
+            os_name: if sys_platform == "win32" {
+                "nt"
+            } else {
+                "posix"
+            },
+            platform_machine: "x86_64",
+            platform_python_implementation: "CPython",
+            platform_release: "",
+            platform_system,
+            platform_version: "",
+            python_full_version: "3.12.1",
+            python_version: "3.12",
+            sys_platform,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn to_pep508_registry() {
+        let mut dist = registry_dist("anyio", "4.3.0", &[]);
+        dist.extras = vec![
+            ExtraName::from_str("trio").unwrap(),
+            ExtraName::from_str("doc").unwrap(),
+            ExtraName::from_str("trio").unwrap(),
+        ];
+
+        let requirement = dist.to_pep508(true).unwrap();
+        assert_eq!(requirement.to_string(), "anyio[doc,trio]==4.3.0");
+        assert!(requirement.marker.is_none());
+
+        let requirement = dist.to_pep508(false).unwrap();
+        assert_eq!(requirement.to_string(), "anyio==4.3.0");
+    }
+
+    #[test]
+    fn to_pep508_path() {
+        // Relative paths can't be represented with PEP 508.
+        let dist = path_dist("./flask-3.0.3-py3-none-any.whl", &["extra1"]);
+        assert!(dist.to_pep508(true).is_none());
+        let dist = path_dist("file:./flask-3.0.3-py3-none-any.whl", &[]);
+        assert!(dist.to_pep508(true).is_none());
+
+        // Absolute paths can.
+        let dist = path_dist(
+            "file:///home/user/flask-3.0.3-py3-none-any.whl",
+            &["extra1"],
+        );
+        let requirement = dist.to_pep508(true).unwrap();
+        assert_eq!(requirement.name, PackageName::from_str("flask").unwrap());
+        assert_eq!(
+            requirement.extras,
+            vec![ExtraName::from_str("extra1").unwrap()]
+        );
+        assert!(matches!(
+            requirement.version_or_url,
+            Some(pep508_rs::VersionOrUrl::Url(_))
+        ));
+    }
+
+    #[test]
+    fn requirements_txt_relative_file_url_extras() {
+        let dist = path_dist("file:./flask-3.0.3-py3-none-any.whl", &["extra2", "extra1"]);
+        assert_eq!(
+            dist.to_requirements_txt(true, &[], &UrlRenderers::default()),
+            "file:./flask-3.0.3-py3-none-any.whl[extra1, extra2]"
+        );
+    }
+
+    #[test]
+    fn requirements_txt_file_url_without_root_extras() {
+        let dist = path_dist(
+            "file://flask-3.0.3-py3-none-any.whl",
+            &["extra2", "extra1", "extra2"],
+        );
+        assert_eq!(
+            dist.to_requirements_txt(true, &[], &UrlRenderers::default()),
+            "flask-3.0.3-py3-none-any.whl[extra1, extra2]"
+        );
+    }
+
+    #[test]
+    fn requirements_txt_windows_path_extras() {
+        let dist = path_dist(
+            "C:\\Users\\user\\flask-3.0.3-py3-none-any.whl",
+            &["extra2", "extra1"],
+        );
+        assert_eq!(
+            dist.to_requirements_txt(true, &[], &UrlRenderers::default()),
+            "C:\\Users\\user\\flask-3.0.3-py3-none-any.whl[extra1, extra2]"
+        );
+    }
+
+    #[test]
+    fn requirements_txt_relative_path_extras() {
+        let dist = path_dist("./flask-3.0.3-py3-none-any.whl", &["extra2", "extra1"]);
+        assert_eq!(
+            dist.to_requirements_txt(true, &[], &UrlRenderers::default()),
+            "./flask-3.0.3-py3-none-any.whl[extra1, extra2]"
+        );
+        assert_eq!(
+            dist.to_requirements_txt(false, &[], &UrlRenderers::default()),
+            "./flask-3.0.3-py3-none-any.whl"
+        );
+    }
+
+    #[test]
+    fn wheel_filename_tags() {
+        let dist = registry_dist("anyio", "4.3.0", &[]);
+        let filename = dist.dist.wheel_filename().unwrap();
+        assert_eq!(filename.python_tag, vec!["py3".to_string()]);
+        assert_eq!(filename.abi_tag, vec!["none".to_string()]);
+        assert_eq!(filename.platform_tag, vec!["any".to_string()]);
+
+        let dist = path_dist("./flask-3.0.3-py3-none-any.whl", &[]);
+        assert_eq!(
+            dist.dist.wheel_filename().unwrap().to_string(),
+            "flask-3.0.3-py3-none-any.whl"
+        );
+    }
+
+    #[test]
+    fn index_url() {
+        assert_eq!(
+            registry_dist("anyio", "4.3.0", &[])
+                .index_url()
+                .map(Url::as_str),
+            Some("https://pypi.org/simple")
+        );
+        assert_eq!(
+            path_dist("./flask-3.0.3-py3-none-any.whl", &[]).index_url(),
+            None
+        );
+        assert_eq!(
+            git_dist("git+https://github.com/pallets/flask.git@3.0.3").index_url(),
+            None
+        );
+    }
+
+    #[test]
+    fn source_kind() {
+        assert_eq!(
+            registry_dist("anyio", "4.3.0", &[]).source_kind(),
+            SourceKind::RegistryWheel
+        );
+        assert_eq!(
+            path_dist("./flask-3.0.3-py3-none-any.whl", &[]).source_kind(),
+            SourceKind::Path
+        );
+    }
+
+    #[test]
+    fn hash_by_algorithm() {
+        let mut dist = registry_dist("anyio", "4.3.0", &[]);
+        dist.hashes = vec![
+            HashDigest::from_str("sha512:abcd").unwrap(),
+            HashDigest::from_str("sha256:1234").unwrap(),
+            HashDigest::from_str("md5:5678").unwrap(),
+        ];
+
+        assert_eq!(
+            dist.hash(HashAlgorithm::Sha256).map(ToString::to_string),
+            Some("sha256:1234".to_string())
+        );
+        assert_eq!(dist.hash(HashAlgorithm::Sha384), None);
+
+        let hashes = dist.hashes_by_algorithm();
+        assert_eq!(
+            hashes.keys().copied().collect::<Vec<_>>(),
+            vec![
+                HashAlgorithm::Md5,
+                HashAlgorithm::Sha256,
+                HashAlgorithm::Sha512
+            ]
+        );
+        assert_eq!(hashes[&HashAlgorithm::Sha512].digest.as_ref(), "abcd");
+    }
+}
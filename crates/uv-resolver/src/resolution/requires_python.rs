@@ -0,0 +1,132 @@
+use pep440_rs::Version;
+
+use crate::error::RequiresPythonError;
+use crate::resolution::AnnotatedDist;
+
+/// Validate that the `Requires-Python` of every distribution includes the given Python version.
+///
+/// Distributions that don't declare a `Requires-Python` are compatible with any Python version.
+/// Otherwise, every incompatible distribution is reported in the returned error.
+pub(crate) fn validate_requires_python<'a>(
+    dists: impl IntoIterator<Item = &'a AnnotatedDist>,
+    python_version: &Version,
+) -> Result<(), RequiresPythonError> {
+    let dists = dists
+        .into_iter()
+        .filter_map(|dist| {
+            let requires_python = dist.metadata.requires_python.as_ref()?;
+            if requires_python.contains(python_version) {
+                None
+            } else {
+                Some((dist.dist.clone(), requires_python.clone()))
+            }
+        })
+        .collect::<Vec<_>>();
+    if dists.is_empty() {
+        Ok(())
+    } else {
+        Err(RequiresPythonError::Incompatible {
+            python_version: python_version.clone(),
+            dists,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use distribution_filename::WheelFilename;
+    use distribution_types::{
+        BuiltDist, Dist, File, FileLocation, IndexUrl, RegistryBuiltDist, RegistryBuiltWheel,
+    };
+    use pep440_rs::{Version, VersionSpecifiers};
+    use pypi_types::Metadata23;
+    use uv_normalize::PackageName;
+
+    use crate::error::RequiresPythonError;
+    use crate::resolution::AnnotatedDist;
+
+    use super::validate_requires_python;
+
+    /// Create an [`AnnotatedDist`] for a wheel from PyPI with the given `Requires-Python`.
+    fn dist(name: &str, version: &str, requires_python: Option<&str>) -> AnnotatedDist {
+        let filename =
+            WheelFilename::from_str(&format!("{name}-{version}-py3-none-any.whl")).unwrap();
+        let file = File {
+            dist_info_metadata: false,
+            filename: filename.to_string(),
+            hashes: vec![],
+            requires_python: None,
+            size: None,
+            upload_time_utc_ms: None,
+            url: FileLocation::AbsoluteUrl(format!(
+                "https://files.pythonhosted.org/packages/{filename}"
+            )),
+            yanked: None,
+        };
+        let dist = Dist::Built(BuiltDist::Registry(RegistryBuiltDist {
+            wheels: vec![RegistryBuiltWheel {
+                filename,
+                file: Box::new(file),
+                index: IndexUrl::from_str("https://pypi.org/simple").unwrap(),
+            }],
+            best_wheel_index: 0,
+            sdist: None,
+        }));
+        let metadata = Metadata23 {
+            name: PackageName::from_str(name).unwrap(),
+            version: Version::from_str(version).unwrap(),
+            requires_dist: vec![],
+            requires_python: requires_python
+                .map(|requires_python| VersionSpecifiers::from_str(requires_python).unwrap()),
+            provides_extras: vec![],
+            license_expression: None,
+            license_files: vec![],
+            build_requires: vec![],
+        };
+        AnnotatedDist {
+            dist: dist.into(),
+            extras: vec![],
+            hashes: vec![],
+            metadata,
+        }
+    }
+
+    #[test]
+    fn compatible() {
+        let dists = [
+            dist("anyio", "4.3.0", Some(">=3.8")),
+            dist("idna", "3.6", None),
+        ];
+        let python_version = Version::from_str("3.12.2").unwrap();
+        assert!(validate_requires_python(&dists, &python_version).is_ok());
+    }
+
+    #[test]
+    fn incompatible() {
+        let dists = [
+            dist("anyio", "4.3.0", Some(">=3.8")),
+            dist("idna", "3.6", None),
+            dist("trio", "0.25.0", Some(">=3.8, <3.12")),
+        ];
+        let python_version = Version::from_str("3.12.2").unwrap();
+        let err = validate_requires_python(&dists, &python_version).unwrap_err();
+        assert!(matches!(
+            &err,
+            RequiresPythonError::Incompatible { python_version, .. }
+                if python_version.to_string() == "3.12.2"
+        ));
+        assert_eq!(
+            err.dists()
+                .iter()
+                .map(|(dist, requires_python)| format!("{dist} ({requires_python})"))
+                .collect::<Vec<_>>(),
+            vec!["trio==0.25.0 (>=3.8, <3.12)"]
+        );
+        assert_eq!(
+            err.to_string(),
+            "The resolution contains packages that are incompatible with the target Python version (3.12.2):\n- trio==0.25.0 requires Python >=3.8, <3.12"
+        );
+    }
+}
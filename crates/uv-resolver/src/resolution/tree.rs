@@ -0,0 +1,184 @@
+use std::fmt::Display;
+use std::hash::Hash;
+
+use rustc_hash::FxHashSet;
+
+/// A [`std::fmt::Display`] implementation for a dependency tree, in which each package is followed
+/// by its dependencies, indented beneath it (e.g., `├── click==8.1.7`).
+///
+/// Each package is expanded at most once; subsequent occurrences of a package with dependencies
+/// are marked with `(*)`, which also prevents infinite recursion on cycles.
+pub struct DependencyTree<N, C, L> {
+    /// The packages at which to root the tree.
+    roots: Vec<N>,
+    /// Returns the (sorted) dependencies of a package.
+    children: C,
+    /// Returns the label for a package, given whether it's rendered as a root.
+    label: L,
+}
+
+impl<N, C, L> DependencyTree<N, C, L>
+where
+    N: Copy + Eq + Hash,
+    C: Fn(N) -> Vec<N>,
+    L: Fn(N, bool) -> String,
+{
+    /// Create a tree rooted at the given packages.
+    pub fn new(roots: Vec<N>, children: C, label: L) -> Self {
+        Self {
+            roots,
+            children,
+            label,
+        }
+    }
+
+    /// Create a tree rooted at the packages in `nodes` that no other package depends on.
+    ///
+    /// Packages that aren't reachable from any such root (i.e., packages that are only depended
+    /// on from within a cycle) are rooted at the first package of the cycle in `nodes`, such that
+    /// every package is rendered.
+    pub fn from_nodes(nodes: &[N], children: C, label: L) -> Self {
+        let dependencies = nodes
+            .iter()
+            .flat_map(|node| children(*node))
+            .collect::<FxHashSet<_>>();
+        let mut roots = nodes
+            .iter()
+            .copied()
+            .filter(|node| !dependencies.contains(node))
+            .collect::<Vec<_>>();
+
+        // Any remaining packages are only depended on from within a cycle, so root the tree at the
+        // first package of each such cycle.
+        let mut reachable = FxHashSet::default();
+        extend_reachable(roots.iter().copied(), &children, &mut reachable);
+        for node in nodes {
+            if reachable.contains(node) {
+                continue;
+            }
+            let mut cycle = FxHashSet::default();
+            extend_reachable(children(*node), &children, &mut cycle);
+            if cycle.contains(node) {
+                roots.push(*node);
+                reachable.extend(cycle);
+            }
+        }
+
+        // Render the roots in the order of `nodes`.
+        let roots = roots.into_iter().collect::<FxHashSet<_>>();
+        let roots = nodes
+            .iter()
+            .copied()
+            .filter(|node| roots.contains(node))
+            .collect();
+
+        Self {
+            roots,
+            children,
+            label,
+        }
+    }
+
+    /// Write a single package in the tree, along with its (unvisited) dependencies.
+    fn fmt_node(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        node: N,
+        prefix: &str,
+        connector: Option<bool>,
+        visited: &mut FxHashSet<N>,
+    ) -> std::fmt::Result {
+        let children = (self.children)(node);
+
+        // Render the package itself, e.g., `├── click==8.1.7`.
+        let mut line = match connector {
+            None => String::new(),
+            Some(true) => format!("{prefix}└── "),
+            Some(false) => format!("{prefix}├── "),
+        };
+        line.push_str(&(self.label)(node, connector.is_none()));
+        let expand = visited.insert(node);
+        if !expand && !children.is_empty() {
+            line.push_str(" (*)");
+        }
+        writeln!(f, "{line}")?;
+
+        if !expand {
+            return Ok(());
+        }
+
+        // Render the dependencies, indented beneath the package.
+        let prefix = match connector {
+            None => String::new(),
+            Some(true) => format!("{prefix}    "),
+            Some(false) => format!("{prefix}│   "),
+        };
+        let len = children.len();
+        for (position, child) in children.into_iter().enumerate() {
+            self.fmt_node(f, child, &prefix, Some(position + 1 == len), visited)?;
+        }
+        Ok(())
+    }
+}
+
+impl<N, C, L> Display for DependencyTree<N, C, L>
+where
+    N: Copy + Eq + Hash,
+    C: Fn(N) -> Vec<N>,
+    L: Fn(N, bool) -> String,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut visited = FxHashSet::default();
+        for root in &self.roots {
+            self.fmt_node(f, *root, "", None, &mut visited)?;
+        }
+        Ok(())
+    }
+}
+
+/// Add the packages reachable from `nodes` (including `nodes` themselves) to `reachable`.
+fn extend_reachable<N: Copy + Eq + Hash>(
+    nodes: impl IntoIterator<Item = N>,
+    children: impl Fn(N) -> Vec<N>,
+    reachable: &mut FxHashSet<N>,
+) {
+    let mut stack = nodes.into_iter().collect::<Vec<_>>();
+    while let Some(node) = stack.pop() {
+        if reachable.insert(node) {
+            stack.extend(children(node));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cycle() {
+        // `a` depends on `b`, while `c` and `d` only depend on each other (and `d` on `b`).
+        let edges: &[(&str, &[&str])] =
+            &[("a", &["b"]), ("b", &[]), ("c", &["d"]), ("d", &["b", "c"])];
+        let nodes = edges.iter().map(|(node, _)| *node).collect::<Vec<_>>();
+        let tree = DependencyTree::from_nodes(
+            &nodes,
+            |node| {
+                edges
+                    .iter()
+                    .find(|(name, _)| *name == node)
+                    .map(|(_, children)| children.to_vec())
+                    .unwrap_or_default()
+            },
+            |node, _| node.to_string(),
+        );
+        assert_eq!(
+            tree.to_string(),
+            "a\n\
+             └── b\n\
+             c\n\
+             └── d\n\
+             \x20   ├── b\n\
+             \x20   └── c (*)\n"
+        );
+    }
+}
@@ -93,6 +93,8 @@ struct ResolverState<InstalledPackages: InstalledPackagesProvider> {
     /// When not set, the resolver is in "universal" mode.
     markers: Option<MarkerEnvironment>,
     python_requirement: PythonRequirement,
+    /// Whether to validate the `Requires-Python` of every resolved distribution.
+    validate_requires_python: bool,
     selector: CandidateSelector,
     index: InMemoryIndex,
     installed_packages: InstalledPackages,
@@ -146,6 +148,7 @@ impl<'a, Context: BuildContext, InstalledPackages: InstalledPackagesProvider>
             AllowedYanks::from_manifest(&manifest, markers, options.dependency_mode),
             hasher,
             options.exclude_newer,
+            options.exclude_newer_packages.clone(),
             build_context.no_binary(),
             build_context.no_build(),
         );
@@ -182,7 +185,7 @@ impl<Provider: ResolverProvider, InstalledPackages: InstalledPackagesProvider>
             index: index.clone(),
             unavailable_packages: DashMap::default(),
             incomplete_packages: DashMap::default(),
-            selector: CandidateSelector::for_resolution(options, &manifest, markers),
+            selector: CandidateSelector::for_resolution(&options, &manifest, markers),
             dependency_mode: options.dependency_mode,
             urls: Urls::from_manifest(&manifest, markers, options.dependency_mode)?,
             locals: Locals::from_manifest(&manifest, markers, options.dependency_mode),
@@ -196,6 +199,7 @@ impl<Provider: ResolverProvider, InstalledPackages: InstalledPackagesProvider>
             hasher: hasher.clone(),
             markers: markers.cloned(),
             python_requirement: python_requirement.clone(),
+            validate_requires_python: options.validate_requires_python,
             reporter: None,
             installed_packages,
         };
@@ -344,6 +348,10 @@ impl<InstalledPackages: InstalledPackagesProvider> ResolverState<InstalledPackag
                     &state.pubgrub,
                     &self.preferences,
                     self.editables.clone(),
+                    &self.requirements,
+                    self.markers
+                        .as_ref()
+                        .filter(|_| self.validate_requires_python),
                 );
             };
             state.next = highest_priority_pkg;
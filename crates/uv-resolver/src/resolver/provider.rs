@@ -1,6 +1,7 @@
 use std::future::Future;
 
 use anyhow::Result;
+use rustc_hash::FxHashMap;
 
 use distribution_types::{Dist, IndexLocations};
 use platform_tags::Tags;
@@ -81,6 +82,7 @@ pub struct DefaultResolverProvider<'a, Context: BuildContext> {
     allowed_yanks: AllowedYanks,
     hasher: HashStrategy,
     exclude_newer: Option<ExcludeNewer>,
+    exclude_newer_packages: FxHashMap<PackageName, ExcludeNewer>,
     no_binary: NoBinary,
     no_build: NoBuild,
 }
@@ -96,6 +98,7 @@ impl<'a, Context: BuildContext> DefaultResolverProvider<'a, Context> {
         allowed_yanks: AllowedYanks,
         hasher: &'a HashStrategy,
         exclude_newer: Option<ExcludeNewer>,
+        exclude_newer_packages: FxHashMap<PackageName, ExcludeNewer>,
         no_binary: &'a NoBinary,
         no_build: &'a NoBuild,
     ) -> Self {
@@ -107,6 +110,7 @@ impl<'a, Context: BuildContext> DefaultResolverProvider<'a, Context> {
             allowed_yanks,
             hasher: hasher.clone(),
             exclude_newer,
+            exclude_newer_packages,
             no_binary: no_binary.clone(),
             no_build: no_build.clone(),
         }
@@ -125,6 +129,12 @@ impl<'a, Context: BuildContext> ResolverProvider for DefaultResolverProvider<'a,
             .managed(|client| client.simple(package_name))
             .await;
 
+        // A per-package timestamp takes precedence over the global timestamp.
+        let exclude_newer = self
+            .exclude_newer_packages
+            .get(package_name)
+            .or(self.exclude_newer.as_ref());
+
         match result {
             Ok(results) => Ok(VersionsResponse::Found(
                 results
@@ -138,7 +148,7 @@ impl<'a, Context: BuildContext> ResolverProvider for DefaultResolverProvider<'a,
                             &self.python_requirement,
                             &self.allowed_yanks,
                             &self.hasher,
-                            self.exclude_newer.as_ref(),
+                            exclude_newer,
                             self.flat_index.get(package_name).cloned(),
                             &self.no_binary,
                             &self.no_build,
@@ -163,7 +173,7 @@ impl<'a, Context: BuildContext> ResolverProvider for DefaultResolverProvider<'a,
                         Ok(VersionsResponse::NoIndex)
                     }
                 }
-                uv_client::ErrorKind::Offline(_) => {
+                uv_client::ErrorKind::Offline(_) | uv_client::ErrorKind::OfflineRequest(_) => {
                     if let Some(flat_index) = self.flat_index.get(package_name).cloned() {
                         Ok(VersionsResponse::Found(vec![VersionMap::from(flat_index)]))
                     } else {
@@ -185,7 +195,9 @@ impl<'a, Context: BuildContext> ResolverProvider for DefaultResolverProvider<'a,
             Ok(metadata) => Ok(MetadataResponse::Found(metadata)),
             Err(err) => match err {
                 uv_distribution::Error::Client(client) => match client.into_kind() {
-                    uv_client::ErrorKind::Offline(_) => Ok(MetadataResponse::Offline),
+                    uv_client::ErrorKind::Offline(_) | uv_client::ErrorKind::OfflineRequest(_) => {
+                        Ok(MetadataResponse::Offline)
+                    }
                     uv_client::ErrorKind::MetadataParseError(_, _, err) => {
                         Ok(MetadataResponse::InvalidMetadata(err))
                     }
@@ -9,6 +9,7 @@ use std::str::FromStr;
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use once_cell::sync::Lazy;
+use rustc_hash::FxHashMap;
 
 use distribution_types::{CachedDist, IndexLocations, Requirement, Resolution, SourceDist};
 use pep440_rs::Version;
@@ -195,6 +196,40 @@ async fn black() -> Result<()> {
     Ok(())
 }
 
+/// Resolve `black` with an earlier upload-time cutoff for `black` itself, which should take
+/// precedence over the global cutoff.
+#[tokio::test]
+async fn black_exclude_newer_package() -> Result<()> {
+    let manifest = Manifest::simple(vec![Requirement::from(
+        pep508_rs::Requirement::from_str("black<=23.9.1").unwrap(),
+    )]);
+    let options = OptionsBuilder::new()
+        .exclude_newer(Some(*EXCLUDE_NEWER))
+        .exclude_newer_packages(FxHashMap::from_iter([(
+            PackageName::from_str("black")?,
+            ExcludeNewer::from_str("2023-09-01").unwrap(),
+        )]))
+        .build();
+
+    let resolution = resolve(manifest, options, &MARKERS_311, &TAGS_311).await?;
+
+    assert_snapshot!(DisplayResolutionGraph::from(&resolution), @r###"
+    black==23.7.0
+    click==8.1.7
+        # via black
+    mypy-extensions==1.0.0
+        # via black
+    packaging==23.2
+        # via black
+    pathspec==0.11.2
+        # via black
+    platformdirs==4.0.0
+        # via black
+    "###);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn black_colorama() -> Result<()> {
     let manifest = Manifest::simple(vec![Requirement::from(
@@ -619,6 +654,34 @@ async fn pylint_disallow_prerelease() -> Result<()> {
     Ok(())
 }
 
+/// Resolve `pylint==2.3.0` with pre-releases disallowed, except for `isort`, which should be
+/// resolved to a pre-release even though it's only a transitive dependency.
+#[tokio::test]
+async fn pylint_disallow_prerelease_except_package() -> Result<()> {
+    let manifest = Manifest::simple(vec![Requirement::from(
+        pep508_rs::Requirement::from_str("pylint==2.3.0").unwrap(),
+    )]);
+    let options = OptionsBuilder::new()
+        .prerelease_mode(PreReleaseMode::Disallow)
+        .prerelease_packages(vec![PackageName::from_str("isort")?])
+        .exclude_newer(Some(*EXCLUDE_NEWER))
+        .build();
+
+    let resolution = resolve(manifest, options, &MARKERS_311, &TAGS_311).await?;
+
+    assert_snapshot!(DisplayResolutionGraph::from(&resolution), @r###"
+    astroid==3.0.1
+        # via pylint
+    isort==6.0.0b2
+        # via pylint
+    mccabe==0.7.0
+        # via pylint
+    pylint==2.3.0
+    "###);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn pylint_allow_prerelease() -> Result<()> {
     let manifest = Manifest::simple(vec![Requirement::from(
@@ -8,7 +8,7 @@ use distribution_types::{
     UnresolvedRequirement,
 };
 use pep508_rs::MarkerEnvironment;
-use pypi_types::{HashDigest, HashError};
+use pypi_types::{HashAlgorithm, HashDigest, HashError};
 use uv_normalize::PackageName;
 
 #[derive(Debug, Default, Clone)]
@@ -16,11 +16,15 @@ pub enum HashStrategy {
     /// No hash policy is specified.
     #[default]
     None,
-    /// Hashes should be generated (specifically, a SHA-256 hash), but not validated.
-    Generate,
+    /// Hashes should be generated using the given algorithms (e.g., SHA-256), but not validated.
+    Generate(Vec<HashAlgorithm>),
     /// Hashes should be validated against a pre-defined list of hashes. If necessary, hashes should
     /// be generated so as to ensure that the archive is valid.
     Validate(FxHashMap<PackageId, Vec<HashDigest>>),
+    /// Hashes should be validated against a pre-defined list of hashes for any distribution that
+    /// has them, while distributions without hashes are allowed (e.g., when installing from a
+    /// lockfile in which only some packages are hashed).
+    Verify(FxHashMap<PackageId, Vec<HashDigest>>),
 }
 
 impl HashStrategy {
@@ -28,13 +32,17 @@ impl HashStrategy {
     pub fn get<T: DistributionMetadata>(&self, distribution: &T) -> HashPolicy {
         match self {
             Self::None => HashPolicy::None,
-            Self::Generate => HashPolicy::Generate,
+            Self::Generate(algorithms) => HashPolicy::Generate(algorithms),
             Self::Validate(hashes) => HashPolicy::Validate(
                 hashes
                     .get(&distribution.package_id())
                     .map(Vec::as_slice)
                     .unwrap_or_default(),
             ),
+            Self::Verify(hashes) => hashes
+                .get(&distribution.package_id())
+                .map(Vec::as_slice)
+                .map_or(HashPolicy::None, HashPolicy::Validate),
         }
     }
 
@@ -42,13 +50,17 @@ impl HashStrategy {
     pub fn get_package(&self, name: &PackageName) -> HashPolicy {
         match self {
             Self::None => HashPolicy::None,
-            Self::Generate => HashPolicy::Generate,
+            Self::Generate(algorithms) => HashPolicy::Generate(algorithms),
             Self::Validate(hashes) => HashPolicy::Validate(
                 hashes
                     .get(&PackageId::from_registry(name.clone()))
                     .map(Vec::as_slice)
                     .unwrap_or_default(),
             ),
+            Self::Verify(hashes) => hashes
+                .get(&PackageId::from_registry(name.clone()))
+                .map(Vec::as_slice)
+                .map_or(HashPolicy::None, HashPolicy::Validate),
         }
     }
 
@@ -56,13 +68,17 @@ impl HashStrategy {
     pub fn get_url(&self, url: &Url) -> HashPolicy {
         match self {
             Self::None => HashPolicy::None,
-            Self::Generate => HashPolicy::Generate,
+            Self::Generate(algorithms) => HashPolicy::Generate(algorithms),
             Self::Validate(hashes) => HashPolicy::Validate(
                 hashes
                     .get(&PackageId::from_url(url))
                     .map(Vec::as_slice)
                     .unwrap_or_default(),
             ),
+            Self::Verify(hashes) => hashes
+                .get(&PackageId::from_url(url))
+                .map(Vec::as_slice)
+                .map_or(HashPolicy::None, HashPolicy::Validate),
         }
     }
 
@@ -70,8 +86,9 @@ impl HashStrategy {
     pub fn allows_package(&self, name: &PackageName) -> bool {
         match self {
             Self::None => true,
-            Self::Generate => true,
+            Self::Generate(_) => true,
             Self::Validate(hashes) => hashes.contains_key(&PackageId::from_registry(name.clone())),
+            Self::Verify(_) => true,
         }
     }
 
@@ -79,8 +96,9 @@ impl HashStrategy {
     pub fn allows_url(&self, url: &Url) -> bool {
         match self {
             Self::None => true,
-            Self::Generate => true,
+            Self::Generate(_) => true,
             Self::Validate(hashes) => hashes.contains_key(&PackageId::from_url(url)),
+            Self::Verify(_) => true,
         }
     }
 
@@ -130,6 +148,44 @@ impl HashStrategy {
 
         Ok(Self::Validate(hashes))
     }
+
+    /// Collect the hashes from a set of [`UnresolvedRequirement`] entries, such that they're
+    /// verified for any distribution that provides them (e.g., the requirements read from a
+    /// lockfile).
+    ///
+    /// Unlike [`HashStrategy::from_requirements`], requirements without hashes are allowed.
+    pub fn verify_requirements<'a>(
+        requirements: impl Iterator<Item = (&'a UnresolvedRequirement, &'a [String])>,
+        markers: Option<&MarkerEnvironment>,
+    ) -> Result<Self, HashStrategyError> {
+        let mut hashes = FxHashMap::<PackageId, Vec<HashDigest>>::default();
+
+        for (requirement, digests) in requirements {
+            if digests.is_empty() || !requirement.evaluate_markers(markers, &[]) {
+                continue;
+            }
+
+            // Every hashed requirement must be either a pinned version or a direct URL.
+            let id = match &requirement {
+                UnresolvedRequirement::Named(requirement) => {
+                    uv_requirement_to_package_id(requirement)?
+                }
+                UnresolvedRequirement::Unnamed(requirement) => {
+                    PackageId::from_url(&requirement.url.verbatim)
+                }
+            };
+
+            // Parse the hashes.
+            let digests = digests
+                .iter()
+                .map(|digest| HashDigest::from_str(digest))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            hashes.insert(id, digests);
+        }
+
+        Ok(Self::Verify(hashes))
+    }
 }
 
 fn uv_requirement_to_package_id(requirement: &Requirement) -> Result<PackageId, HashStrategyError> {
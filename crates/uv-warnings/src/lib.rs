@@ -1,5 +1,6 @@
 use std::sync::atomic::AtomicBool;
 use std::sync::Mutex;
+use std::time::Duration;
 
 // macro hygiene: The user might not have direct dependencies on those crates
 #[doc(hidden)]
@@ -17,6 +18,22 @@ pub fn enable() {
     ENABLED.store(true, std::sync::atomic::Ordering::SeqCst);
 }
 
+/// Format a duration as a human-readable string, Cargo-style (e.g., `1.30s` or `450ms`).
+pub fn elapsed(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let ms = duration.subsec_millis();
+
+    if secs >= 60 {
+        format!("{}m {:02}s", secs / 60, secs % 60)
+    } else if secs > 0 {
+        format!("{}.{:02}s", secs, duration.subsec_nanos() / 10_000_000)
+    } else if ms > 0 {
+        format!("{ms}ms")
+    } else {
+        format!("0.{:02}ms", duration.subsec_nanos() / 10_000)
+    }
+}
+
 /// Warn a user, if warnings are enabled.
 #[macro_export]
 macro_rules! warn_user {
@@ -97,6 +97,9 @@ impl Combine for PipOptions {
             config_settings: self.config_settings.combine(other.config_settings),
             python_version: self.python_version.combine(other.python_version),
             python_platform: self.python_platform.combine(other.python_platform),
+            platform_independent: self
+                .platform_independent
+                .combine(other.platform_independent),
             exclude_newer: self.exclude_newer.combine(other.exclude_newer),
             no_emit_package: self.no_emit_package.combine(other.no_emit_package),
             emit_index_url: self.emit_index_url.combine(other.emit_index_url),
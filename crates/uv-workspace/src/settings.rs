@@ -75,6 +75,7 @@ pub struct PipOptions {
     pub config_settings: Option<ConfigSettings>,
     pub python_version: Option<PythonVersion>,
     pub python_platform: Option<TargetTriple>,
+    pub platform_independent: Option<bool>,
     pub exclude_newer: Option<ExcludeNewer>,
     pub no_emit_package: Option<Vec<PackageName>>,
     pub emit_index_url: Option<bool>,
@@ -6,16 +6,21 @@ use anyhow::Result;
 
 use clap::{Args, Parser, Subcommand};
 
-use distribution_types::{FlatIndexLocation, IndexUrl};
+use distribution_types::{FlatIndexLocation, IndexUrl, Requirement};
+use pypi_types::{HashAlgorithm, VerbatimParsedUrl};
 use uv_cache::CacheArgs;
 use uv_configuration::{
     ConfigSettingEntry, IndexStrategy, KeyringProviderType, PackageNameSpecifier, TargetTriple,
 };
 use uv_interpreter::PythonVersion;
 use uv_normalize::{ExtraName, PackageName};
-use uv_resolver::{AnnotationStyle, ExcludeNewer, PreReleaseMode, ResolutionMode};
+use uv_resolver::{
+    AnnotationStyle, ExcludeNewer, ExcludeNewerPackage, PreReleaseMode, ResolutionMode,
+};
 
-use crate::commands::{extra_name_with_clap_error, ListFormat, VersionFormat};
+use crate::commands::{
+    extra_name_with_clap_error, CompileFormat, ExportFormat, ListFormat, VersionFormat,
+};
 use crate::compat;
 
 #[derive(Parser)]
@@ -151,6 +156,9 @@ pub(crate) enum Commands {
     /// Resolve the project requirements into a lockfile.
     #[clap(hide = true)]
     Lock(LockArgs),
+    /// Export the project's lockfile to an alternate format.
+    #[clap(hide = true)]
+    Export(ExportArgs),
     /// Display uv's version
     Version {
         #[arg(long, value_enum, default_value = "text")]
@@ -222,6 +230,8 @@ pub(crate) enum PipCommand {
     Show(PipShowArgs),
     /// Verify installed packages have compatible dependencies.
     Check(PipCheckArgs),
+    /// Display the installed packages in the current environment as a dependency tree.
+    Tree(PipTreeArgs),
 }
 
 /// A re-implementation of `Option`, used to avoid Clap's automatic `Option` flattening in
@@ -265,6 +275,26 @@ fn parse_file_path(input: &str) -> Result<Maybe<PathBuf>, String> {
     }
 }
 
+/// Parse a string into a [`Requirement`], e.g., `requests` or `requests<2.32`.
+fn parse_requirement(input: &str) -> Result<Requirement, String> {
+    match pep508_rs::Requirement::<VerbatimParsedUrl>::from_str(input) {
+        Ok(requirement) => Ok(Requirement::from(requirement)),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+/// Parse a string into a [`HashAlgorithm`], rejecting algorithms that aren't accepted by `--hash`.
+fn parse_hash_algorithm(input: &str) -> Result<HashAlgorithm, String> {
+    match HashAlgorithm::from_str(input) {
+        Ok(HashAlgorithm::Md5) => Err(
+            "MD5 hashes are not supported (expected one of: `sha256`, `sha384`, or `sha512`)"
+                .to_string(),
+        ),
+        Ok(algorithm) => Ok(algorithm),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
 #[derive(Args)]
 #[allow(clippy::struct_excessive_bools)]
 pub(crate) struct PipCompileArgs {
@@ -333,6 +363,11 @@ pub(crate) struct PipCompileArgs {
     #[arg(long, hide = true)]
     pub(crate) pre: bool,
 
+    /// Allow pre-release versions for a specific package, regardless of the `--prerelease`
+    /// strategy (e.g., to accept a release candidate of a single package).
+    #[arg(long)]
+    pub(crate) prerelease_package: Vec<PackageName>,
+
     /// Write the compiled requirements to the given `requirements.txt` file.
     #[arg(long, short)]
     pub(crate) output_file: Option<PathBuf>,
@@ -494,8 +529,11 @@ pub(crate) struct PipCompileArgs {
 
     /// Allow upgrades for a specific package, ignoring pinned versions in the existing output
     /// file.
-    #[arg(long, short = 'P')]
-    pub(crate) upgrade_package: Vec<PackageName>,
+    ///
+    /// Accepts a version specifier to constrain the upgrade (e.g., `--upgrade-package
+    /// "requests<2.32"`), while preserving all other pins.
+    #[arg(long, short = 'P', value_parser = parse_requirement)]
+    pub(crate) upgrade_package: Vec<Requirement>,
 
     /// Include distribution hashes in the output file.
     #[arg(long, overrides_with("no_generate_hashes"))]
@@ -504,6 +542,16 @@ pub(crate) struct PipCompileArgs {
     #[arg(long, overrides_with("generate_hashes"), hide = true)]
     pub(crate) no_generate_hashes: bool,
 
+    /// The algorithm(s) to use for the hashes included by `--generate-hashes` (one of: `sha256`,
+    /// `sha384`, or `sha512`).
+    ///
+    /// May be provided multiple times to include a hash for each algorithm. Defaults to `sha256`.
+    /// For packages from an index that doesn't provide hashes for the requested algorithms (e.g.,
+    /// PyPI, which only provides SHA-256 hashes), the resolved distribution is downloaded to
+    /// compute them.
+    #[arg(long, value_parser = parse_hash_algorithm)]
+    pub(crate) hash_algorithm: Vec<HashAlgorithm>,
+
     /// Use legacy `setuptools` behavior when building source distributions without a
     /// `pyproject.toml`.
     #[arg(long, overrides_with("no_legacy_setup_py"))]
@@ -575,6 +623,20 @@ pub(crate) struct PipCompileArgs {
     #[arg(long)]
     pub(crate) python_platform: Option<TargetTriple>,
 
+    /// Retain dependencies that only apply on other platforms, annotating them with their markers
+    /// in the output (e.g., `colorama==0.4.6 ; sys_platform == 'win32'`).
+    ///
+    /// Each package is resolved to a single version across all platforms; the resolution doesn't
+    /// fork on markers, so it fails if different platforms require incompatible versions of the
+    /// same package. The Python version and platform tags are still taken from the target
+    /// interpreter (or `--python-version` and `--python-platform`), so distributions are only
+    /// selected if they're compatible with it.
+    #[arg(long, overrides_with("no_platform_independent"))]
+    pub(crate) platform_independent: bool,
+
+    #[arg(long, overrides_with("platform_independent"), hide = true)]
+    pub(crate) no_platform_independent: bool,
+
     /// Limit candidate packages to those that were uploaded prior to the given date.
     ///
     /// Accepts both RFC 3339 timestamps (e.g., `2006-12-02T02:07:43Z`) and UTC dates in the same
@@ -582,6 +644,14 @@ pub(crate) struct PipCompileArgs {
     #[arg(long)]
     pub(crate) exclude_newer: Option<ExcludeNewer>,
 
+    /// Limit candidate versions of a specific package to those that were uploaded prior to the
+    /// given date, overriding `--exclude-newer` for that package.
+    ///
+    /// Accepts package-date pairs in the format `PACKAGE=DATE` (e.g., `numpy=2024-01-01`), where
+    /// the date is in any format accepted by `--exclude-newer`.
+    #[arg(long)]
+    pub(crate) exclude_newer_package: Vec<ExcludeNewerPackage>,
+
     /// Specify a package to omit from the output resolution. Its dependencies will still be
     /// included in the resolution. Equivalent to pip-compile's `--unsafe-package` option.
     #[arg(long, alias = "unsafe-package")]
@@ -621,6 +691,17 @@ pub(crate) struct PipCompileArgs {
     #[arg(long, overrides_with("emit_index_annotation"), hide = true)]
     pub(crate) no_emit_index_annotation: bool,
 
+    /// Select the output format between: `requirements-txt` (default), `json`, or `dot`.
+    ///
+    /// The `json` and `dot` formats omit the header and annotation comments.
+    #[arg(long, value_enum, default_value_t = CompileFormat::default())]
+    pub(crate) format: CompileFormat,
+
+    /// When resolution fails, explain the failure as a tree of every incompatibility that led to
+    /// it, in addition to the summary (e.g., to track down a deep transitive conflict).
+    #[arg(long)]
+    pub(crate) explain: bool,
+
     #[arg(long, overrides_with("no_unstable_uv_lock_file"), hide = true)]
     pub(crate) unstable_uv_lock_file: bool,
 
@@ -940,6 +1021,14 @@ pub(crate) struct PipSyncArgs {
     #[arg(long)]
     pub(crate) exclude_newer: Option<ExcludeNewer>,
 
+    /// Limit candidate versions of a specific package to those that were uploaded prior to the
+    /// given date, overriding `--exclude-newer` for that package.
+    ///
+    /// Accepts package-date pairs in the format `PACKAGE=DATE` (e.g., `numpy=2024-01-01`), where
+    /// the date is in any format accepted by `--exclude-newer`.
+    #[arg(long)]
+    pub(crate) exclude_newer_package: Vec<ExcludeNewerPackage>,
+
     /// Perform a dry run, i.e., don't actually install anything but resolve the dependencies and
     /// print the resulting plan.
     #[arg(long)]
@@ -1006,8 +1095,11 @@ pub(crate) struct PipInstallArgs {
     pub(crate) no_upgrade: bool,
 
     /// Allow upgrade of a specific package.
-    #[arg(long, short = 'P')]
-    pub(crate) upgrade_package: Vec<PackageName>,
+    ///
+    /// Accepts a version specifier to constrain the upgrade (e.g., `--upgrade-package
+    /// "requests<2.32"`).
+    #[arg(long, short = 'P', value_parser = parse_requirement)]
+    pub(crate) upgrade_package: Vec<Requirement>,
 
     /// Reinstall all packages, regardless of whether they're already installed.
     #[arg(long, alias = "force-reinstall", overrides_with("no_reinstall"))]
@@ -1069,6 +1161,11 @@ pub(crate) struct PipInstallArgs {
     #[arg(long, hide = true)]
     pub(crate) pre: bool,
 
+    /// Allow pre-release versions for a specific package, regardless of the `--prerelease`
+    /// strategy (e.g., to accept a release candidate of a single package).
+    #[arg(long)]
+    pub(crate) prerelease_package: Vec<PackageName>,
+
     /// The URL of the Python package index (by default: <https://pypi.org/simple>).
     ///
     /// The index given by this flag is given lower priority than all other
@@ -1331,6 +1428,11 @@ pub(crate) struct PipInstallArgs {
     #[arg(long)]
     pub(crate) dry_run: bool,
 
+    /// When resolution fails, explain the failure as a tree of every incompatibility that led to
+    /// it, in addition to the summary (e.g., to track down a deep transitive conflict).
+    #[arg(long)]
+    pub(crate) explain: bool,
+
     #[arg(long, hide = true, group = "sources")]
     pub(crate) unstable_uv_lock_file: Option<String>,
 
@@ -1618,6 +1720,62 @@ pub(crate) struct PipShowArgs {
     pub(crate) no_system: bool,
 }
 
+#[derive(Args)]
+#[allow(clippy::struct_excessive_bools)]
+pub(crate) struct PipTreeArgs {
+    /// Root the tree at the given package(s), rather than at every package that isn't required by
+    /// another installed package.
+    #[arg(long)]
+    pub(crate) package: Vec<PackageName>,
+
+    /// Show the reverse dependencies of each package, i.e., the packages that require it, rather
+    /// than its dependencies.
+    #[arg(long)]
+    pub(crate) invert: bool,
+
+    /// Validate the virtual environment, to detect packages with missing dependencies or other
+    /// issues.
+    #[arg(long, overrides_with("no_strict"))]
+    pub(crate) strict: bool,
+
+    #[arg(long, overrides_with("strict"), hide = true)]
+    pub(crate) no_strict: bool,
+
+    /// The Python interpreter for which packages should be listed.
+    ///
+    /// By default, `uv` lists packages in the currently activated virtual environment, or a virtual
+    /// environment (`.venv`) located in the current working directory or any parent directory,
+    /// falling back to the system Python if no virtual environment is found.
+    ///
+    /// Supported formats:
+    /// - `3.10` looks for an installed Python 3.10 using `py --list-paths` on Windows, or
+    ///   `python3.10` on Linux and macOS.
+    /// - `python3.10` or `python.exe` looks for a binary with the given name in `PATH`.
+    /// - `/home/ferris/.local/bin/python3.10` uses the exact Python at the given path.
+    #[arg(long, short, env = "UV_PYTHON", verbatim_doc_comment)]
+    pub(crate) python: Option<String>,
+
+    /// List packages for the system Python.
+    ///
+    /// By default, `uv` lists packages in the currently activated virtual environment, or a virtual
+    /// environment (`.venv`) located in the current working directory or any parent directory,
+    /// falling back to the system Python if no virtual environment is found. The `--system` option
+    /// instructs `uv` to use the first Python found in the system `PATH`.
+    ///
+    /// WARNING: `--system` is intended for use in continuous integration (CI) environments and
+    /// should be used with caution.
+    #[arg(
+        long,
+        env = "UV_SYSTEM_PYTHON",
+        value_parser = clap::builder::BoolishValueParser::new(),
+        overrides_with("no_system")
+    )]
+    pub(crate) system: bool,
+
+    #[arg(long, overrides_with("system"))]
+    pub(crate) no_system: bool,
+}
+
 #[derive(Args)]
 #[allow(clippy::struct_excessive_bools)]
 pub(crate) struct VenvArgs {
@@ -1826,6 +1984,20 @@ pub(crate) struct LockArgs {
     pub(crate) python: Option<String>,
 }
 
+#[derive(Args)]
+pub(crate) struct ExportArgs {
+    /// The format to which the lockfile should be exported.
+    #[arg(long, value_enum, default_value_t = ExportFormat::default())]
+    pub(crate) format: ExportFormat,
+
+    /// Write the exported lockfile to the given file, rather than to stdout.
+    ///
+    /// Any local paths in the lockfile are written relative to the directory containing the output
+    /// file.
+    #[arg(long, short)]
+    pub(crate) output_file: Option<PathBuf>,
+}
+
 #[derive(Args)]
 #[allow(clippy::struct_excessive_bools)]
 struct AddArgs {
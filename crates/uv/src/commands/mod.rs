@@ -1,4 +1,3 @@
-use std::time::Duration;
 use std::{fmt::Display, fmt::Write, process::ExitCode};
 
 use anyhow::Context;
@@ -15,7 +14,9 @@ pub(crate) use pip::install::pip_install;
 pub(crate) use pip::list::pip_list;
 pub(crate) use pip::show::pip_show;
 pub(crate) use pip::sync::pip_sync;
+pub(crate) use pip::tree::pip_tree;
 pub(crate) use pip::uninstall::pip_uninstall;
+pub(crate) use project::export::export;
 pub(crate) use project::lock::lock;
 pub(crate) use project::run::run;
 pub(crate) use project::sync::sync;
@@ -27,6 +28,7 @@ use uv_fs::Simplified;
 use uv_installer::compile_tree;
 use uv_interpreter::PythonEnvironment;
 use uv_normalize::PackageName;
+pub(super) use uv_warnings::elapsed;
 pub(crate) use venv::venv;
 pub(crate) use version::version;
 
@@ -67,22 +69,6 @@ impl From<ExitStatus> for ExitCode {
     }
 }
 
-/// Format a duration as a human-readable string, Cargo-style.
-pub(super) fn elapsed(duration: Duration) -> String {
-    let secs = duration.as_secs();
-    let ms = duration.subsec_millis();
-
-    if secs >= 60 {
-        format!("{}m {:02}s", secs / 60, secs % 60)
-    } else if secs > 0 {
-        format!("{}.{:02}s", secs, duration.subsec_nanos() / 10_000_000)
-    } else if ms > 0 {
-        format!("{ms}ms")
-    } else {
-        format!("0.{:02}ms", duration.subsec_nanos() / 10_000)
-    }
-}
-
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
 pub(super) enum ChangeEventKind {
     /// The package was removed from the environment.
@@ -122,6 +108,27 @@ pub(crate) enum ListFormat {
     Json,
 }
 
+#[derive(Debug, Default, Clone, Copy, clap::ValueEnum)]
+pub(crate) enum CompileFormat {
+    /// Write the resolution as a `requirements.txt` file.
+    #[default]
+    RequirementsTxt,
+    /// Write the resolution in a machine-readable JSON format, with one entry per package,
+    /// including its version, hashes, markers, and dependencies.
+    Json,
+    /// Write the resolution as a Graphviz DOT graph, with one node per package and one edge per
+    /// dependency.
+    Dot,
+}
+
+#[derive(Debug, Default, Clone, Copy, clap::ValueEnum)]
+pub(crate) enum ExportFormat {
+    /// Export the lockfile as a PEP 751 `pylock.toml` file.
+    #[default]
+    #[value(name = "pylock.toml")]
+    PylockToml,
+}
+
 /// Compile all Python source files in site-packages to bytecode, to speed up the
 /// initial run of any subsequent executions.
 ///
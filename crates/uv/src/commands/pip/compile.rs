@@ -12,15 +12,18 @@ use fs_err as fs;
 use indexmap::IndexMap;
 use itertools::Itertools;
 use owo_colors::OwoColorize;
+use rustc_hash::FxHashMap;
 use tempfile::tempdir_in;
 use tracing::debug;
 
 use distribution_types::{
-    IndexLocations, LocalEditable, LocalEditables, SourceAnnotation, SourceAnnotations, Verbatim,
+    Dist, HashPolicy, Hashed, IndexLocations, LocalEditable, LocalEditables, Name, ResolvedDist,
+    SourceAnnotation, SourceAnnotations, Verbatim,
 };
 use distribution_types::{Requirement, Requirements};
 use install_wheel_rs::linker::LinkMode;
 use platform_tags::Tags;
+use pypi_types::{HashAlgorithm, HashDigest};
 use requirements_txt::EditableRequirement;
 use uv_auth::store_credentials_from_url;
 use uv_cache::Cache;
@@ -41,20 +44,21 @@ use uv_interpreter::{
 use uv_interpreter::{PythonVersion, SourceSelector};
 use uv_normalize::{ExtraName, PackageName};
 use uv_requirements::{
-    upgrade::read_lockfile, ExtrasSpecification, LookaheadResolver, NamedRequirementsResolver,
-    RequirementsSource, RequirementsSpecification, SourceTreeResolver,
+    upgrade::{apply_upgrade, read_pins},
+    ExtrasSpecification, LookaheadResolver, NamedRequirementsResolver, RequirementsSource,
+    RequirementsSpecification, SourceTreeResolver,
 };
 use uv_resolver::{
     AnnotationStyle, BuiltEditableMetadata, DependencyMode, DisplayResolutionGraph, ExcludeNewer,
     Exclusions, FlatIndex, InMemoryIndex, Manifest, OptionsBuilder, PreReleaseMode,
-    PythonRequirement, ResolutionMode, Resolver,
+    PythonRequirement, RequirementsTxtOptions, ResolutionGraph, ResolutionMode, Resolver,
 };
 use uv_types::{BuildIsolation, EmptyInstalledPackages, HashStrategy, InFlight};
 use uv_warnings::warn_user;
 
 use crate::commands::pip::operations;
 use crate::commands::reporters::{DownloadReporter, ResolverReporter};
-use crate::commands::{elapsed, ExitStatus};
+use crate::commands::{elapsed, CompileFormat, ExitStatus};
 use crate::printer::Printer;
 
 /// Resolve a set of requirements into a set of pinned versions.
@@ -67,9 +71,11 @@ pub(crate) async fn pip_compile(
     output_file: Option<&Path>,
     resolution_mode: ResolutionMode,
     prerelease_mode: PreReleaseMode,
+    prerelease_packages: Vec<PackageName>,
     dependency_mode: DependencyMode,
     upgrade: Upgrade,
     generate_hashes: bool,
+    hash_algorithms: Vec<HashAlgorithm>,
     no_emit_packages: Vec<PackageName>,
     include_extras: bool,
     include_annotations: bool,
@@ -90,11 +96,15 @@ pub(crate) async fn pip_compile(
     python_version: Option<PythonVersion>,
     python_platform: Option<TargetTriple>,
     exclude_newer: Option<ExcludeNewer>,
+    exclude_newer_packages: FxHashMap<PackageName, ExcludeNewer>,
     annotation_style: AnnotationStyle,
     link_mode: LinkMode,
     python: Option<String>,
     system: bool,
     concurrency: Concurrency,
+    platform_independent: bool,
+    format: CompileFormat,
+    explain: bool,
     uv_lock: bool,
     native_tls: bool,
     quiet: bool,
@@ -257,9 +267,13 @@ pub(crate) async fn pip_compile(
         (None, None) => Cow::Borrowed(interpreter.markers()),
     };
     // The marker environment to use for evaluating requirements. When
-    // `uv_lock` is enabled, we specifically do environment independent marker
+    // `platform_independent` or `uv_lock` is enabled, we specifically do environment independent marker
     // evaluation. (i.e., Only consider extras.)
-    let marker_filter = if uv_lock { None } else { Some(&*markers) };
+    let marker_filter = if platform_independent || uv_lock {
+        None
+    } else {
+        Some(&*markers)
+    };
     // The Python requirement in "workspace-aware uv" should, I believe, come
     // from the pyproject.toml. For now, we just take it from the markers
     // (which does have its Python version set potentially from the CLI, which
@@ -268,8 +282,13 @@ pub(crate) async fn pip_compile(
     let python_requirement = PythonRequirement::from_marker_environment(&interpreter, &markers);
 
     // Generate, but don't enforce hashes for the requirements.
+    let hash_algorithms = if hash_algorithms.is_empty() {
+        vec![HashAlgorithm::Sha256]
+    } else {
+        hash_algorithms
+    };
     let hasher = if generate_hashes {
-        HashStrategy::Generate
+        HashStrategy::Generate(hash_algorithms.clone())
     } else {
         HashStrategy::None
     };
@@ -294,8 +313,14 @@ pub(crate) async fn pip_compile(
         .platform(interpreter.platform())
         .build();
 
-    // Read the lockfile, if present.
-    let preferences = read_lockfile(output_file, upgrade).await?;
+    // Read the pins from the existing output file, if present. Unless upgrading, the pinned
+    // versions are preferred, such that only the pins that are forced to change are updated.
+    // Machine-readable outputs can't be read back as requirements, and so are never pinned.
+    let pins = match format {
+        CompileFormat::RequirementsTxt => read_pins(output_file).await?,
+        CompileFormat::Json | CompileFormat::Dot => Vec::new(),
+    };
+    let preferences = apply_upgrade(pins.clone(), upgrade.clone());
 
     // Resolve the flat indexes from `--find-links`.
     let flat_index = {
@@ -335,7 +360,12 @@ pub(crate) async fn pip_compile(
         &NoBinary::None,
         concurrency,
     )
-    .with_options(OptionsBuilder::new().exclude_newer(exclude_newer).build());
+    .with_options(
+        OptionsBuilder::new()
+            .exclude_newer(exclude_newer)
+            .exclude_newer_packages(exclude_newer_packages.clone())
+            .build(),
+    );
 
     // Resolve the requirements from the provided sources.
     let requirements = {
@@ -428,8 +458,13 @@ pub(crate) async fn pip_compile(
         }
     }
 
-    // Collect constraints and overrides.
-    let constraints = Constraints::from_requirements(constraints);
+    // Collect constraints and overrides, including any bounds passed to `--upgrade-package`.
+    let constraints = Constraints::from_requirements(
+        constraints
+            .into_iter()
+            .chain(upgrade.constraints().cloned())
+            .collect(),
+    );
     let overrides = Overrides::from_requirements(overrides);
 
     // Build the editables and add their requirements
@@ -546,8 +581,10 @@ pub(crate) async fn pip_compile(
     let options = OptionsBuilder::new()
         .resolution_mode(resolution_mode)
         .prerelease_mode(prerelease_mode)
+        .prerelease_packages(prerelease_packages)
         .dependency_mode(dependency_mode)
         .exclude_newer(exclude_newer)
+        .exclude_newer_packages(exclude_newer_packages)
         .index_strategy(index_strategy)
         .build();
 
@@ -572,11 +609,32 @@ pub(crate) async fn pip_compile(
             let report = miette::Report::msg(format!("{err}"))
                 .context("No solution found when resolving dependencies:");
             eprint!("{report:?}");
+            if explain {
+                writeln!(
+                    printer.stderr(),
+                    "\n{}\n{}",
+                    "Explanation:".bold(),
+                    err.explain()
+                )?;
+            }
             return Ok(ExitStatus::Failure);
         }
         result => result,
     }?;
 
+    // Compute any requested hashes that the index didn't provide (e.g., PyPI only provides
+    // SHA-256 hashes), then retain only the requested hashes.
+    let resolution = if generate_hashes {
+        let database = DistributionDatabase::new(&client, &build_dispatch, concurrency.downloads);
+        let hashes =
+            generate_missing_hashes(&resolution, &hash_algorithms, &tags, &database).await?;
+        resolution
+            .with_hashes(&hashes)
+            .with_hash_algorithms(&hash_algorithms)
+    } else {
+        resolution
+    };
+
     let s = if resolution.len() == 1 { "" } else { "s" };
     writeln!(
         printer.stderr(),
@@ -589,9 +647,87 @@ pub(crate) async fn pip_compile(
         .dimmed()
     )?;
 
+    // Report any pins that changed relative to the existing output file.
+    if let Some(output_file) = output_file.filter(|_| !pins.is_empty()) {
+        let diff = resolution.pin_diff(&pins);
+        if !diff.is_empty() {
+            writeln!(
+                printer.stderr(),
+                "{}",
+                format!("Updated pins in {}:", output_file.user_display()).dimmed()
+            )?;
+            for (name, version) in &diff.removed {
+                writeln!(
+                    printer.stderr(),
+                    " {} {}{}",
+                    "-".red(),
+                    name.bold(),
+                    format!("=={version}").dimmed()
+                )?;
+            }
+            for (name, previous, version) in &diff.changed {
+                let reason = if upgrade.contains(name) {
+                    "upgrade requested"
+                } else {
+                    "existing pin is incompatible"
+                };
+                writeln!(
+                    printer.stderr(),
+                    " {} {}{} {}",
+                    "~".yellow(),
+                    name.bold(),
+                    format!("=={previous} -> {version}").dimmed(),
+                    format!("({reason})").dimmed()
+                )?;
+            }
+            for (name, version) in &diff.added {
+                writeln!(
+                    printer.stderr(),
+                    " {} {}{}",
+                    "+".green(),
+                    name.bold(),
+                    format!("=={version}").dimmed()
+                )?;
+            }
+        }
+    }
+
+    // Log the hosts that were contacted to produce the resolution, including redirect targets.
+    let contacted_hosts = client.uncached_client().contacted_hosts();
+    if !contacted_hosts.is_empty() {
+        debug!("Contacted hosts: {}", contacted_hosts.join(", "));
+    }
+
+    if uv_lock {
+        let lock = resolution.lock()?;
+        let encoded = toml::to_string_pretty(&lock)?;
+        fs::tokio::write("uv.lock", encoded.as_bytes()).await?;
+    }
+
     // Write the resolved dependencies to the output channel.
     let mut writer = OutputWriter::new(!quiet || output_file.is_none(), output_file)?;
 
+    // Machine-readable formats are written as-is, without the header or any annotations.
+    let export = match format {
+        CompileFormat::RequirementsTxt => None,
+        CompileFormat::Json => {
+            let mut buffer = Vec::new();
+            resolution.write_json(&mut buffer, &no_emit_packages)?;
+            buffer.push(b'\n');
+            Some(buffer)
+        }
+        CompileFormat::Dot => {
+            let mut buffer = Vec::new();
+            resolution.write_dot(&mut buffer, &no_emit_packages)?;
+            Some(buffer)
+        }
+    };
+    if let Some(export) = export {
+        write!(writer, "{}", String::from_utf8(export)?)?;
+        operations::diagnose_resolution(resolution.diagnostics(), printer)?;
+        return Ok(ExitStatus::Success);
+    }
+
     if include_header {
         writeln!(
             writer,
@@ -623,52 +759,27 @@ pub(crate) async fn pip_compile(
         writeln!(writer, "{}", format!("#    {relevant_markers}").green())?;
     }
 
-    if uv_lock {
-        let lock = resolution.lock()?;
-        let encoded = toml::to_string_pretty(&lock)?;
-        fs::tokio::write("uv.lock", encoded.as_bytes()).await?;
-    }
-
-    // Write the index locations to the output channel.
-    let mut wrote_index = false;
-
-    // If necessary, include the `--index-url` and `--extra-index-url` locations.
-    if include_index_url {
-        if let Some(index) = index_locations.index() {
-            writeln!(writer, "--index-url {}", index.verbatim())?;
-            wrote_index = true;
-        }
-        for extra_index in index_locations.extra_index() {
-            writeln!(writer, "--extra-index-url {}", extra_index.verbatim())?;
-            wrote_index = true;
-        }
-    }
-
-    // If necessary, include the `--find-links` locations.
-    if include_find_links {
-        for flat_index in index_locations.flat_index() {
-            writeln!(writer, "--find-links {flat_index}")?;
-            wrote_index = true;
-        }
-    }
-
-    // If we wrote an index, add a newline to separate it from the requirements
-    if wrote_index {
-        writeln!(writer)?;
-    }
-
     write!(
         writer,
         "{}",
         DisplayResolutionGraph::new(
             &resolution,
-            &no_emit_packages,
-            generate_hashes,
-            include_extras,
-            include_annotations,
-            include_index_annotation,
-            annotation_style,
-            sources,
+            RequirementsTxtOptions {
+                no_emit_packages: &no_emit_packages,
+                include_extras,
+                include_hashes: generate_hashes,
+                include_annotations,
+                annotation_style,
+                include_index_annotation,
+                sources,
+                index_locations: Some(&index_locations),
+                include_index_url,
+                // The default index is only written if explicitly requested.
+                include_default_index: include_index_url,
+                include_find_links,
+                include_markers: platform_independent,
+                ..RequirementsTxtOptions::default()
+            },
         )
     )?;
 
@@ -695,6 +806,59 @@ pub(crate) async fn pip_compile(
     Ok(ExitStatus::Success)
 }
 
+/// Compute the hashes for the given algorithms for each package from an index that doesn't
+/// provide them, by downloading (or, for source distributions, fetching) the resolved
+/// distribution.
+///
+/// Packages from a direct URL are hashed during resolution, and so are omitted.
+async fn generate_missing_hashes(
+    resolution: &ResolutionGraph,
+    algorithms: &[HashAlgorithm],
+    tags: &Tags,
+    database: &DistributionDatabase<'_, BuildDispatch<'_>>,
+) -> Result<FxHashMap<PackageName, Vec<HashDigest>>> {
+    let mut hashes = FxHashMap::default();
+    for annotated in resolution.dists() {
+        if annotated.dist.index().is_none() {
+            continue;
+        }
+        if algorithms.iter().all(|algorithm| {
+            annotated
+                .hashes
+                .iter()
+                .any(|hash| hash.algorithm == *algorithm)
+        }) {
+            continue;
+        }
+        let ResolvedDist::Installable(dist) = &annotated.dist else {
+            continue;
+        };
+
+        debug!(
+            "Computing {} hashes for: {dist}",
+            algorithms.iter().join(", ")
+        );
+        let policy = HashPolicy::Generate(algorithms);
+        let digests = match dist {
+            Dist::Built(_) => database
+                .get_or_build_wheel(dist, tags, policy)
+                .await
+                .with_context(|| format!("Failed to compute hashes for: {dist}"))?
+                .hashes()
+                .to_vec(),
+            Dist::Source(_) => {
+                database
+                    .get_or_build_wheel_metadata(dist, policy)
+                    .await
+                    .with_context(|| format!("Failed to compute hashes for: {dist}"))?
+                    .hashes
+            }
+        };
+        hashes.insert(dist.name().clone(), digests);
+    }
+    Ok(hashes)
+}
+
 /// Format the `uv` command used to generate the output file.
 #[allow(clippy::fn_params_excessive_bools)]
 fn cmd(
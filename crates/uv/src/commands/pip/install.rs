@@ -6,6 +6,7 @@ use distribution_types::{IndexLocations, Resolution};
 use fs_err as fs;
 use itertools::Itertools;
 use owo_colors::OwoColorize;
+use rustc_hash::FxHashMap;
 use tracing::{debug, enabled, Level};
 
 use install_wheel_rs::linker::LinkMode;
@@ -45,6 +46,7 @@ pub(crate) async fn pip_install(
     extras: &ExtrasSpecification,
     resolution_mode: ResolutionMode,
     prerelease_mode: PreReleaseMode,
+    prerelease_packages: Vec<PackageName>,
     dependency_mode: DependencyMode,
     upgrade: Upgrade,
     index_locations: IndexLocations,
@@ -64,11 +66,13 @@ pub(crate) async fn pip_install(
     python_platform: Option<TargetTriple>,
     strict: bool,
     exclude_newer: Option<ExcludeNewer>,
+    exclude_newer_packages: FxHashMap<PackageName, ExcludeNewer>,
     python: Option<String>,
     system: bool,
     break_system_packages: bool,
     target: Option<Target>,
     concurrency: Concurrency,
+    explain: bool,
     uv_lock: Option<String>,
     native_tls: bool,
     preview: PreviewMode,
@@ -83,6 +87,16 @@ pub(crate) async fn pip_install(
         .native_tls(native_tls)
         .keyring(keyring_provider);
 
+    // If the requirements come solely from a `pylock.toml`, install its locked artifacts directly,
+    // rather than re-resolving.
+    let pylock = match (requirements, constraints, overrides) {
+        ([RequirementsSource::PylockToml(path)], [], []) => Some(path.clone()),
+        _ => None,
+    };
+
+    // Any hashes provided by a lockfile are verified, even without `--require-hashes`.
+    let lockfile = requirements.iter().any(RequirementsSource::is_lockfile);
+
     // Read all requirements from the provided sources.
     let RequirementsSpecification {
         project,
@@ -258,6 +272,14 @@ pub(crate) async fn pip_install(
                 .map(|entry| (&entry.requirement, entry.hashes.as_slice())),
             Some(&markers),
         )?
+    } else if lockfile {
+        HashStrategy::verify_requirements(
+            requirements
+                .iter()
+                .chain(overrides.iter())
+                .map(|entry| (&entry.requirement, entry.hashes.as_slice())),
+            Some(&markers),
+        )?
     } else {
         HashStrategy::None
     };
@@ -323,7 +345,12 @@ pub(crate) async fn pip_install(
         &no_binary,
         concurrency,
     )
-    .with_options(OptionsBuilder::new().exclude_newer(exclude_newer).build());
+    .with_options(
+        OptionsBuilder::new()
+            .exclude_newer(exclude_newer)
+            .exclude_newer_packages(exclude_newer_packages.clone())
+            .build(),
+    );
 
     // Build all editable distributions. The editables are shared between resolution and
     // installation, and should live for the duration of the command.
@@ -350,12 +377,16 @@ pub(crate) async fn pip_install(
         let encoded = fs::tokio::read_to_string("uv.lock").await?;
         let lock: Lock = toml::from_str(&encoded)?;
         lock.to_resolution(&markers, &tags, &root)
+    } else if let Some(pylock) = pylock {
+        operations::read_pylock(&pylock, &markers, &tags).await?
     } else {
         let options = OptionsBuilder::new()
             .resolution_mode(resolution_mode)
             .prerelease_mode(prerelease_mode)
+            .prerelease_packages(prerelease_packages)
             .dependency_mode(dependency_mode)
             .exclude_newer(exclude_newer)
+            .exclude_newer_packages(exclude_newer_packages.clone())
             .index_strategy(index_strategy)
             .build();
 
@@ -373,7 +404,7 @@ pub(crate) async fn pip_install(
             &upgrade,
             &interpreter,
             &tags,
-            &markers,
+            Some(&markers),
             &client,
             &flat_index,
             &index,
@@ -389,6 +420,14 @@ pub(crate) async fn pip_install(
                 let report = miette::Report::msg(format!("{err}"))
                     .context("No solution found when resolving dependencies:");
                 eprint!("{report:?}");
+                if explain {
+                    writeln!(
+                        printer.stderr(),
+                        "\n{}\n{}",
+                        "Explanation:".bold(),
+                        err.explain()
+                    )?;
+                }
                 return Ok(ExitStatus::Failure);
             }
             Err(err) => return Err(err.into()),
@@ -419,7 +458,12 @@ pub(crate) async fn pip_install(
             &no_binary,
             concurrency,
         )
-        .with_options(OptionsBuilder::new().exclude_newer(exclude_newer).build())
+        .with_options(
+            OptionsBuilder::new()
+                .exclude_newer(exclude_newer)
+                .exclude_newer_packages(exclude_newer_packages)
+                .build(),
+        )
     };
 
     // Sync the environment.
@@ -1,7 +1,7 @@
 //! Common operations shared across the `pip` API and subcommands.
 
 use std::fmt::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::{anyhow, Context};
 use itertools::Itertools;
@@ -35,7 +35,7 @@ use uv_requirements::{
 };
 use uv_resolver::{
     DependencyMode, Exclusions, FlatIndex, InMemoryIndex, Manifest, Options, Preference,
-    PythonRequirement, ResolutionGraph, Resolver,
+    PylockToml, PythonRequirement, ResolutionGraph, Resolver,
 };
 use uv_types::{HashStrategy, InFlight, InstalledPackagesProvider};
 use uv_warnings::warn_user;
@@ -99,7 +99,28 @@ pub(crate) async fn read_requirements(
     Ok(spec)
 }
 
+/// Read the [`Resolution`] for the given environment from a `pylock.toml`, such that its locked
+/// artifacts are installed without re-resolving.
+pub(crate) async fn read_pylock(
+    path: &Path,
+    markers: &MarkerEnvironment,
+    tags: &Tags,
+) -> Result<Resolution, Error> {
+    let contents = fs_err::tokio::read_to_string(path).await?;
+    let pylock: PylockToml = toml::from_str(&contents)
+        .with_context(|| format!("Failed to parse: `{}`", path.user_display()))?;
+    let install_path = uv_fs::absolutize_path(path)?;
+    let install_path = install_path.parent().unwrap_or(&install_path);
+    let resolution = pylock
+        .to_resolution(install_path, markers, tags)
+        .with_context(|| format!("Failed to parse: `{}`", path.user_display()))?;
+    Ok(resolution)
+}
+
 /// Resolve a set of requirements, similar to running `pip compile`.
+///
+/// If no [`MarkerEnvironment`] is provided, the requirements are resolved universally (i.e., for
+/// all platforms), as with `pip compile --platform-independent`.
 #[allow(clippy::too_many_arguments)]
 pub(crate) async fn resolve<InstalledPackages: InstalledPackagesProvider>(
     requirements: Vec<UnresolvedRequirementSpecification>,
@@ -115,7 +136,7 @@ pub(crate) async fn resolve<InstalledPackages: InstalledPackagesProvider>(
     upgrade: &Upgrade,
     interpreter: &Interpreter,
     tags: &Tags,
-    markers: &MarkerEnvironment,
+    markers: Option<&MarkerEnvironment>,
     client: &RegistryClient,
     flat_index: &FlatIndex,
     index: &InMemoryIndex,
@@ -169,10 +190,18 @@ pub(crate) async fn resolve<InstalledPackages: InstalledPackagesProvider>(
     .resolve()
     .await?;
 
-    // Collect constraints and overrides.
-    let constraints = Constraints::from_requirements(constraints);
+    // Collect constraints and overrides, including any bounds passed to `--upgrade-package`.
+    let constraints = Constraints::from_requirements(
+        constraints
+            .into_iter()
+            .chain(upgrade.constraints().cloned())
+            .collect(),
+    );
     let overrides = Overrides::from_requirements(overrides);
-    let python_requirement = PythonRequirement::from_marker_environment(interpreter, markers);
+    let python_requirement = PythonRequirement::from_marker_environment(
+        interpreter,
+        markers.unwrap_or(interpreter.markers()),
+    );
 
     // Map the editables to their metadata.
     let editables = editables.as_metadata();
@@ -190,7 +219,7 @@ pub(crate) async fn resolve<InstalledPackages: InstalledPackagesProvider>(
                 DistributionDatabase::new(client, build_dispatch, concurrency.downloads),
             )
             .with_reporter(ResolverReporter::from(printer))
-            .resolve(Some(markers))
+            .resolve(markers)
             .await?
         }
         DependencyMode::Direct => Vec::new(),
@@ -232,7 +261,7 @@ pub(crate) async fn resolve<InstalledPackages: InstalledPackagesProvider>(
             manifest,
             options,
             &python_requirement,
-            Some(markers),
+            markers,
             tags,
             flat_index,
             index,
@@ -72,6 +72,16 @@ pub(crate) async fn pip_sync(
         .native_tls(native_tls)
         .keyring(keyring_provider);
 
+    // If the requirements come solely from a `pylock.toml`, install its locked artifacts directly,
+    // rather than re-resolving.
+    let pylock = match (requirements, constraints) {
+        ([RequirementsSource::PylockToml(path)], []) => Some(path.clone()),
+        _ => None,
+    };
+
+    // Any hashes provided by a lockfile are verified, even without `--require-hashes`.
+    let lockfile = requirements.iter().any(RequirementsSource::is_lockfile);
+
     // Initialize a few defaults.
     let overrides = &[];
     let extras = ExtrasSpecification::default();
@@ -206,6 +216,13 @@ pub(crate) async fn pip_sync(
                 .map(|entry| (&entry.requirement, entry.hashes.as_slice())),
             Some(&markers),
         )?
+    } else if lockfile {
+        HashStrategy::verify_requirements(
+            requirements
+                .iter()
+                .map(|entry| (&entry.requirement, entry.hashes.as_slice())),
+            Some(&markers),
+        )?
     } else {
         HashStrategy::None
     };
@@ -303,39 +320,43 @@ pub(crate) async fn pip_sync(
         .index_strategy(index_strategy)
         .build();
 
-    let resolution = match operations::resolve(
-        requirements,
-        constraints,
-        overrides,
-        source_trees,
-        project,
-        &extras,
-        &editables,
-        site_packages.clone(),
-        &hasher,
-        reinstall,
-        &upgrade,
-        interpreter,
-        &tags,
-        &markers,
-        &client,
-        &flat_index,
-        &index,
-        &resolve_dispatch,
-        concurrency,
-        options,
-        printer,
-    )
-    .await
-    {
-        Ok(resolution) => Resolution::from(resolution),
-        Err(operations::Error::Resolve(uv_resolver::ResolveError::NoSolution(err))) => {
-            let report = miette::Report::msg(format!("{err}"))
-                .context("No solution found when resolving dependencies:");
-            eprint!("{report:?}");
-            return Ok(ExitStatus::Failure);
+    let resolution = if let Some(pylock) = pylock {
+        operations::read_pylock(&pylock, &markers, &tags).await?
+    } else {
+        match operations::resolve(
+            requirements,
+            constraints,
+            overrides,
+            source_trees,
+            project,
+            &extras,
+            &editables,
+            site_packages.clone(),
+            &hasher,
+            reinstall,
+            &upgrade,
+            interpreter,
+            &tags,
+            Some(&markers),
+            &client,
+            &flat_index,
+            &index,
+            &resolve_dispatch,
+            concurrency,
+            options,
+            printer,
+        )
+        .await
+        {
+            Ok(resolution) => Resolution::from(resolution),
+            Err(operations::Error::Resolve(uv_resolver::ResolveError::NoSolution(err))) => {
+                let report = miette::Report::msg(format!("{err}"))
+                    .context("No solution found when resolving dependencies:");
+                eprint!("{report:?}");
+                return Ok(ExitStatus::Failure);
+            }
+            Err(err) => return Err(err.into()),
         }
-        Err(err) => return Err(err.into()),
     };
 
     // Re-initialize the in-flight map.
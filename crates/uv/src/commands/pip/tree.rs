@@ -0,0 +1,178 @@
+use std::fmt::Write;
+
+use anyhow::Result;
+use itertools::Itertools;
+use owo_colors::OwoColorize;
+use rustc_hash::FxHashMap;
+use tracing::debug;
+
+use distribution_types::{Diagnostic, Name};
+use uv_cache::Cache;
+use uv_configuration::PreviewMode;
+use uv_fs::Simplified;
+use uv_installer::SitePackages;
+use uv_interpreter::{PythonEnvironment, SystemPython};
+use uv_normalize::{ExtraName, PackageName};
+use uv_resolver::DependencyTree;
+
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+
+/// Display the installed packages in the current environment as a dependency tree.
+#[allow(clippy::fn_params_excessive_bools)]
+pub(crate) fn pip_tree(
+    mut packages: Vec<PackageName>,
+    invert: bool,
+    strict: bool,
+    python: Option<&str>,
+    system: bool,
+    preview: PreviewMode,
+    cache: &Cache,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    // Detect the current Python interpreter.
+    let system = if system {
+        SystemPython::Required
+    } else {
+        SystemPython::Allowed
+    };
+    let venv = PythonEnvironment::find(python, system, preview, cache)?;
+
+    debug!(
+        "Using Python {} environment at {}",
+        venv.interpreter().python_version(),
+        venv.python_executable().user_display().cyan()
+    );
+
+    // Build the installed index.
+    let site_packages = SitePackages::from_executable(&venv)?;
+
+    // Determine the markers to use for evaluating the requirements.
+    let markers = venv.interpreter().markers();
+
+    // Index the installed distributions by name, along with their requirements.
+    let distributions = site_packages
+        .iter()
+        .map(|dist| (dist.name(), dist))
+        .collect::<FxHashMap<_, _>>();
+    let requires_dist = distributions
+        .iter()
+        .map(|(name, dist)| {
+            let requires_dist = match dist.metadata() {
+                Ok(metadata) => metadata.requires_dist,
+                Err(err) => {
+                    debug!("Failed to read metadata for {name}: {err}");
+                    Vec::new()
+                }
+            };
+            (*name, requires_dist)
+        })
+        .collect::<FxHashMap<_, _>>();
+
+    // Determine the extras that are active for each package, i.e., those requested by the
+    // packages that depend on it. Since extras can enable requirements that activate further
+    // extras, iterate until a fixed point is reached.
+    let mut extras: FxHashMap<&PackageName, Vec<ExtraName>> = FxHashMap::default();
+    loop {
+        let mut changed = false;
+        for (name, requirements) in &requires_dist {
+            let active = extras.get(name).cloned().unwrap_or_default();
+            for requirement in requirements
+                .iter()
+                .filter(|req| req.evaluate_markers(markers, &active))
+            {
+                let Some((dependency, _)) = distributions.get_key_value(&requirement.name) else {
+                    continue;
+                };
+                let dependency_extras = extras.entry(*dependency).or_default();
+                for extra in &requirement.extras {
+                    if !dependency_extras.contains(extra) {
+                        dependency_extras.push(extra.clone());
+                        changed = true;
+                    }
+                }
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    // Determine the (installed) dependencies of each package, given its active extras.
+    let mut requires: FxHashMap<&PackageName, Vec<&PackageName>> = FxHashMap::default();
+    for (name, requirements) in &requires_dist {
+        let active = extras.get(name).map(Vec::as_slice).unwrap_or_default();
+        let edges = requirements
+            .iter()
+            .filter(|req| req.evaluate_markers(markers, active))
+            .filter_map(|req| distributions.get_key_value(&req.name).map(|(key, _)| *key))
+            .filter(|dependency| dependency != name)
+            .sorted_unstable()
+            .dedup()
+            .collect_vec();
+        requires.insert(*name, edges);
+    }
+
+    // When inverted, each package points to the packages that require it.
+    if invert {
+        let mut required_by: FxHashMap<&PackageName, Vec<&PackageName>> =
+            requires.keys().map(|name| (*name, Vec::new())).collect();
+        for (name, dependencies) in &requires {
+            for dependency in dependencies {
+                required_by.entry(*dependency).or_default().push(*name);
+            }
+        }
+        for dependents in required_by.values_mut() {
+            dependents.sort_unstable();
+        }
+        requires = required_by;
+    }
+
+    let children = |name: &PackageName| requires.get(name).cloned().unwrap_or_default();
+    let label =
+        |name: &PackageName, _: bool| format!("{}=={}", name, distributions[name].version());
+
+    // Root the tree at the requested packages, or at any packages that nothing else points to.
+    let tree = if packages.is_empty() {
+        let nodes = requires.keys().copied().sorted_unstable().collect_vec();
+        DependencyTree::from_nodes(&nodes, children, label)
+    } else {
+        packages.sort_unstable();
+        packages.dedup();
+
+        let (missing, roots): (Vec<_>, Vec<_>) = packages
+            .iter()
+            .partition(|name| !distributions.contains_key(name));
+        if !missing.is_empty() {
+            writeln!(
+                printer.stderr(),
+                "{}{} Package(s) not found for: {}",
+                "warning".yellow().bold(),
+                ":".bold(),
+                missing.iter().join(", ").bold()
+            )?;
+        }
+
+        // Like `pip show`, if no packages were found, return a failure.
+        if roots.is_empty() {
+            return Ok(ExitStatus::Failure);
+        }
+        DependencyTree::new(roots, children, label)
+    };
+    write!(printer.stdout(), "{tree}")?;
+
+    // Validate that the environment is consistent.
+    if strict {
+        for diagnostic in site_packages.diagnostics()? {
+            writeln!(
+                printer.stderr(),
+                "{}{} {}",
+                "warning".yellow().bold(),
+                ":".bold(),
+                diagnostic.message().bold()
+            )?;
+        }
+    }
+
+    Ok(ExitStatus::Success)
+}
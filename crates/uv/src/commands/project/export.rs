@@ -0,0 +1,62 @@
+use std::fmt::Write;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use uv_configuration::PreviewMode;
+use uv_fs::Simplified;
+use uv_requirements::ProjectWorkspace;
+use uv_resolver::Lock;
+use uv_warnings::warn_user;
+
+use crate::commands::{ExitStatus, ExportFormat};
+use crate::printer::Printer;
+
+/// Export the project's lockfile to an alternate format.
+pub(crate) async fn export(
+    format: ExportFormat,
+    output_file: Option<&Path>,
+    preview: PreviewMode,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    if preview.is_disabled() {
+        warn_user!("`uv export` is experimental and may change without warning.");
+    }
+
+    // Find the project.
+    let project = ProjectWorkspace::discover(std::env::current_dir()?)?;
+
+    // Read the lockfile.
+    let lock_path = project.workspace().root().join("uv.lock");
+    let lock: Lock = {
+        let encoded = fs_err::tokio::read_to_string(&lock_path)
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to read lockfile at `{}` (run `uv lock` to create it)",
+                    lock_path.user_display()
+                )
+            })?;
+        toml::from_str(&encoded)?
+    };
+
+    // Write any local paths relative to the directory that will contain the exported lockfile.
+    let install_path = match output_file {
+        Some(output_file) => uv_fs::absolutize_path(output_file)?
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default(),
+        None => std::env::current_dir()?,
+    };
+
+    let encoded = match format {
+        ExportFormat::PylockToml => toml::to_string_pretty(&lock.to_pylock(&install_path))?,
+    };
+
+    match output_file {
+        Some(output_file) => fs_err::tokio::write(output_file, encoded.as_bytes()).await?,
+        None => write!(printer.stdout(), "{encoded}")?,
+    }
+
+    Ok(ExitStatus::Success)
+}
@@ -120,7 +120,8 @@ pub(crate) async fn lock(
     )
     .await?;
 
-    // Resolve the requirements.
+    // Resolve the requirements. The lockfile is universal, so we resolve for all platforms rather
+    // than the current marker environment; the markers are evaluated at install time instead.
     let resolution = pip::operations::resolve(
         spec.requirements,
         spec.constraints,
@@ -135,7 +136,7 @@ pub(crate) async fn lock(
         &upgrade,
         &interpreter,
         tags,
-        markers,
+        None,
         &client,
         &flat_index,
         &index,
@@ -27,6 +27,7 @@ use uv_types::{BuildIsolation, HashStrategy, InFlight};
 use crate::editables::ResolvedEditables;
 use crate::printer::Printer;
 
+pub(crate) mod export;
 pub(crate) mod lock;
 pub(crate) mod run;
 pub(crate) mod sync;
@@ -229,7 +230,7 @@ pub(crate) async fn update_environment(
         &upgrade,
         &interpreter,
         tags,
-        markers,
+        Some(markers),
         &client,
         &flat_index,
         &index,
@@ -22,7 +22,8 @@ use crate::commands::ExitStatus;
 use crate::compat::CompatArgs;
 use crate::settings::{
     CacheSettings, GlobalSettings, PipCheckSettings, PipCompileSettings, PipFreezeSettings,
-    PipInstallSettings, PipListSettings, PipShowSettings, PipSyncSettings, PipUninstallSettings,
+    PipInstallSettings, PipListSettings, PipShowSettings, PipSyncSettings, PipTreeSettings,
+    PipUninstallSettings,
 };
 
 #[cfg(target_os = "windows")]
@@ -210,9 +211,11 @@ async fn run() -> Result<ExitStatus> {
                 args.shared.output_file.as_deref(),
                 args.shared.resolution,
                 args.shared.prerelease,
+                args.prerelease_package,
                 args.shared.dependency_mode,
                 args.upgrade,
                 args.shared.generate_hashes,
+                args.hash_algorithm,
                 args.shared.no_emit_package,
                 args.shared.no_strip_extras,
                 !args.shared.no_annotate,
@@ -233,11 +236,15 @@ async fn run() -> Result<ExitStatus> {
                 args.shared.python_version,
                 args.shared.python_platform,
                 args.shared.exclude_newer,
+                args.exclude_newer_package,
                 args.shared.annotation_style,
                 args.shared.link_mode,
                 args.shared.python,
                 args.shared.system,
                 args.shared.concurrency,
+                args.shared.platform_independent,
+                args.format,
+                args.explain,
                 args.uv_lock,
                 globals.native_tls,
                 globals.quiet,
@@ -349,6 +356,7 @@ async fn run() -> Result<ExitStatus> {
                 &args.shared.extras,
                 args.shared.resolution,
                 args.shared.prerelease,
+                args.prerelease_package,
                 args.shared.dependency_mode,
                 args.upgrade,
                 args.shared.index_locations,
@@ -368,11 +376,13 @@ async fn run() -> Result<ExitStatus> {
                 args.shared.python_platform,
                 args.shared.strict,
                 args.shared.exclude_newer,
+                args.exclude_newer_package,
                 args.shared.python,
                 args.shared.system,
                 args.shared.break_system_packages,
                 args.shared.target,
                 args.shared.concurrency,
+                args.explain,
                 args.uv_lock,
                 globals.native_tls,
                 globals.preview,
@@ -495,6 +505,26 @@ async fn run() -> Result<ExitStatus> {
                 printer,
             )
         }
+        Commands::Pip(PipNamespace {
+            command: PipCommand::Tree(args),
+        }) => {
+            // Resolve the settings from the command-line arguments and workspace configuration.
+            let args = PipTreeSettings::resolve(args, workspace);
+
+            // Initialize the cache.
+            let cache = cache.init()?;
+
+            commands::pip_tree(
+                args.package,
+                args.invert,
+                args.shared.strict,
+                args.shared.python.as_deref(),
+                args.shared.system,
+                globals.preview,
+                &cache,
+                printer,
+            )
+        }
         Commands::Cache(CacheNamespace {
             command: CacheCommand::Clean(args),
         })
@@ -604,6 +634,18 @@ async fn run() -> Result<ExitStatus> {
 
             commands::lock(globals.preview, &cache, printer).await
         }
+        Commands::Export(args) => {
+            // Resolve the settings from the command-line arguments and workspace configuration.
+            let args = settings::ExportSettings::resolve(args, workspace);
+
+            commands::export(
+                args.format,
+                args.output_file.as_deref(),
+                globals.preview,
+                printer,
+            )
+            .await
+        }
         #[cfg(feature = "self-update")]
         Commands::Self_(SelfNamespace {
             command: SelfCommand::Update,
@@ -7,6 +7,8 @@ use std::str::FromStr;
 
 use distribution_types::IndexLocations;
 use install_wheel_rs::linker::LinkMode;
+use pypi_types::HashAlgorithm;
+use rustc_hash::FxHashMap;
 use uv_cache::{CacheArgs, Refresh};
 use uv_client::Connectivity;
 use uv_configuration::{
@@ -20,11 +22,11 @@ use uv_resolver::{AnnotationStyle, DependencyMode, ExcludeNewer, PreReleaseMode,
 use uv_workspace::{Combine, PipOptions, Workspace};
 
 use crate::cli::{
-    ColorChoice, GlobalArgs, LockArgs, Maybe, PipCheckArgs, PipCompileArgs, PipFreezeArgs,
-    PipInstallArgs, PipListArgs, PipShowArgs, PipSyncArgs, PipUninstallArgs, RunArgs, SyncArgs,
-    VenvArgs,
+    ColorChoice, ExportArgs, GlobalArgs, LockArgs, Maybe, PipCheckArgs, PipCompileArgs,
+    PipFreezeArgs, PipInstallArgs, PipListArgs, PipShowArgs, PipSyncArgs, PipTreeArgs,
+    PipUninstallArgs, RunArgs, SyncArgs, VenvArgs,
 };
-use crate::commands::ListFormat;
+use crate::commands::{CompileFormat, ExportFormat, ListFormat};
 
 /// The resolved global settings to use for any invocation of the CLI.
 #[allow(clippy::struct_excessive_bools)]
@@ -168,6 +170,31 @@ impl LockSettings {
     }
 }
 
+/// The resolved settings to use for an `export` invocation.
+#[derive(Debug, Clone)]
+pub(crate) struct ExportSettings {
+    // CLI-only settings.
+    pub(crate) format: ExportFormat,
+    pub(crate) output_file: Option<PathBuf>,
+}
+
+impl ExportSettings {
+    /// Resolve the [`ExportSettings`] from the CLI and workspace configuration.
+    #[allow(clippy::needless_pass_by_value)]
+    pub(crate) fn resolve(args: ExportArgs, _workspace: Option<Workspace>) -> Self {
+        let ExportArgs {
+            format,
+            output_file,
+        } = args;
+
+        Self {
+            // CLI-only settings.
+            format,
+            output_file,
+        }
+    }
+}
+
 /// The resolved settings to use for a `pip compile` invocation.
 #[allow(clippy::struct_excessive_bools)]
 #[derive(Debug, Clone)]
@@ -178,6 +205,11 @@ pub(crate) struct PipCompileSettings {
     pub(crate) r#override: Vec<PathBuf>,
     pub(crate) refresh: Refresh,
     pub(crate) upgrade: Upgrade,
+    pub(crate) prerelease_package: Vec<PackageName>,
+    pub(crate) exclude_newer_package: FxHashMap<PackageName, ExcludeNewer>,
+    pub(crate) hash_algorithm: Vec<HashAlgorithm>,
+    pub(crate) format: CompileFormat,
+    pub(crate) explain: bool,
     pub(crate) uv_lock: bool,
 
     // Shared settings.
@@ -199,6 +231,7 @@ impl PipCompileSettings {
             resolution,
             prerelease,
             pre,
+            prerelease_package,
             output_file,
             no_strip_extras,
             strip_extras,
@@ -227,6 +260,7 @@ impl PipCompileSettings {
             upgrade_package,
             generate_hashes,
             no_generate_hashes,
+            hash_algorithm,
             legacy_setup_py,
             no_legacy_setup_py,
             no_build_isolation,
@@ -237,7 +271,10 @@ impl PipCompileSettings {
             config_setting,
             python_version,
             python_platform,
+            platform_independent,
+            no_platform_independent,
             exclude_newer,
+            exclude_newer_package,
             no_emit_package,
             emit_index_url,
             no_emit_index_url,
@@ -247,6 +284,8 @@ impl PipCompileSettings {
             no_emit_marker_expression,
             emit_index_annotation,
             no_emit_index_annotation,
+            format,
+            explain,
             unstable_uv_lock_file,
             no_unstable_uv_lock_file,
             compat_args: _,
@@ -262,6 +301,14 @@ impl PipCompileSettings {
             r#override,
             refresh: Refresh::from_args(flag(refresh, no_refresh), refresh_package),
             upgrade: Upgrade::from_args(flag(upgrade, no_upgrade), upgrade_package),
+            prerelease_package,
+            exclude_newer_package: exclude_newer_package
+                .into_iter()
+                .map(|entry| (entry.package, entry.timestamp))
+                .collect(),
+            hash_algorithm,
+            format,
+            explain,
             uv_lock: flag(unstable_uv_lock_file, no_unstable_uv_lock_file).unwrap_or(false),
 
             // Shared settings.
@@ -305,6 +352,7 @@ impl PipCompileSettings {
                     }),
                     python_version,
                     python_platform,
+                    platform_independent: flag(platform_independent, no_platform_independent),
                     exclude_newer,
                     no_emit_package,
                     emit_index_url: flag(emit_index_url, no_emit_index_url),
@@ -453,9 +501,12 @@ pub(crate) struct PipInstallSettings {
     pub(crate) constraint: Vec<PathBuf>,
     pub(crate) r#override: Vec<PathBuf>,
     pub(crate) upgrade: Upgrade,
+    pub(crate) prerelease_package: Vec<PackageName>,
+    pub(crate) exclude_newer_package: FxHashMap<PackageName, ExcludeNewer>,
     pub(crate) reinstall: Reinstall,
     pub(crate) refresh: Refresh,
     pub(crate) dry_run: bool,
+    pub(crate) explain: bool,
     pub(crate) uv_lock: Option<String>,
 
     // Shared settings.
@@ -489,6 +540,7 @@ impl PipInstallSettings {
             resolution,
             prerelease,
             pre,
+            prerelease_package,
             index_url,
             extra_index_url,
             find_links,
@@ -519,7 +571,9 @@ impl PipInstallSettings {
             strict,
             no_strict,
             exclude_newer,
+            exclude_newer_package,
             dry_run,
+            explain,
             unstable_uv_lock_file,
             compat_args: _,
         } = args;
@@ -535,9 +589,15 @@ impl PipInstallSettings {
                 .collect(),
             r#override,
             upgrade: Upgrade::from_args(flag(upgrade, no_upgrade), upgrade_package),
+            prerelease_package,
+            exclude_newer_package: exclude_newer_package
+                .into_iter()
+                .map(|entry| (entry.package, entry.timestamp))
+                .collect(),
             reinstall: Reinstall::from_args(flag(reinstall, no_reinstall), reinstall_package),
             refresh: Refresh::from_args(flag(refresh, no_refresh), refresh_package),
             dry_run,
+            explain,
             uv_lock: unstable_uv_lock_file,
 
             // Shared settings.
@@ -774,6 +834,50 @@ impl PipShowSettings {
     }
 }
 
+/// The resolved settings to use for a `pip tree` invocation.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone)]
+pub(crate) struct PipTreeSettings {
+    // CLI-only settings.
+    pub(crate) package: Vec<PackageName>,
+    pub(crate) invert: bool,
+
+    // Shared settings.
+    pub(crate) shared: PipSharedSettings,
+}
+
+impl PipTreeSettings {
+    /// Resolve the [`PipTreeSettings`] from the CLI and workspace configuration.
+    pub(crate) fn resolve(args: PipTreeArgs, workspace: Option<Workspace>) -> Self {
+        let PipTreeArgs {
+            package,
+            invert,
+            strict,
+            no_strict,
+            python,
+            system,
+            no_system,
+        } = args;
+
+        Self {
+            // CLI-only settings.
+            package,
+            invert,
+
+            // Shared settings.
+            shared: PipSharedSettings::combine(
+                PipOptions {
+                    python,
+                    system: flag(system, no_system),
+                    strict: flag(strict, no_strict),
+                    ..PipOptions::default()
+                },
+                workspace,
+            ),
+        }
+    }
+}
+
 /// The resolved settings to use for a `pip check` invocation.
 #[allow(clippy::struct_excessive_bools)]
 #[derive(Debug, Clone)]
@@ -910,6 +1014,7 @@ pub(crate) struct PipSharedSettings {
     pub(crate) config_setting: ConfigSettings,
     pub(crate) python_version: Option<PythonVersion>,
     pub(crate) python_platform: Option<TargetTriple>,
+    pub(crate) platform_independent: bool,
     pub(crate) exclude_newer: Option<ExcludeNewer>,
     pub(crate) no_emit_package: Vec<PackageName>,
     pub(crate) emit_index_url: bool,
@@ -957,6 +1062,7 @@ impl PipSharedSettings {
             config_settings,
             python_version,
             python_platform,
+            platform_independent,
             exclude_newer,
             no_emit_package,
             emit_index_url,
@@ -1041,6 +1147,10 @@ impl PipSharedSettings {
                 .unwrap_or_default(),
             python_version: args.python_version.combine(python_version),
             python_platform: args.python_platform.combine(python_platform),
+            platform_independent: args
+                .platform_independent
+                .combine(platform_independent)
+                .unwrap_or_default(),
             exclude_newer: args.exclude_newer.combine(exclude_newer),
             no_emit_package: args
                 .no_emit_package
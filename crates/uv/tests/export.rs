@@ -0,0 +1,176 @@
+#![cfg(feature = "python")]
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+use indoc::indoc;
+use insta::assert_snapshot;
+
+use common::{uv_snapshot, TestContext};
+
+mod common;
+
+/// The `uv.lock` for a project that depends on a registry package and a Git package.
+const LOCK: &str = indoc! {r#"
+    version = 1
+
+    [[distribution]]
+    name = "anyio"
+    version = "3.7.0"
+    source = "registry+https://pypi.org/simple"
+
+    [distribution.sdist]
+    url = "https://files.pythonhosted.org/packages/c6/b3/fefbf7e78ab3b805dec67d698dc18dd505af7a18a8dd08868c9b4fa736b5/anyio-3.7.0.tar.gz"
+    hash = "sha256:275d9973793619a5374e1c89a4f4ad3f4b0a5510a2b5b939444bee8f4c4d37ce"
+    size = 142737
+
+    [[distribution.wheel]]
+    url = "https://files.pythonhosted.org/packages/68/fe/7ce1926952c8a403b35029e194555558514b365ad77d75125f521a2bec62/anyio-3.7.0-py3-none-any.whl"
+    hash = "sha256:eddca883c4175f14df8aedce21054bfca3adb70ffe76a9f607aef9d7fa2ea7f0"
+    size = 80873
+
+    [[distribution]]
+    name = "iniconfig"
+    version = "2.0.0"
+    source = "git+https://github.com/pytest-dev/iniconfig?rev=main#93f5930e668c0d1ddf4597e38dd0dea4e2665e7a"
+"#};
+
+/// Create a project with the given lockfile.
+fn project(context: &TestContext, lock: Option<&str>) -> Result<()> {
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        dependencies = ["anyio==3.7.0", "iniconfig @ git+https://github.com/pytest-dev/iniconfig@main"]
+    "#})?;
+
+    if let Some(lock) = lock {
+        context.temp_dir.child("uv.lock").write_str(lock)?;
+    }
+
+    Ok(())
+}
+
+/// Export a lockfile to `pylock.toml`, writing it to stdout.
+#[test]
+fn export_pylock_toml() -> Result<()> {
+    let context = TestContext::new("3.12");
+    project(&context, Some(LOCK))?;
+
+    uv_snapshot!(context.filters(), context.export(), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    lock-version = "1.0"
+    created-by = "uv"
+
+    [[packages]]
+    name = "anyio"
+    version = "3.7.0"
+    index = "https://pypi.org/simple"
+
+    [packages.sdist]
+    name = "anyio-3.7.0.tar.gz"
+    url = "https://files.pythonhosted.org/packages/c6/b3/fefbf7e78ab3b805dec67d698dc18dd505af7a18a8dd08868c9b4fa736b5/anyio-3.7.0.tar.gz"
+    size = 142737
+
+    [packages.sdist.hashes]
+    sha256 = "275d9973793619a5374e1c89a4f4ad3f4b0a5510a2b5b939444bee8f4c4d37ce"
+
+    [[packages.wheels]]
+    name = "anyio-3.7.0-py3-none-any.whl"
+    url = "https://files.pythonhosted.org/packages/68/fe/7ce1926952c8a403b35029e194555558514b365ad77d75125f521a2bec62/anyio-3.7.0-py3-none-any.whl"
+    size = 80873
+
+    [packages.wheels.hashes]
+    sha256 = "eddca883c4175f14df8aedce21054bfca3adb70ffe76a9f607aef9d7fa2ea7f0"
+
+    [[packages]]
+    name = "iniconfig"
+    version = "2.0.0"
+
+    [packages.vcs]
+    type = "git"
+    url = "https://github.com/pytest-dev/iniconfig"
+    requested-revision = "main"
+    commit-id = "93f5930e668c0d1ddf4597e38dd0dea4e2665e7a"
+
+    ----- stderr -----
+    "###);
+
+    Ok(())
+}
+
+/// Export a lockfile to `pylock.toml`, writing it to the given output file.
+#[test]
+fn export_pylock_toml_output_file() -> Result<()> {
+    let context = TestContext::new("3.12");
+    project(&context, Some(LOCK))?;
+
+    uv_snapshot!(context.filters(), context.export().arg("--output-file").arg("pylock.toml"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    "###);
+
+    let pylock = fs_err::read_to_string(context.temp_dir.join("pylock.toml"))?;
+    assert_snapshot!(pylock, @r###"
+    lock-version = "1.0"
+    created-by = "uv"
+
+    [[packages]]
+    name = "anyio"
+    version = "3.7.0"
+    index = "https://pypi.org/simple"
+
+    [packages.sdist]
+    name = "anyio-3.7.0.tar.gz"
+    url = "https://files.pythonhosted.org/packages/c6/b3/fefbf7e78ab3b805dec67d698dc18dd505af7a18a8dd08868c9b4fa736b5/anyio-3.7.0.tar.gz"
+    size = 142737
+
+    [packages.sdist.hashes]
+    sha256 = "275d9973793619a5374e1c89a4f4ad3f4b0a5510a2b5b939444bee8f4c4d37ce"
+
+    [[packages.wheels]]
+    name = "anyio-3.7.0-py3-none-any.whl"
+    url = "https://files.pythonhosted.org/packages/68/fe/7ce1926952c8a403b35029e194555558514b365ad77d75125f521a2bec62/anyio-3.7.0-py3-none-any.whl"
+    size = 80873
+
+    [packages.wheels.hashes]
+    sha256 = "eddca883c4175f14df8aedce21054bfca3adb70ffe76a9f607aef9d7fa2ea7f0"
+
+    [[packages]]
+    name = "iniconfig"
+    version = "2.0.0"
+
+    [packages.vcs]
+    type = "git"
+    url = "https://github.com/pytest-dev/iniconfig"
+    requested-revision = "main"
+    commit-id = "93f5930e668c0d1ddf4597e38dd0dea4e2665e7a"
+    "###);
+
+    Ok(())
+}
+
+/// Exporting a project without a lockfile should fail.
+#[test]
+fn export_missing_lockfile() -> Result<()> {
+    let context = TestContext::new("3.12");
+    project(&context, None)?;
+
+    uv_snapshot!(context.filters(), context.export(), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Failed to read lockfile at `uv.lock` (run `uv lock` to create it)
+      Caused by: failed to read from file `[TEMP_DIR]/uv.lock`
+      Caused by: No such file or directory (os error 2)
+    "###);
+
+    Ok(())
+}
@@ -76,6 +76,7 @@ fn lock_wheel_registry() -> Result<()> {
     name = "exceptiongroup"
     version = "1.2.0"
     source = "registry+https://pypi.org/simple"
+    marker = "python_version < '3.11'"
 
     [[distribution.dependencies]]
     name = "idna"
@@ -91,11 +92,13 @@ fn lock_wheel_registry() -> Result<()> {
     name = "typing-extensions"
     version = "4.10.0"
     source = "registry+https://pypi.org/simple"
+    marker = "python_version < '3.8'"
 
     [[distribution]]
     name = "exceptiongroup"
     version = "1.2.0"
     source = "registry+https://pypi.org/simple"
+    marker = "python_version < '3.11'"
 
     [distribution.sdist]
     url = "https://files.pythonhosted.org/packages/8e/1c/beef724eaf5b01bb44b6338c8c3494eff7cab376fab4904cfbbc3585dc79/exceptiongroup-1.2.0.tar.gz"
@@ -141,6 +144,7 @@ fn lock_wheel_registry() -> Result<()> {
     name = "typing-extensions"
     version = "4.10.0"
     source = "registry+https://pypi.org/simple"
+    marker = "python_version < '3.8'"
 
     [distribution.sdist]
     url = "https://files.pythonhosted.org/packages/16/3a/0d26ce356c7465a19c9ea8814b960f8a36c3b0d07c323176620b7b483e44/typing_extensions-4.10.0.tar.gz"
@@ -161,13 +165,11 @@ fn lock_wheel_registry() -> Result<()> {
     ----- stdout -----
 
     ----- stderr -----
-    Downloaded 5 packages in [TIME]
-    Installed 5 packages in [TIME]
+    Downloaded 3 packages in [TIME]
+    Installed 3 packages in [TIME]
      + anyio==3.7.0
-     + exceptiongroup==1.2.0
      + idna==3.6
      + sniffio==1.3.1
-     + typing-extensions==4.10.0
     "###);
 
     Ok(())
@@ -290,6 +292,7 @@ fn lock_sdist_git() -> Result<()> {
     name = "exceptiongroup"
     version = "1.2.0"
     source = "registry+https://pypi.org/simple"
+    marker = "python_version < '3.11'"
 
     [[distribution.dependencies]]
     name = "idna"
@@ -305,11 +308,13 @@ fn lock_sdist_git() -> Result<()> {
     name = "typing-extensions"
     version = "4.10.0"
     source = "registry+https://pypi.org/simple"
+    marker = "python_version < '3.8'"
 
     [[distribution]]
     name = "exceptiongroup"
     version = "1.2.0"
     source = "registry+https://pypi.org/simple"
+    marker = "python_version < '3.11'"
 
     [distribution.sdist]
     url = "https://files.pythonhosted.org/packages/8e/1c/beef724eaf5b01bb44b6338c8c3494eff7cab376fab4904cfbbc3585dc79/exceptiongroup-1.2.0.tar.gz"
@@ -355,6 +360,7 @@ fn lock_sdist_git() -> Result<()> {
     name = "typing-extensions"
     version = "4.10.0"
     source = "registry+https://pypi.org/simple"
+    marker = "python_version < '3.8'"
 
     [distribution.sdist]
     url = "https://files.pythonhosted.org/packages/16/3a/0d26ce356c7465a19c9ea8814b960f8a36c3b0d07c323176620b7b483e44/typing_extensions-4.10.0.tar.gz"
@@ -375,13 +381,11 @@ fn lock_sdist_git() -> Result<()> {
     ----- stdout -----
 
     ----- stderr -----
-    Downloaded 4 packages in [TIME]
-    Installed 5 packages in [TIME]
+    Downloaded 2 packages in [TIME]
+    Installed 3 packages in [TIME]
      + anyio==3.7.0 (from git+https://github.com/agronholm/anyio@f7a880ffac4766efb39e6fb60fc28d944f5d2f65)
-     + exceptiongroup==1.2.0
      + idna==3.6
      + sniffio==1.3.1
-     + typing-extensions==4.10.0
     "###);
 
     Ok(())
@@ -447,6 +451,7 @@ fn lock_wheel_url() -> Result<()> {
     name = "exceptiongroup"
     version = "1.2.0"
     source = "registry+https://pypi.org/simple"
+    marker = "python_version < '3.11'"
 
     [[distribution.dependencies]]
     name = "idna"
@@ -462,11 +467,13 @@ fn lock_wheel_url() -> Result<()> {
     name = "typing-extensions"
     version = "4.10.0"
     source = "registry+https://pypi.org/simple"
+    marker = "python_version < '3.11'"
 
     [[distribution]]
     name = "exceptiongroup"
     version = "1.2.0"
     source = "registry+https://pypi.org/simple"
+    marker = "python_version < '3.11'"
 
     [distribution.sdist]
     url = "https://files.pythonhosted.org/packages/8e/1c/beef724eaf5b01bb44b6338c8c3494eff7cab376fab4904cfbbc3585dc79/exceptiongroup-1.2.0.tar.gz"
@@ -512,6 +519,7 @@ fn lock_wheel_url() -> Result<()> {
     name = "typing-extensions"
     version = "4.10.0"
     source = "registry+https://pypi.org/simple"
+    marker = "python_version < '3.11'"
 
     [distribution.sdist]
     url = "https://files.pythonhosted.org/packages/16/3a/0d26ce356c7465a19c9ea8814b960f8a36c3b0d07c323176620b7b483e44/typing_extensions-4.10.0.tar.gz"
@@ -532,13 +540,11 @@ fn lock_wheel_url() -> Result<()> {
     ----- stdout -----
 
     ----- stderr -----
-    Downloaded 4 packages in [TIME]
-    Installed 5 packages in [TIME]
+    Downloaded 2 packages in [TIME]
+    Installed 3 packages in [TIME]
      + anyio==4.3.0 (from https://files.pythonhosted.org/packages/14/fd/2f20c40b45e4fb4324834aea24bd4afdf1143390242c0b33774da0e2e34f/anyio-4.3.0-py3-none-any.whl)
-     + exceptiongroup==1.2.0
      + idna==3.6
      + sniffio==1.3.1
-     + typing-extensions==4.10.0
     "###);
 
     Ok(())
@@ -604,6 +610,7 @@ fn lock_sdist_url() -> Result<()> {
     name = "exceptiongroup"
     version = "1.2.0"
     source = "registry+https://pypi.org/simple"
+    marker = "python_version < '3.11'"
 
     [[distribution.dependencies]]
     name = "idna"
@@ -619,11 +626,13 @@ fn lock_sdist_url() -> Result<()> {
     name = "typing-extensions"
     version = "4.10.0"
     source = "registry+https://pypi.org/simple"
+    marker = "python_version < '3.11'"
 
     [[distribution]]
     name = "exceptiongroup"
     version = "1.2.0"
     source = "registry+https://pypi.org/simple"
+    marker = "python_version < '3.11'"
 
     [distribution.sdist]
     url = "https://files.pythonhosted.org/packages/8e/1c/beef724eaf5b01bb44b6338c8c3494eff7cab376fab4904cfbbc3585dc79/exceptiongroup-1.2.0.tar.gz"
@@ -669,6 +678,7 @@ fn lock_sdist_url() -> Result<()> {
     name = "typing-extensions"
     version = "4.10.0"
     source = "registry+https://pypi.org/simple"
+    marker = "python_version < '3.11'"
 
     [distribution.sdist]
     url = "https://files.pythonhosted.org/packages/16/3a/0d26ce356c7465a19c9ea8814b960f8a36c3b0d07c323176620b7b483e44/typing_extensions-4.10.0.tar.gz"
@@ -689,13 +699,11 @@ fn lock_sdist_url() -> Result<()> {
     ----- stdout -----
 
     ----- stderr -----
-    Downloaded 4 packages in [TIME]
-    Installed 5 packages in [TIME]
+    Downloaded 2 packages in [TIME]
+    Installed 3 packages in [TIME]
      + anyio==4.3.0 (from https://files.pythonhosted.org/packages/db/4d/3970183622f0330d3c23d9b8a5f52e365e50381fd484d08e3285104333d3/anyio-4.3.0.tar.gz)
-     + exceptiongroup==1.2.0
      + idna==3.6
      + sniffio==1.3.1
-     + typing-extensions==4.10.0
     "###);
 
     Ok(())
@@ -1763,6 +1763,38 @@ fn conflicting_transitive_url_dependency() -> Result<()> {
     Ok(())
 }
 
+/// Request Flask, but include a URL dependency for a conflicting version of Werkzeug, and explain
+/// the failure with the full derivation tree.
+#[test]
+fn conflicting_transitive_url_dependency_explain() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("flask==3.0.0\nwerkzeug @ https://files.pythonhosted.org/packages/ff/1d/960bb4017c68674a1cb099534840f18d3def3ce44aed12b5ed8b78e0153e/Werkzeug-2.0.0-py3-none-any.whl")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--explain"), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+      × No solution found when resolving dependencies:
+      ╰─▶ Because only werkzeug<3.0.0 is available and flask==3.0.0 depends on werkzeug>=3.0.0, we can conclude that flask==3.0.0 cannot be used.
+          And because you require flask==3.0.0, we can conclude that the requirements are unsatisfiable.
+
+    Explanation:
+    the requirements are unsatisfiable
+    ├── flask==3.0.0 cannot be used
+    │   ├── only werkzeug<3.0.0 is available
+    │   └── flask==3.0.0 depends on werkzeug>=3.0.0
+    └── you require flask==3.0.0
+    "###
+    );
+
+    Ok(())
+}
+
 /// Request `uv-public-pypackage` via two different URLs which resolve to the same canonical version.
 #[test]
 fn compatible_repeated_url_dependency() -> Result<()> {
@@ -2447,6 +2479,52 @@ fn compile_exclude_newer() -> Result<()> {
     Ok(())
 }
 
+/// Resolve a specific package at a time in the past, while resolving everything else at the
+/// global `--exclude-newer` cutoff.
+#[test]
+fn compile_exclude_newer_package() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("tqdm")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--exclude-newer-package")
+            // 4.64.0: 2022-04-04T01:48:46.194635Z1
+            // 4.64.1: 2022-09-03T11:10:27.148080Z
+            .arg("tqdm=2022-04-04T12:00:00Z"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --exclude-newer-package tqdm=2022-04-04T12:00:00Z
+    tqdm==4.64.0
+        # via -r requirements.in
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    // Check the error message for a missing package name.
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--exclude-newer-package")
+            .arg("2022-04-04"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: invalid value '2022-04-04' for '--exclude-newer-package <EXCLUDE_NEWER_PACKAGE>': `2022-04-04` is not a valid package timestamp (expected `PACKAGE=DATE`)
+
+    For more information, try '--help'.
+    "###
+    );
+
+    Ok(())
+}
+
 /// Resolve a local path dependency on a specific wheel.
 #[test]
 fn compile_wheel_path_dependency() -> Result<()> {
@@ -3790,6 +3868,39 @@ fn generate_hashes_registry() -> Result<()> {
     Ok(())
 }
 
+/// Compute the requested hashes for packages from an index that doesn't provide them (here, a
+/// `--find-links` directory, which doesn't provide any hashes).
+#[test]
+fn generate_hashes_algorithm_computed() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("validation==1.0.0")?;
+
+    uv_snapshot!(context.filters(), context.compile()
+        .arg("requirements.in")
+        .arg("--no-index")
+        .arg("--find-links")
+        .arg(context.workspace_root.join("scripts").join("links"))
+        .arg("--generate-hashes")
+        .arg("--hash-algorithm")
+        .arg("sha512"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --no-index --generate-hashes --hash-algorithm sha512
+    validation==1.0.0 \
+        --hash=sha512:1fa7017f9723e730a98b2eae64fcf700a66d2b740173b8d4cc64b5ad5030aee694cea1110841e925d8ddf1fa4fbd5c9e7a02a698f28693ff203e4369d48b9fae
+        # via -r requirements.in
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
 /// Include hashes from the URL in the generated output.
 #[test]
 fn generate_hashes_source_distribution_url() -> Result<()> {
@@ -4330,6 +4441,11 @@ fn upgrade_all() -> Result<()> {
 
     ----- stderr -----
     Resolved 6 packages in [TIME]
+    Updated pins in requirements.txt:
+     ~ click==8.1.2 -> 8.1.7 (upgrade requested)
+     ~ packaging==23.2 -> 24.0 (upgrade requested)
+     ~ pathspec==0.11.0 -> 0.12.1 (upgrade requested)
+     ~ platformdirs==4.0.0 -> 4.2.0 (upgrade requested)
     "###
     );
 
@@ -4387,6 +4503,67 @@ fn upgrade_package() -> Result<()> {
 
     ----- stderr -----
     Resolved 6 packages in [TIME]
+    Updated pins in requirements.txt:
+     ~ click==8.1.2 -> 8.1.7 (upgrade requested)
+    "###
+    );
+
+    Ok(())
+}
+
+/// Use an existing resolution for `black==23.10.1`, with stale versions of `click` and `pathspec`.
+/// Only `click` should be upgraded, and only up to the bound passed to `--upgrade-package`.
+#[test]
+fn upgrade_package_specifier() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("black==23.10.1")?;
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(indoc! {r"
+        # This file was autogenerated by uv via the following command:
+        #    uv pip compile requirements.in --python-version 3.12 --cache-dir [CACHE_DIR]
+        black==23.10.1
+        click==8.1.2
+            # via black
+        mypy-extensions==1.0.0
+            # via black
+        packaging==23.2
+            # via black
+        pathspec==0.11.0
+            # via black
+        platformdirs==4.0.0
+            # via black
+    "})?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--output-file")
+            .arg("requirements.txt")
+            .arg("--upgrade-package")
+            .arg("click<8.1.7"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --output-file requirements.txt --upgrade-package click<8.1.7
+    black==23.10.1
+        # via -r requirements.in
+    click==8.1.6
+        # via black
+    mypy-extensions==1.0.0
+        # via black
+    packaging==23.2
+        # via black
+    pathspec==0.11.0
+        # via black
+    platformdirs==4.0.0
+        # via black
+
+    ----- stderr -----
+    Resolved 6 packages in [TIME]
+    Updated pins in requirements.txt:
+     ~ click==8.1.2 -> 8.1.6 (upgrade requested)
     "###
     );
 
@@ -5046,7 +5223,7 @@ fn offline_direct_url() -> Result<()> {
 
     ----- stderr -----
     error: Failed to download: `iniconfig @ https://files.pythonhosted.org/packages/ef/a6/62565a6e1cf69e10f5727360368e451d4b7f58beeac6173dc9db836a5b46/iniconfig-2.0.0-py3-none-any.whl`
-      Caused by: Network connectivity is disabled, but the requested data wasn't found in the cache for: `https://files.pythonhosted.org/packages/ef/a6/62565a6e1cf69e10f5727360368e451d4b7f58beeac6173dc9db836a5b46/iniconfig-2.0.0-py3-none-any.whl`
+      Caused by: Network connectivity is disabled, but the requested data wasn't found in the cache for: `https://files.pythonhosted.org/packages/ef/a6/62565a6e1cf69e10f5727360368e451d4b7f58beeac6173dc9db836a5b46/iniconfig-2.0.0-py3-none-any.whl` (run without `--offline`, or run the command once with network access to populate the cache)
     "###
     );
 
@@ -7193,6 +7370,8 @@ fn preserve_hashes_newer_version() -> Result<()> {
 
     ----- stderr -----
     Resolved 1 package in [TIME]
+    Updated pins in requirements.txt:
+     ~ markupsafe==2.1.2 -> 2.1.3 (existing pin is incompatible)
     "###
     );
 
@@ -9267,3 +9446,153 @@ fn tool_uv_sources() -> Result<()> {
 
     Ok(())
 }
+
+/// Perform a platform-independent resolution, retaining dependencies that only apply on other
+/// platforms, and annotating them with their markers.
+#[test]
+fn compile_platform_independent() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("click==8.1.7")?;
+
+    uv_snapshot!(context
+        .compile()
+        .arg("requirements.in")
+        .arg("--platform-independent"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --platform-independent
+    click==8.1.7
+        # via -r requirements.in
+    colorama==0.4.6 ; platform_system == 'Windows'
+        # via click
+
+    ----- stderr -----
+    Resolved 2 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Write the resolution as JSON, omitting any `--no-emit-package` packages, and without reading
+/// the existing output file back as a `requirements.txt`.
+#[test]
+fn compile_format_json() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio==4.0.0")?;
+
+    // Seed the output file with a previous JSON export.
+    let output_file = context.temp_dir.child("requirements.json");
+    output_file.write_str(r#"[{"name":"anyio","version":"3.7.1","dependencies":[]}]"#)?;
+
+    uv_snapshot!(context
+        .compile()
+        .arg("requirements.in")
+        .arg("--output-file")
+        .arg("requirements.json")
+        .arg("--format")
+        .arg("json")
+        .arg("--no-emit-package")
+        .arg("idna")
+        .arg("--no-emit-package")
+        .arg("sniffio"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    [{"name":"anyio","version":"4.0.0","index":"https://pypi.org/simple","artifact":"wheel","filename":"anyio-4.0.0-py3-none-any.whl","requires_python":">=3.8","dependencies":[]}]
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Write the resolution as a Graphviz DOT graph, omitting any `--unsafe-package` packages.
+#[test]
+fn compile_format_dot() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio==4.0.0")?;
+
+    uv_snapshot!(context
+        .compile()
+        .arg("requirements.in")
+        .arg("--format")
+        .arg("dot")
+        .arg("--unsafe-package")
+        .arg("sniffio"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    digraph resolution {
+        "anyio" [label="anyio==4.0.0"];
+        "idna" [label="idna==3.6"];
+        "anyio" -> "idna";
+    }
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Enable `--platform-independent` via the `[pip]` section of a `uv.toml` file.
+#[test]
+fn compile_platform_independent_configuration() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let config = context.temp_dir.child("uv.toml");
+    config.write_str(indoc::indoc! {r"
+        [pip]
+        platform-independent = true
+    "})?;
+
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("click==8.1.7")?;
+
+    uv_snapshot!(context
+        .compile()
+        .arg("requirements.in"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in
+    click==8.1.7
+        # via -r requirements.in
+    colorama==0.4.6 ; platform_system == 'Windows'
+        # via click
+
+    ----- stderr -----
+    Resolved 2 packages in [TIME]
+    "###
+    );
+
+    // The command line takes precedence over the configuration.
+    uv_snapshot!(context
+        .compile()
+        .arg("requirements.in")
+        .arg("--no-platform-independent"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2024-03-25T00:00:00Z requirements.in --no-platform-independent
+    click==8.1.7
+        # via -r requirements.in
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    Ok(())
+}
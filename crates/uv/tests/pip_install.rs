@@ -525,6 +525,69 @@ build-backend = "poetry.core.masonry.api"
     Ok(())
 }
 
+/// Install the packages locked in a `Pipfile.lock`, including the `develop` category via
+/// `--extra dev`.
+#[test]
+fn install_pipfile_lock() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let pipfile_lock = context.temp_dir.child("Pipfile.lock");
+    pipfile_lock.write_str(indoc! {r#"
+        {
+            "_meta": {
+                "pipfile-spec": 6,
+                "requires": {
+                    "python_version": "3.12"
+                },
+                "sources": [
+                    {
+                        "name": "pypi",
+                        "url": "https://pypi.org/simple",
+                        "verify_ssl": true
+                    }
+                ]
+            },
+            "default": {
+                "markupsafe": {
+                    "index": "pypi",
+                    "markers": "python_version >= '3.7'",
+                    "version": "==2.1.3"
+                },
+                "tomli": {
+                    "index": "pypi",
+                    "markers": "python_version < '3.11'",
+                    "version": "==2.0.1"
+                }
+            },
+            "develop": {
+                "iniconfig": {
+                    "index": "pypi",
+                    "version": "==2.0.0"
+                }
+            }
+        }
+    "#})?;
+
+    uv_snapshot!(context.install()
+            .arg("-r")
+            .arg("Pipfile.lock")
+            .arg("--extra")
+            .arg("dev"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 2 packages in [TIME]
+    Downloaded 2 packages in [TIME]
+    Installed 2 packages in [TIME]
+     + iniconfig==2.0.0
+     + markupsafe==2.1.3
+    "###
+    );
+
+    Ok(())
+}
+
 /// Respect installed versions when resolving.
 #[test]
 fn respect_installed_and_reinstall() -> Result<()> {
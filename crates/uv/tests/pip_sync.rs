@@ -178,6 +178,273 @@ fn install() -> Result<()> {
     Ok(())
 }
 
+/// Install from a PEP 751 `pylock.toml` file, skipping any packages whose markers don't apply.
+#[test]
+fn install_pylock_toml() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pylock_toml = context.temp_dir.child("pylock.toml");
+    pylock_toml.write_str(indoc! {r#"
+        lock-version = "1.0"
+        created-by = "uv"
+
+        [[packages]]
+        name = "iniconfig"
+        version = "2.0.0"
+        index = "https://pypi.org/simple"
+
+        [[packages.wheels]]
+        name = "iniconfig-2.0.0-py3-none-any.whl"
+        url = "https://files.pythonhosted.org/packages/ef/a6/62565a6e1cf69e10f5727360368e451d4b7f58beeac6173dc9db836a5b46/iniconfig-2.0.0-py3-none-any.whl"
+        hashes = { sha256 = "b6a85871a79d2e3b22d2d1b94ac2824226a63c6b741c88f7ae975f18b6778374" }
+
+        [[packages]]
+        name = "tomli"
+        version = "2.0.1"
+        marker = "python_version < '3.11'"
+    "#})?;
+
+    uv_snapshot!(sync_without_exclude_newer(&context)
+        .arg("pylock.toml")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + iniconfig==2.0.0
+    "###
+    );
+
+    context.assert_command("import iniconfig").success();
+
+    Ok(())
+}
+
+/// Verify the hashes in a `pylock.toml` file, even without `--require-hashes`.
+#[test]
+fn install_pylock_toml_hash_mismatch() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pylock_toml = context.temp_dir.child("pylock.toml");
+    pylock_toml.write_str(indoc! {r#"
+        lock-version = "1.0"
+        created-by = "uv"
+
+        [[packages]]
+        name = "iniconfig"
+        version = "2.0.0"
+        index = "https://pypi.org/simple"
+
+        [[packages.wheels]]
+        name = "iniconfig-2.0.0-py3-none-any.whl"
+        url = "https://files.pythonhosted.org/packages/ef/a6/62565a6e1cf69e10f5727360368e451d4b7f58beeac6173dc9db836a5b46/iniconfig-2.0.0-py3-none-any.whl"
+        hashes = { sha256 = "a6a85871a79d2e3b22d2d1b94ac2824226a63c6b741c88f7ae975f18b6778374" }
+    "#})?;
+
+    uv_snapshot!(sync_without_exclude_newer(&context)
+        .arg("pylock.toml"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Failed to download distributions
+      Caused by: Failed to fetch wheel: iniconfig==2.0.0
+      Caused by: Hash mismatch for `iniconfig==2.0.0`
+
+    Expected:
+      sha256:a6a85871a79d2e3b22d2d1b94ac2824226a63c6b741c88f7ae975f18b6778374
+
+    Computed:
+      sha256:b6a85871a79d2e3b22d2d1b94ac2824226a63c6b741c88f7ae975f18b6778374
+    "###
+    );
+
+    Ok(())
+}
+
+/// Install the packages locked in a Poetry `poetry.lock`, using the dependencies declared in the
+/// adjacent `pyproject.toml` to determine which packages apply to the current environment.
+#[test]
+fn install_poetry_lock() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! {r#"
+        [tool.poetry]
+        name = "project"
+        version = "0.1.0"
+
+        [tool.poetry.dependencies]
+        python = "^3.8"
+        markupsafe = "^2.1"
+        tomli = { version = "^2.0", python = "<3.11" }
+    "#})?;
+
+    let poetry_lock = context.temp_dir.child("poetry.lock");
+    poetry_lock.write_str(indoc! {r#"
+        [[package]]
+        name = "markupsafe"
+        version = "2.1.3"
+        description = "Safely add untrusted strings to HTML/XML markup."
+        optional = false
+        python-versions = ">=3.7"
+        files = []
+
+        [[package]]
+        name = "tomli"
+        version = "2.0.1"
+        description = "A lil' TOML parser"
+        optional = false
+        python-versions = ">=3.7"
+        files = []
+
+        [metadata]
+        lock-version = "2.0"
+        python-versions = "^3.8"
+        content-hash = "0000000000000000000000000000000000000000000000000000000000000000"
+    "#})?;
+
+    uv_snapshot!(sync_without_exclude_newer(&context)
+        .arg("poetry.lock")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + markupsafe==2.1.3
+    "###
+    );
+
+    context.assert_command("import markupsafe").success();
+
+    Ok(())
+}
+
+/// Verify the hashes in a Poetry `poetry.lock`, even without `--require-hashes`.
+#[test]
+fn install_poetry_lock_hash_mismatch() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! {r#"
+        [tool.poetry]
+        name = "project"
+        version = "0.1.0"
+
+        [tool.poetry.dependencies]
+        python = "^3.8"
+        anyio = "4.0.0"
+    "#})?;
+
+    let poetry_lock = context.temp_dir.child("poetry.lock");
+    poetry_lock.write_str(indoc! {r#"
+        [[package]]
+        name = "anyio"
+        version = "4.0.0"
+        description = "High level compatibility layer for multiple asynchronous event loop implementations"
+        optional = false
+        python-versions = ">=3.8"
+        files = [
+            {file = "anyio-4.0.0-py3-none-any.whl", hash = "sha256:afdb2b588b9fc25ede96d8db56ed50848b0b649dca3dd1df0b11f683bb9e0b5f"},
+        ]
+
+        [metadata]
+        lock-version = "2.0"
+        python-versions = "^3.8"
+        content-hash = "0000000000000000000000000000000000000000000000000000000000000000"
+    "#})?;
+
+    uv_snapshot!(sync_without_exclude_newer(&context)
+        .arg("poetry.lock"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    error: Failed to download distributions
+      Caused by: Failed to fetch wheel: anyio==4.0.0
+      Caused by: Hash mismatch for `anyio==4.0.0`
+
+    Expected:
+      sha256:afdb2b588b9fc25ede96d8db56ed50848b0b649dca3dd1df0b11f683bb9e0b5f
+
+    Computed:
+      sha256:cfdb2b588b9fc25ede96d8db56ed50848b0b649dca3dd1df0b11f683bb9e0b5f
+    "###
+    );
+
+    Ok(())
+}
+
+/// Verify the hashes in a Pipenv `Pipfile.lock`, even without `--require-hashes`.
+#[test]
+fn install_pipfile_lock_hash_mismatch() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pipfile_lock = context.temp_dir.child("Pipfile.lock");
+    pipfile_lock.write_str(indoc! {r#"
+        {
+            "_meta": {
+                "hash": {
+                    "sha256": "0000000000000000000000000000000000000000000000000000000000000000"
+                },
+                "pipfile-spec": 6,
+                "requires": {
+                    "python_version": "3.12"
+                },
+                "sources": [
+                    {
+                        "name": "pypi",
+                        "url": "https://pypi.org/simple",
+                        "verify_ssl": true
+                    }
+                ]
+            },
+            "default": {
+                "anyio": {
+                    "hashes": [
+                        "sha256:afdb2b588b9fc25ede96d8db56ed50848b0b649dca3dd1df0b11f683bb9e0b5f"
+                    ],
+                    "index": "pypi",
+                    "version": "==4.0.0"
+                }
+            },
+            "develop": {}
+        }
+    "#})?;
+
+    uv_snapshot!(sync_without_exclude_newer(&context)
+        .arg("Pipfile.lock"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    error: Failed to download distributions
+      Caused by: Failed to fetch wheel: anyio==4.0.0
+      Caused by: Hash mismatch for `anyio==4.0.0`
+
+    Expected:
+      sha256:afdb2b588b9fc25ede96d8db56ed50848b0b649dca3dd1df0b11f683bb9e0b5f
+
+    Computed:
+      sha256:cfdb2b588b9fc25ede96d8db56ed50848b0b649dca3dd1df0b11f683bb9e0b5f
+    "###
+    );
+
+    Ok(())
+}
+
 /// Install a package into a virtual environment using copy semantics.
 #[test]
 fn install_copy() -> Result<()> {
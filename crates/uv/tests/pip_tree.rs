@@ -0,0 +1,210 @@
+use std::process::Command;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use assert_fs::fixture::FileWriteStr;
+use assert_fs::fixture::PathChild;
+
+use common::uv_snapshot;
+
+use crate::common::{get_bin, TestContext, EXCLUDE_NEWER};
+
+mod common;
+
+/// Create a `pip install` command with options shared across scenarios.
+fn install_command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("pip")
+        .arg("install")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .env("UV_NO_WRAP", "1")
+        .current_dir(&context.temp_dir);
+
+    if cfg!(all(windows, debug_assertions)) {
+        // TODO(konstin): Reduce stack usage in debug mode enough that the tests pass with the
+        // default windows stack of 1MB
+        command.env("UV_STACK_SIZE", (2 * 1024 * 1024).to_string());
+    }
+
+    command
+}
+
+/// Create a `pip tree` command with options shared across scenarios.
+fn tree_command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("pip")
+        .arg("tree")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .env("UV_NO_WRAP", "1")
+        .current_dir(&context.temp_dir);
+    command
+}
+
+#[test]
+fn tree_empty() {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(tree_command(&context), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    "###
+    );
+}
+
+#[test]
+fn tree_requests() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("requests==2.31.0")?;
+
+    uv_snapshot!(install_command(&context)
+        .arg("-r")
+        .arg("requirements.txt")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 5 packages in [TIME]
+    Downloaded 5 packages in [TIME]
+    Installed 5 packages in [TIME]
+     + certifi==2024.2.2
+     + charset-normalizer==3.3.2
+     + idna==3.6
+     + requests==2.31.0
+     + urllib3==2.2.1
+    "###
+    );
+
+    uv_snapshot!(context.filters(), tree_command(&context), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    requests==2.31.0
+    ├── certifi==2024.2.2
+    ├── charset-normalizer==3.3.2
+    ├── idna==3.6
+    └── urllib3==2.2.1
+
+    ----- stderr -----
+    "###
+    );
+
+    // Rooting the tree at a dependency should only show that dependency.
+    uv_snapshot!(context.filters(), tree_command(&context)
+        .arg("--package")
+        .arg("idna"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    idna==3.6
+
+    ----- stderr -----
+    "###
+    );
+
+    // Inverting the tree should show the packages that require the dependency.
+    uv_snapshot!(context.filters(), tree_command(&context)
+        .arg("--package")
+        .arg("idna")
+        .arg("--invert"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    idna==3.6
+    └── requests==2.31.0
+
+    ----- stderr -----
+    "###
+    );
+
+    Ok(())
+}
+
+/// Write a minimal `.dist-info` directory for a package into the virtual environment.
+fn write_dist_info(
+    context: &TestContext,
+    name: &str,
+    version: &str,
+    requires_dist: &[&str],
+) -> Result<()> {
+    let dist_info = context
+        .site_packages()
+        .join(format!("{name}-{version}.dist-info"));
+    fs_err::create_dir_all(&dist_info)?;
+    let mut metadata = format!("Metadata-Version: 2.1\nName: {name}\nVersion: {version}\n");
+    for requirement in requires_dist {
+        metadata.push_str(&format!("Requires-Dist: {requirement}\n"));
+    }
+    fs_err::write(dist_info.join("METADATA"), metadata)?;
+    Ok(())
+}
+
+/// Respect the extras requested by dependents, and render packages that are only required from
+/// within a cycle.
+#[test]
+fn tree_extras_and_cycles() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // `a` enables the `extra` extra of `b`, but not the `other` extra.
+    write_dist_info(&context, "a", "1.0.0", &["b[extra]"])?;
+    write_dist_info(
+        &context,
+        "b",
+        "1.0.0",
+        &["e ; extra == 'extra'", "f ; extra == 'other'"],
+    )?;
+    // `c` and `d` only depend on each other.
+    write_dist_info(&context, "c", "1.0.0", &["d"])?;
+    write_dist_info(&context, "d", "1.0.0", &["c"])?;
+    write_dist_info(&context, "e", "1.0.0", &[])?;
+    write_dist_info(&context, "f", "1.0.0", &[])?;
+
+    uv_snapshot!(context.filters(), tree_command(&context), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    a==1.0.0
+    └── b==1.0.0
+        └── e==1.0.0
+    c==1.0.0
+    └── d==1.0.0
+        └── c==1.0.0 (*)
+    f==1.0.0
+
+    ----- stderr -----
+    "###
+    );
+
+    Ok(())
+}
+
+#[test]
+fn tree_missing_package() {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(tree_command(&context)
+        .arg("--package")
+        .arg("flask"), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: Package(s) not found for: flask
+    "###
+    );
+}